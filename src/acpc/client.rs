@@ -0,0 +1,172 @@
+use super::{Connection, Error, Game, MatchState, Move, VERSION};
+use crate::player::{Agent, View};
+use crate::table::{state::State, structure::Legal, Config, Street, Summary};
+use std::net::{TcpStream, ToSocketAddrs};
+
+/// Plays an `Agent` in a match dealt by a dealer speaking the protocol.
+#[derive(Debug)]
+pub struct Client {
+    game: Game,
+    config: Config,
+    connection: Connection,
+}
+
+/// A hand replayed from what a player is told, positions being seats and
+/// the button on the last one.
+struct Replay {
+    state: State,
+    /// The seat to act, if any.
+    to_act: Option<usize>,
+    over: bool,
+}
+
+impl Client {
+    /// Connects to the dealer at `address`, for a match of `game`.
+    ///
+    /// Returns `Error::Unsupported` if a `Table` can't deal the game.
+    pub fn connect<A: ToSocketAddrs>(address: A, game: Game) -> Result<Client, Error> {
+        let config = game.config()?;
+        let mut connection = Connection::new(TcpStream::connect(address)?)?;
+        connection.send(VERSION)?;
+
+        Ok(Client {
+            game,
+            config,
+            connection,
+        })
+    }
+
+    /// Plays with `agent` until the dealer hangs up, and returns the number
+    /// of hands played to the end.
+    ///
+    /// The agent learns about every hand as from a `Table`, its seat being
+    /// its position and the button on the last position.
+    pub fn play(&mut self, agent: &mut dyn Agent) -> Result<u64, Error> {
+        let (mut started, mut ended) = (None, None);
+        let mut hands = 0;
+
+        while let Some(line) = self.connection.receive()? {
+            let told: MatchState = line.parse()?;
+            let replay = self.replay(&told)?;
+            let seat = told.position;
+
+            if started != Some(told.hand) {
+                started = Some(told.hand);
+                agent.hand_start(&self.view(&told, &replay.state, Legal::default()));
+            }
+
+            if replay.over {
+                if ended != Some(told.hand) {
+                    ended = Some(told.hand);
+                    hands += 1;
+                    self.end(agent, &told, replay.state)?;
+                }
+            } else if replay.to_act == Some(seat) {
+                let legal = replay.state.legal(&self.config, seat);
+                let action = agent.decide(&self.view(&told, &replay.state, legal));
+                let info = &replay.state.seats[seat];
+                let action = Move::new(action, info.tot_bet - info.bet, self.game.kind);
+                self.connection.send(&format!("{}:{}", line, action))?;
+            }
+        }
+
+        Ok(hands)
+    }
+
+    /// Replays the hand `told` about.
+    fn replay(&self, told: &MatchState) -> Result<Replay, Error> {
+        let players = self.game.players;
+        if told.holes.len() != players || told.betting.len() > self.game.rounds {
+            return Err(Error::Protocol(format!("'{}' isn't of the game", told)));
+        }
+
+        let button = players - 1;
+        let mut state = State::new(self.game.stacks.iter().copied());
+        let (_, mut last) = state.post_blinds(&self.config, button);
+        let streets = [Street::Preflop, Street::Flop, Street::Turn, Street::River];
+
+        for (round, moves) in told.betting.iter().enumerate() {
+            if round > 0 {
+                let cards = told.board.get(round - 1).ok_or_else(|| {
+                    Error::Protocol(format!("'{}' doesn't deal round {}", told, round))
+                })?;
+                state.board.extend(cards);
+                state.new_street(streets[round]);
+                last = button;
+            }
+
+            for &action in moves {
+                let seat = state.to_act(last).ok_or_else(|| {
+                    Error::Protocol(format!("'{}' has an action out of turn", told))
+                })?;
+                let legal = state.legal(&self.config, seat);
+                let info = &state.seats[seat];
+                let to_match = state.seats.iter().map(|seat| seat.bet).max().unwrap_or(0);
+                let action = action.action(info.tot_bet - info.bet, to_match, &legal);
+                state.act(&self.config, seat, action)?;
+                last = seat;
+            }
+        }
+
+        let to_act = state.to_act(last);
+        let over =
+            state.in_hand() < 2 || (to_act.is_none() && told.betting.len() == self.game.rounds);
+        Ok(Replay {
+            state,
+            to_act,
+            over,
+        })
+    }
+
+    fn view<'a>(&self, told: &'a MatchState, state: &'a State, legal: Legal) -> View<'a> {
+        View {
+            seat: told.position,
+            button: self.game.players - 1,
+            street: state.street,
+            big_blind: self.config.big_blind,
+            hole: &told.holes[told.position],
+            board: &state.board,
+            pot: state.pot(),
+            seats: &state.seats,
+            history: &state.history,
+            legal,
+        }
+    }
+
+    /// Tells `agent` how the hand `told` about ended.
+    fn end(&self, agent: &mut dyn Agent, told: &MatchState, mut state: State) -> Result<(), Error> {
+        let hidden = (0..told.holes.len()).any(|seat| {
+            state.seats[seat].in_hand() && told.holes[seat].len() != self.game.hole_cards as usize
+        });
+        if state.in_hand() > 1 && (hidden || state.board.len() != 5) {
+            return Err(Error::Protocol(format!(
+                "'{}' doesn't show the hands",
+                told
+            )));
+        }
+
+        let showdown = state.showdown(&told.holes);
+        if !showdown.shown.is_empty() {
+            agent.showdown(&self.view(told, &state, Legal::default()), &showdown.shown);
+        }
+
+        let button = self.game.players - 1;
+        let award = state.award(button, &showdown.values);
+        let summary = Summary {
+            button,
+            stacks: self.game.stacks.clone(),
+            history: state.history.clone(),
+            board: state.board.clone(),
+            shown: showdown.shown,
+            won: award.won,
+            net: state
+                .seats
+                .iter()
+                .zip(&self.game.stacks)
+                .map(|(info, &stack)| i64::from(info.stack) - i64::from(stack))
+                .collect(),
+        };
+        agent.hand_end(&self.view(told, &state, Legal::default()), &summary);
+        Ok(())
+    }
+}
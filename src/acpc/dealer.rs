@@ -0,0 +1,232 @@
+use super::{state, Connection, Error, Game, MatchState, Move};
+use crate::card::Card;
+use crate::player::{Action, Agent, View};
+use crate::table::{Config, Summary, Table};
+use std::cell::RefCell;
+use std::net::TcpListener;
+use std::rc::Rc;
+
+/// Deals a match of a game to players connecting over TCP, each on a port of
+/// its own.
+#[derive(Debug)]
+pub struct Dealer {
+    game: Game,
+    config: Config,
+    listeners: Vec<TcpListener>,
+    ports: Vec<u16>,
+}
+
+/// What the seats of a match share.
+#[derive(Debug)]
+struct Hub {
+    game: Game,
+    config: Config,
+    /// The connection of the player at each seat.
+    connections: Vec<Connection>,
+    hand: u64,
+    button: usize,
+    holes: Vec<Vec<Card>>,
+    /// The first thing to go wrong, which ends the match after the hand.
+    error: Option<Error>,
+}
+
+/// Plays a seat by asking the player connected to it.
+#[derive(Debug)]
+struct Remote {
+    seat: usize,
+    hub: Rc<RefCell<Hub>>,
+}
+
+impl Dealer {
+    /// Constructs a dealer of `game` listening on localhost.
+    ///
+    /// Returns `Error::Unsupported` if a `Table` can't deal the game.
+    pub fn bind(game: Game) -> Result<Dealer, Error> {
+        let config = game.config()?;
+        let mut listeners = Vec::with_capacity(game.players);
+        let mut ports = Vec::with_capacity(game.players);
+        for _ in 0..game.players {
+            let listener = TcpListener::bind(("127.0.0.1", 0))?;
+            ports.push(listener.local_addr()?.port());
+            listeners.push(listener);
+        }
+
+        Ok(Dealer {
+            game,
+            config,
+            listeners,
+            ports,
+        })
+    }
+
+    /// Returns the port each player connects to.
+    pub fn ports(&self) -> Vec<u16> {
+        self.ports.clone()
+    }
+
+    /// Waits for every player to connect, then deals `hands` hands from
+    /// `seed`, and returns the chips each player won or lost.
+    ///
+    /// Players sit at the seats of a `Table` in the order of their ports,
+    /// and their positions move round with the button. Actions that aren't
+    /// legal are made legal, a raise too large going all in for instance.
+    pub fn run(self, hands: u64, seed: u64) -> Result<Vec<i64>, Error> {
+        let Dealer {
+            game,
+            config,
+            listeners,
+            ..
+        } = self;
+
+        let mut connections = Vec::with_capacity(listeners.len());
+        for listener in &listeners {
+            let mut connection = Connection::new(listener.accept()?.0)?;
+            match connection.receive()? {
+                Some(ref version) if version.starts_with("VERSION:2.") => (),
+                version => {
+                    return Err(Error::Protocol(format!(
+                        "expected the version, got {:?}",
+                        version
+                    )))
+                }
+            }
+            connections.push(connection);
+        }
+
+        let (players, stack) = (game.players, game.stacks[0]);
+        let hub = Rc::new(RefCell::new(Hub {
+            game,
+            config,
+            connections,
+            hand: 0,
+            button: 0,
+            holes: vec![Vec::new(); players],
+            error: None,
+        }));
+        let mut agents: Vec<Box<dyn Agent>> = (0..players)
+            .map(|seat| -> Box<dyn Agent> {
+                Box::new(Remote {
+                    seat,
+                    hub: Rc::clone(&hub),
+                })
+            })
+            .collect();
+
+        let mut table = Table::new_seeded(config, &vec![stack; players], seed);
+        let mut net = vec![0; players];
+        for hand in 0..hands {
+            for player in table.players_mut() {
+                player.pot = stack;
+            }
+            hub.borrow_mut().hand = hand;
+
+            let summary = table.play(&mut agents)?;
+            if let Some(error) = hub.borrow_mut().error.take() {
+                return Err(error);
+            }
+            for (net, won) in net.iter_mut().zip(summary.net) {
+                *net += won;
+            }
+        }
+
+        Ok(net)
+    }
+}
+
+impl Hub {
+    /// Returns the state of the hand told to the player at `seat`, with the
+    /// `shown` cards of the other seats.
+    fn state(&self, view: &View, seat: usize, shown: &[(usize, Vec<Card>)]) -> MatchState {
+        let players = self.game.players;
+        let holes = (0..players)
+            .map(|position| match (self.button + 1 + position) % players {
+                other if other == seat => self.holes[seat].clone(),
+                other => shown
+                    .iter()
+                    .find(|&&(shown, _)| shown == other)
+                    .map_or_else(Vec::new, |(_, cards)| cards.clone()),
+            })
+            .collect();
+
+        let mut board = Vec::new();
+        let mut dealt = 0;
+        for &cards in &self.game.board_cards[1..] {
+            let cards = cards as usize;
+            if dealt + cards > view.board.len() {
+                break;
+            }
+            board.push(view.board[dealt..dealt + cards].to_vec());
+            dealt += cards;
+        }
+
+        MatchState {
+            position: (seat + players - self.button - 1) % players,
+            hand: self.hand,
+            betting: state::betting(
+                view.history,
+                self.game.kind,
+                self.config.big_blind,
+                board.len() + 1,
+            ),
+            holes,
+            board,
+        }
+    }
+
+    /// Tells every player the state of the hand, and returns the action of
+    /// the one at `seat`.
+    fn ask(&mut self, view: &View, seat: usize) -> Result<Action, Error> {
+        let mut asked = String::new();
+        for other in 0..self.game.players {
+            let state = self.state(view, other, &[]).to_string();
+            self.connections[other].send(&state)?;
+            if other == seat {
+                asked = state;
+            }
+        }
+
+        let reply = self.connections[seat]
+            .receive()?
+            .ok_or_else(|| Error::Protocol(format!("seat {} hung up", seat)))?;
+        let action: Move = match reply.rfind(':') {
+            Some(at) if reply[..at] == asked => reply[at + 1..].parse()?,
+            _ => {
+                return Err(Error::Protocol(format!(
+                    "'{}' doesn't answer '{}'",
+                    reply, asked
+                )))
+            }
+        };
+
+        let info = &view.seats[seat];
+        Ok(action.action(info.tot_bet - info.bet, view.to_match(), &view.legal))
+    }
+}
+
+impl Agent for Remote {
+    fn decide(&mut self, view: &View) -> Action {
+        let mut hub = self.hub.borrow_mut();
+        if hub.error.is_some() {
+            return view.check_or_call();
+        }
+
+        hub.ask(view, self.seat).unwrap_or_else(|error| {
+            hub.error = Some(error);
+            view.check_or_call()
+        })
+    }
+
+    fn hand_start(&mut self, view: &View) {
+        let mut hub = self.hub.borrow_mut();
+        hub.button = view.button;
+        hub.holes[self.seat] = view.hole.to_vec();
+    }
+
+    fn hand_end(&mut self, view: &View, summary: &Summary) {
+        let mut hub = self.hub.borrow_mut();
+        let state = hub.state(view, self.seat, &summary.shown).to_string();
+        if let Err(error) = hub.connections[self.seat].send(&state) {
+            hub.error.get_or_insert(error);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use crate::table;
+use std::{fmt, io};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A game definition, match state or action couldn't be read.
+    Parse(String),
+    /// A game `Table` can't deal, such as one with antes or three hole
+    /// cards.
+    Unsupported(String),
+    /// A match state breaks the rules of the game.
+    Table(table::Error),
+    /// The other side didn't follow the protocol.
+    Protocol(String),
+    /// Talking to the other side failed.
+    Io(io::ErrorKind),
+}
+
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(what) => write!(f, "can't parse {}", what),
+            Error::Unsupported(what) => write!(f, "{} isn't supported", what),
+            Error::Table(error) => write!(f, "{}", error),
+            Error::Protocol(what) => write!(f, "protocol error: {}", what),
+            Error::Io(kind) => write!(f, "connection failed: {:?}", kind),
+        }
+    }
+}
+
+impl From<table::Error> for Error {
+    fn from(error: table::Error) -> Self {
+        Error::Table(error)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error.kind())
+    }
+}
@@ -0,0 +1,355 @@
+use super::{Error, Game, Kind};
+use crate::deck::Deck;
+use crate::table::{
+    structure::{Betting, FixedLimit},
+    Config,
+};
+use std::{fmt, str::FromStr};
+
+/// Chips of every position in games that don't say, as the competition's
+/// dealer gives them.
+const STACK: u32 = i32::MAX as u32;
+
+impl Game {
+    /// Constructs the definition of hold'em played at a `Table` with
+    /// `config`, by `players` starting every hand with `stack` chips.
+    ///
+    /// # Example
+    /// ```
+    /// # use aces_high::{acpc::*, table::Config};
+    /// let game = Game::from_config(&Config::no_limit(50, 100), 2, 20_000)?;
+    /// assert_eq!(game.blinds, [100, 50]);
+    /// assert!(game.to_string().contains("firstPlayer = 2 1 1 1"));
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn from_config(config: &Config, players: usize, stack: u32) -> Result<Game, Error> {
+        if config.ante > 0 {
+            return Err(Error::Unsupported("an ante".to_string()));
+        }
+        if players < 2 {
+            return Err(Error::Unsupported(format!("a game of {} players", players)));
+        }
+
+        let (kind, raise_sizes, max_raises) = match config.betting {
+            Betting::NoLimit => (Kind::NoLimit, Vec::new(), Vec::new()),
+            // The cap counts the big blind, the protocol doesn't.
+            Betting::FixedLimit(limit) if limit.cap > 0 => (
+                Kind::Limit,
+                vec![
+                    limit.small_bet,
+                    limit.small_bet,
+                    limit.big_bet,
+                    limit.big_bet,
+                ],
+                vec![limit.cap - 1, limit.cap, limit.cap, limit.cap],
+            ),
+            betting => return Err(Error::Unsupported(format!("{:?} betting", betting))),
+        };
+
+        // Heads up, the big blind is first after the button.
+        let (blinds, first) = if players == 2 {
+            (vec![config.big_blind, config.small_blind], 1)
+        } else {
+            let mut blinds = vec![0; players];
+            blinds[0] = config.small_blind;
+            blinds[1] = config.big_blind;
+            (blinds, 2)
+        };
+
+        Ok(Game {
+            kind,
+            players,
+            rounds: 4,
+            stacks: vec![stack; players],
+            blinds,
+            raise_sizes,
+            first_player: vec![first, 0, 0, 0],
+            max_raises,
+            suits: 4,
+            ranks: 13,
+            hole_cards: 2,
+            board_cards: vec![0, 3, 1, 1],
+        })
+    }
+
+    /// Returns the configuration of a `Table` dealing this game.
+    ///
+    /// Returns `Error::Unsupported` unless the game is hold'em, with equal
+    /// stacks, and blinds and betting as `Game::from_config` gives them.
+    pub fn config(&self) -> Result<Config, Error> {
+        let unsupported = |what: &str| Err(Error::Unsupported(what.to_string()));
+
+        if (self.suits, self.ranks, self.hole_cards) != (4, 13, 2)
+            || self.board_cards != [0, 3, 1, 1]
+        {
+            return unsupported("a game other than hold'em");
+        }
+        if self.players < 2 || self.stacks.iter().any(|&stack| stack != self.stacks[0]) {
+            return unsupported("unequal stacks");
+        }
+
+        let (small_blind, big_blind) = match self.blinds[..] {
+            [big, small] => (small, big),
+            [small, big, ..] => (small, big),
+            _ => return unsupported("a game without blinds"),
+        };
+        let betting = match self.kind {
+            Kind::NoLimit => Betting::NoLimit,
+            Kind::Limit => match (self.raise_sizes.first(), self.raise_sizes.get(2)) {
+                (Some(&small_bet), Some(&big_bet)) => Betting::FixedLimit(FixedLimit {
+                    small_bet,
+                    big_bet,
+                    cap: self.max_raises.get(1).copied().unwrap_or(0),
+                }),
+                _ => return unsupported("limit betting without sizes"),
+            },
+        };
+        let config = Config {
+            betting,
+            ..Config::no_limit(small_blind, big_blind)
+        };
+
+        // Everything else has to be as a table plays it.
+        match Game::from_config(&config, self.players, self.stacks[0]) {
+            Ok(ref game) if game == self => Ok(config),
+            _ => unsupported("blinds, bets or turns other than a table's"),
+        }
+    }
+
+    /// Returns a sorted deck of the game's cards.
+    pub fn deck(&self) -> Result<Deck, Error> {
+        if (self.suits, self.ranks) == (4, 13) {
+            Ok(Deck::new_sorted())
+        } else {
+            Err(Error::Unsupported(format!(
+                "a deck of {} suits of {} ranks",
+                self.suits, self.ranks
+            )))
+        }
+    }
+}
+
+/// Writes `key = values` on a line, unless there are no values.
+fn line<T: fmt::Display>(f: &mut fmt::Formatter<'_>, key: &str, values: &[T]) -> fmt::Result {
+    if values.is_empty() {
+        return Ok(());
+    }
+    write!(f, "{} =", key)?;
+    for value in values {
+        write!(f, " {}", value)?;
+    }
+    writeln!(f)
+}
+
+impl fmt::Display for Game {
+    /// Formats the game as the competition's `.game` files.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "GAMEDEF")?;
+        match self.kind {
+            Kind::Limit => writeln!(f, "limit")?,
+            Kind::NoLimit => writeln!(f, "nolimit")?,
+        }
+        line(f, "numPlayers", &[self.players])?;
+        line(f, "numRounds", &[self.rounds])?;
+        if self.stacks.iter().any(|&stack| stack != STACK) {
+            line(f, "stack", &self.stacks)?;
+        }
+        line(f, "blind", &self.blinds)?;
+        line(f, "raiseSize", &self.raise_sizes)?;
+        let first: Vec<usize> = self.first_player.iter().map(|first| first + 1).collect();
+        line(f, "firstPlayer", &first)?;
+        line(f, "maxRaises", &self.max_raises)?;
+        line(f, "numSuits", &[self.suits])?;
+        line(f, "numRanks", &[self.ranks])?;
+        line(f, "numHoleCards", &[self.hole_cards])?;
+        line(f, "numBoardCards", &self.board_cards)?;
+        write!(f, "END GAMEDEF")
+    }
+}
+
+impl FromStr for Game {
+    type Err = Error;
+
+    /// Parses a game definition as in the competition's `.game` files.
+    /// Keys are case insensitive, and lines starting with `#` are comments.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+        let invalid = |what: String| Err(Error::Parse(format!("a game definition {}", what)));
+
+        if !lines
+            .next()
+            .map_or(false, |line| line.eq_ignore_ascii_case("GAMEDEF"))
+        {
+            return invalid("without GAMEDEF".to_string());
+        }
+
+        let mut kind = None;
+        let mut game = Game {
+            kind: Kind::Limit,
+            players: 0,
+            rounds: 0,
+            stacks: Vec::new(),
+            blinds: Vec::new(),
+            raise_sizes: Vec::new(),
+            first_player: Vec::new(),
+            max_raises: Vec::new(),
+            suits: 0,
+            ranks: 0,
+            hole_cards: 0,
+            board_cards: Vec::new(),
+        };
+        let mut ended = false;
+
+        for line in lines {
+            let bad = || Error::Parse(format!("'{}'", line));
+            if line.eq_ignore_ascii_case("END GAMEDEF") {
+                ended = true;
+                break;
+            } else if line.eq_ignore_ascii_case("limit") {
+                kind = Some(Kind::Limit);
+                continue;
+            } else if line.eq_ignore_ascii_case("nolimit") {
+                kind = Some(Kind::NoLimit);
+                continue;
+            }
+
+            let mut parts = line.splitn(2, '=');
+            let key = parts.next().unwrap().trim().to_ascii_lowercase();
+            let values: Vec<u32> = parts
+                .next()
+                .ok_or_else(bad)?
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<Result<_, _>>()
+                .map_err(|_| bad())?;
+            let one = || match values[..] {
+                [value] => Ok(value),
+                _ => Err(bad()),
+            };
+
+            match &key[..] {
+                "numplayers" => game.players = one()? as usize,
+                "numrounds" => game.rounds = one()? as usize,
+                "stack" => game.stacks = values,
+                "blind" => game.blinds = values,
+                "raisesize" => game.raise_sizes = values,
+                "firstplayer" => {
+                    game.first_player = values
+                        .iter()
+                        .map(|&first| (first as usize).checked_sub(1))
+                        .collect::<Option<_>>()
+                        .ok_or_else(bad)?
+                }
+                "maxraises" => game.max_raises = values,
+                "numsuits" => game.suits = one()?,
+                "numranks" => game.ranks = one()?,
+                "numholecards" => game.hole_cards = one()?,
+                "numboardcards" => game.board_cards = values,
+                _ => return Err(bad()),
+            }
+        }
+
+        if !ended {
+            return invalid("without END GAMEDEF".to_string());
+        }
+        game.kind = match kind {
+            Some(kind) => kind,
+            None => return invalid("without limit or nolimit".to_string()),
+        };
+        if game.players < 2 || game.rounds == 0 || game.rounds > 4 {
+            return invalid(format!(
+                "of {} players and {} rounds",
+                game.players, game.rounds
+            ));
+        }
+
+        // What the competition's dealer defaults to.
+        if game.stacks.is_empty() {
+            game.stacks = vec![STACK; game.players];
+        }
+        if game.blinds.is_empty() {
+            game.blinds = vec![0; game.players];
+        }
+        if game.first_player.is_empty() {
+            game.first_player = vec![0; game.rounds];
+        }
+
+        let lists = [
+            ("stack", game.stacks.len(), game.players),
+            ("blind", game.blinds.len(), game.players),
+            ("firstPlayer", game.first_player.len(), game.rounds),
+            ("numBoardCards", game.board_cards.len(), game.rounds),
+        ];
+        for &(key, len, expected) in &lists {
+            if len != expected {
+                return invalid(format!("with {} {} for {}", len, key, expected));
+            }
+        }
+        if game.kind == Kind::Limit && game.raise_sizes.len() != game.rounds {
+            return invalid("without a raiseSize for every round".to_string());
+        }
+        if !game.max_raises.is_empty() && game.max_raises.len() != game.rounds {
+            return invalid("without maxRaises for every round".to_string());
+        }
+        if game.first_player.iter().any(|&first| first >= game.players) {
+            return invalid("with a firstPlayer who isn't playing".to_string());
+        }
+
+        Ok(game)
+    }
+}
+
+#[test]
+fn definitions() {
+    let limit = "\
+# Heads up limit hold'em.
+GAMEDEF
+limit
+numPlayers = 2
+numRounds = 4
+blind = 10 5
+raiseSize = 10 10 20 20
+firstPlayer = 2 1 1 1
+maxRaises = 3 4 4 4
+numSuits = 4
+numRanks = 13
+numHoleCards = 2
+numBoardCards = 0 3 1 1
+END GAMEDEF
+";
+    let game: Game = limit.parse().unwrap();
+    assert_eq!(game.stacks, [STACK, STACK]);
+    assert_eq!(format!("# Heads up limit hold'em.\n{}\n", game), limit);
+    assert_eq!(
+        game.config(),
+        Ok(Config {
+            betting: Betting::FixedLimit(FixedLimit::new(10)),
+            ..Config::no_limit(5, 10)
+        })
+    );
+
+    let no_limit = "gamedef\nNOLIMIT\nnumplayers = 3\nnumrounds = 4\nstack = 200 200 200\n\
+                    blind = 1 2 0\nfirstplayer = 3 1 1 1\nnumsuits = 4\nnumranks = 13\n\
+                    numholecards = 2\nnumboardcards = 0 3 1 1\nend gamedef";
+    let game: Game = no_limit.parse().unwrap();
+    assert_eq!(
+        Game::from_config(&Config::no_limit(1, 2), 3, 200),
+        Ok(game.clone())
+    );
+    assert_eq!(game.config(), Ok(Config::no_limit(1, 2)));
+
+    let kuhn = no_limit.replace("numranks = 13", "numranks = 3");
+    assert!(kuhn.parse::<Game>().unwrap().config().is_err());
+    for broken in &[
+        no_limit.replace("end gamedef", ""),
+        no_limit.replace("NOLIMIT", ""),
+        no_limit.replace("= 200 200 200", "= 200 200"),
+        no_limit.replace("= 3 1 1 1", "= 0 1 1 1"),
+        no_limit.replace("numrounds", "numrnds"),
+    ] {
+        assert!(matches!(broken.parse::<Game>(), Err(Error::Parse(_))));
+    }
+}
@@ -0,0 +1,221 @@
+//! The text protocol of the Annual Computer Poker Competition, so that
+//! agents can play matches against bots of other programs.
+//!
+//! A match is played under a game definition, a `Game`. The dealer sends
+//! every player a `MatchState` after every action, each player seeing only
+//! its own cards until the showdown, and the player to act answers with the
+//! state followed by its action:
+//!
+//! ```text
+//! MATCHSTATE:0:30:cr300:9s8h|
+//! MATCHSTATE:0:30:cr300:9s8h|:c
+//! ```
+//!
+//! Positions in a hand are counted from the seat after the button, so heads
+//! up the big blind is position 0. Raises give the chips put in over the
+//! whole hand, not just on the street. Stacks start over every hand.
+//!
+//! `Dealer` plays a match on a `Table`, with the players connecting over TCP
+//! on localhost, and `Client` connects an `Agent` to a dealer, this one or
+//! that of the competition.
+//!
+//! # Example
+//! ```
+//! # use aces_high::{acpc::*, bot::*, table::Config};
+//! # use std::thread;
+//! let game = Game::from_config(&Config::no_limit(50, 100), 2, 20_000)?;
+//! let dealer = Dealer::bind(game.clone())?;
+//! let ports = dealer.ports();
+//! let match_ = thread::spawn(move || dealer.run(10, 1));
+//!
+//! let clients: Vec<_> = ports
+//!     .into_iter()
+//!     .map(|port| {
+//!         let game = game.clone();
+//!         thread::spawn(move || {
+//!             let mut client = Client::connect(("127.0.0.1", port), game)?;
+//!             client.play(&mut Tag::default())
+//!         })
+//!     })
+//!     .collect();
+//! for client in clients {
+//!     assert_eq!(client.join().unwrap()?, 10);
+//! }
+//! let net = match_.join().unwrap()?;
+//! assert_eq!(net[0] + net[1], 0);
+//! # Ok::<(), Error>(())
+//! ```
+mod client;
+mod dealer;
+pub mod error;
+mod game;
+mod state;
+
+pub use client::Client;
+pub use dealer::Dealer;
+pub use error::Error;
+
+use crate::card::Card;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::TcpStream;
+
+/// The version of the protocol spoken.
+pub const VERSION: &str = "VERSION:2.0.0";
+
+/// Whether bets have a fixed size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Kind {
+    Limit,
+    NoLimit,
+}
+
+/// A game definition, as in the competition's `.game` files.
+///
+/// Lists have an entry for each position or each round. `Game::config`
+/// maps the ones `Table` can deal onto a `Config`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Game {
+    pub kind: Kind,
+    pub players: usize,
+    pub rounds: usize,
+    /// Chips of each position at the start of every hand.
+    pub stacks: Vec<u32>,
+    /// Blinds posted by each position.
+    pub blinds: Vec<u32>,
+    /// Size of the bets and raises of each round, in limit games.
+    pub raise_sizes: Vec<u32>,
+    /// The position first to act in each round.
+    pub first_player: Vec<usize>,
+    /// The most bets and raises in each round, without limit if empty.
+    pub max_raises: Vec<u32>,
+    pub suits: u32,
+    pub ranks: u32,
+    pub hole_cards: u32,
+    /// Cards dealt to the board at the start of each round.
+    pub board_cards: Vec<u32>,
+}
+
+/// An action as the protocol writes it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Move {
+    /// `f`
+    Fold,
+    /// `c`, a check or a call.
+    Call,
+    /// `r`, a bet or raise, to the chips put in over the hand in no limit
+    /// games, as `r300`.
+    Raise(Option<u32>),
+}
+
+/// What a player is told about a hand.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatchState {
+    /// The position of the player told.
+    pub position: usize,
+    /// The number of the hand in the match, from 0.
+    pub hand: u64,
+    /// The actions of each round dealt so far.
+    pub betting: Vec<Vec<Move>>,
+    /// The hole cards of each position, empty when unseen.
+    pub holes: Vec<Vec<Card>>,
+    /// The board cards of each round after the first.
+    pub board: Vec<Vec<Card>>,
+}
+
+/// Lines sent and received over TCP, ending in `\r\n`.
+#[derive(Debug)]
+struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Connection {
+    fn new(stream: TcpStream) -> io::Result<Connection> {
+        // Every line is answered before the next is sent.
+        stream.set_nodelay(true)?;
+        Ok(Connection {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        })
+    }
+
+    fn send(&mut self, line: &str) -> Result<(), Error> {
+        write!(self.writer, "{}\r\n", line)?;
+        Ok(())
+    }
+
+    /// Returns the next line which isn't a comment, or `None` once the other
+    /// side hangs up.
+    fn receive(&mut self) -> Result<Option<String>, Error> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            let line = line.trim_end();
+            if !line.is_empty() && !line.starts_with('#') && !line.starts_with(';') {
+                return Ok(Some(line.to_string()));
+            }
+        }
+    }
+}
+
+#[test]
+fn against_table() {
+    use crate::bot::{Random, Tag};
+    use crate::player::Agent;
+    use crate::table::{structure::*, Config, Table};
+    use std::thread;
+
+    let limit = Config {
+        betting: Betting::FixedLimit(FixedLimit::new(10)),
+        ..Config::no_limit(5, 10)
+    };
+    for &(config, players) in &[(Config::no_limit(50, 100), 3), (limit, 2)] {
+        let game = Game::from_config(&config, players, 2_000).unwrap();
+        assert_eq!(game.to_string().parse(), Ok(game.clone()));
+        assert_eq!(game.config(), Ok(config));
+
+        let agent = |seat: usize| -> Box<dyn Agent> {
+            if seat == 0 {
+                Box::new(Tag::default())
+            } else {
+                Box::new(Random::new_seeded(seat as u64))
+            }
+        };
+
+        // The same match played directly.
+        let mut table = Table::new_seeded(config, &vec![2_000; players], 5);
+        let mut agents: Vec<Box<dyn Agent>> = (0..players).map(agent).collect();
+        let mut expected = vec![0; players];
+        for _ in 0..40 {
+            for player in table.players_mut() {
+                player.pot = 2_000;
+            }
+            let summary = table.play(&mut agents).unwrap();
+            for (net, won) in expected.iter_mut().zip(summary.net) {
+                *net += won;
+            }
+        }
+
+        let dealer = Dealer::bind(game.clone()).unwrap();
+        let ports = dealer.ports();
+        let dealer = thread::spawn(move || dealer.run(40, 5));
+        let clients: Vec<_> = ports
+            .into_iter()
+            .enumerate()
+            .map(|(seat, port)| {
+                let game = game.clone();
+                thread::spawn(move || {
+                    let mut client = Client::connect(("127.0.0.1", port), game).unwrap();
+                    client.play(&mut *agent(seat)).unwrap()
+                })
+            })
+            .collect();
+        for client in clients {
+            assert_eq!(client.join().unwrap(), 40);
+        }
+        assert_eq!(dealer.join().unwrap(), Ok(expected));
+    }
+}
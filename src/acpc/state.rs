@@ -0,0 +1,236 @@
+use super::{Error, Kind, MatchState, Move};
+use crate::card::Card;
+use crate::player::{Action, Decision};
+use crate::table::{structure::Legal, Street};
+use std::{cmp, fmt, str::FromStr};
+
+impl Move {
+    /// Constructs the move of a seat taking `action`, having put in
+    /// `before` chips on earlier streets.
+    pub(crate) fn new(action: Action, before: u32, kind: Kind) -> Move {
+        match action {
+            Action::Fold => Move::Fold,
+            Action::Check | Action::Call(_) => Move::Call,
+            Action::Bet(to) | Action::Raise(to) => match kind {
+                Kind::Limit => Move::Raise(None),
+                Kind::NoLimit => Move::Raise(Some(before + to)),
+            },
+        }
+    }
+
+    /// Returns the action of a seat having put in `before` chips on earlier
+    /// streets, facing a bet of `to_match`, with the `legal` actions.
+    ///
+    /// Like the competition's dealer, raises are moved within the limits,
+    /// and become calls when the seat may not raise.
+    pub(crate) fn action(self, before: u32, to_match: u32, legal: &Legal) -> Action {
+        let call = || legal.call.map_or(Action::Check, Action::Call);
+
+        match (self, legal.raise) {
+            (Move::Fold, _) => Action::Fold,
+            (Move::Call, _) | (Move::Raise(_), None) => call(),
+            (Move::Raise(total), Some(limits)) => {
+                let to = total.map_or(limits.min, |total| total.saturating_sub(before));
+                let to = cmp::max(limits.min, cmp::min(to, limits.max));
+                if to_match == 0 {
+                    Action::Bet(to)
+                } else {
+                    Action::Raise(to)
+                }
+            }
+        }
+    }
+}
+
+/// Returns the moves of each of the first `rounds` rounds of a hand, given
+/// every action taken in it.
+pub(crate) fn betting(
+    history: &[Decision],
+    kind: Kind,
+    big_blind: u32,
+    rounds: usize,
+) -> Vec<Vec<Move>> {
+    let mut betting = vec![Vec::new(); rounds];
+
+    // Whoever raises has put in what was bet on every earlier street.
+    let (mut street, mut before, mut to_match) = (Street::Preflop, 0, big_blind);
+    for decision in history {
+        if decision.street != street {
+            street = decision.street;
+            before += to_match;
+            to_match = 0;
+        }
+        betting[street as usize].push(Move::new(decision.action, before, kind));
+        if let Action::Bet(to) | Action::Raise(to) = decision.action {
+            to_match = to;
+        }
+    }
+
+    betting
+}
+
+/// Parses cards written one after the other, as `KdQc2s`.
+fn parse_cards(s: &str) -> Option<Vec<Card>> {
+    let chars: Vec<char> = s.chars().collect();
+    chars
+        .chunks(2)
+        .map(|card| card.iter().collect::<String>().parse().ok())
+        .collect()
+}
+
+fn write_cards(f: &mut fmt::Formatter<'_>, cards: &[Card]) -> fmt::Result {
+    cards.iter().try_for_each(|card| write!(f, "{:?}", card))
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Move::Fold => write!(f, "f"),
+            Move::Call => write!(f, "c"),
+            Move::Raise(None) => write!(f, "r"),
+            Move::Raise(Some(total)) => write!(f, "r{}", total),
+        }
+    }
+}
+
+impl FromStr for Move {
+    type Err = Error;
+
+    /// Parses a move such as `c` or `r300`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "f" => Ok(Move::Fold),
+            "c" => Ok(Move::Call),
+            "r" => Ok(Move::Raise(None)),
+            _ if s.starts_with('r') => s[1..]
+                .parse()
+                .map(|total| Move::Raise(Some(total)))
+                .map_err(|_| Error::Parse(format!("action '{}'", s))),
+            _ => Err(Error::Parse(format!("action '{}'", s))),
+        }
+    }
+}
+
+impl fmt::Display for MatchState {
+    /// Formats the state as the dealer sends it, such as
+    /// `MATCHSTATE:0:30:cr300c/r600f:9s8h|/KdQc2s`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MATCHSTATE:{}:{}:", self.position, self.hand)?;
+        for (round, moves) in self.betting.iter().enumerate() {
+            if round > 0 {
+                write!(f, "/")?;
+            }
+            moves
+                .iter()
+                .try_for_each(|action| write!(f, "{}", action))?;
+        }
+
+        write!(f, ":")?;
+        for (position, hole) in self.holes.iter().enumerate() {
+            if position > 0 {
+                write!(f, "|")?;
+            }
+            write_cards(f, hole)?;
+        }
+        for cards in &self.board {
+            write!(f, "/")?;
+            write_cards(f, cards)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for MatchState {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bad = || Error::Parse(format!("match state '{}'", s));
+
+        let (position, hand, betting, cards) = match s.split(':').collect::<Vec<_>>()[..] {
+            ["MATCHSTATE", position, hand, betting, cards] => (position, hand, betting, cards),
+            _ => return Err(bad()),
+        };
+        let position: usize = position.parse().map_err(|_| bad())?;
+        let hand = hand.parse().map_err(|_| bad())?;
+
+        let betting = betting
+            .split('/')
+            .map(|round| {
+                let mut moves = Vec::new();
+                let mut rest = round;
+                while !rest.is_empty() {
+                    // Every move but the first of `rest` starts with a letter.
+                    let len = rest[1..]
+                        .find(|c: char| c.is_ascii_alphabetic())
+                        .map_or(rest.len(), |at| at + 1);
+                    moves.push(rest[..len].parse().map_err(|_| bad())?);
+                    rest = &rest[len..];
+                }
+                Ok(moves)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let mut rounds = cards.split('/');
+        let holes: Vec<Vec<Card>> = rounds
+            .next()
+            .unwrap()
+            .split('|')
+            .map(parse_cards)
+            .collect::<Option<_>>()
+            .ok_or_else(bad)?;
+        let board: Vec<Vec<Card>> = rounds
+            .map(parse_cards)
+            .collect::<Option<_>>()
+            .ok_or_else(bad)?;
+        if position >= holes.len() || board.len() >= betting.len() {
+            return Err(bad());
+        }
+
+        Ok(MatchState {
+            position,
+            hand,
+            betting,
+            holes,
+            board,
+        })
+    }
+}
+
+#[test]
+fn states() {
+    for &state in &[
+        "MATCHSTATE:0:0::9s8h|",
+        "MATCHSTATE:1:30:r300c/cr900:|JdTc/9h8c4d",
+        "MATCHSTATE:0:2:crc/r:Ks7h||/2c3d4h",
+        "MATCHSTATE:1:7:r20000c///:Kh9c|AsAd/2c3d4h/5s/6s",
+    ] {
+        let parsed: MatchState = state.parse().unwrap();
+        assert_eq!(parsed.to_string(), state);
+    }
+
+    let state: MatchState = "MATCHSTATE:1:30:r300c/cr900:|JdTc/9h8c4d".parse().unwrap();
+    assert_eq!(state.position, 1);
+    assert_eq!(state.hand, 30);
+    assert_eq!(
+        state.betting,
+        [
+            vec![Move::Raise(Some(300)), Move::Call],
+            vec![Move::Call, Move::Raise(Some(900))]
+        ]
+    );
+    assert_eq!(state.holes[0], []);
+    assert_eq!(format!("{:?}", state.holes[1]), "[Jd, Tc]");
+    assert_eq!(state.board.len(), 1);
+
+    for &broken in &[
+        "MATCHSTATE:0:x::|",
+        "MATCHSTATE:0:1:cq:|",
+        "MATCHSTATE:0:1:r30x:|",
+        "MATCHSTATE:0:1:c:Zz|",
+        "MATCHSTATE:2:1:c:|",
+        "MATCHSTATE:0:1:c:|/2c3d4h",
+        "MATCHSTATE:0:1:c:|:c",
+    ] {
+        assert!(broken.parse::<MatchState>().is_err(), "{}", broken);
+    }
+}
@@ -0,0 +1,24 @@
+use crate::table;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A match needs at least two agents.
+    NotEnoughAgents { agents: usize },
+    /// The table rejected a hand. `agent` is the name of the agent in the
+    /// seat of an illegal action.
+    Table { agent: String, error: table::Error },
+}
+
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotEnoughAgents { agents } => {
+                write!(f, "need at least two agents, not {}", agents)
+            }
+            Error::Table { agent, error } => write!(f, "{}: {}", agent, error),
+        }
+    }
+}
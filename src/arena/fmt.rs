@@ -0,0 +1,22 @@
+use super::{Report, Standing};
+use std::fmt;
+
+impl fmt::Display for Standing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {:+.2} ± {:.2} bb/100 ({:+} chips)",
+            self.name, self.bb_per_100, self.interval, self.net
+        )
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} hands", self.hands)?;
+        for standing in &self.standings {
+            write!(f, "\n{}", standing)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,177 @@
+use super::{Arena, Error, Report, Standing, TABLES};
+use crate::player::Agent;
+use crate::table::{self, Config, Table};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rayon::prelude::*;
+
+/// Running sums of what an agent won over blocks of hands.
+#[derive(Clone, Copy, Debug, Default)]
+struct Tally {
+    blocks: u64,
+    net: i64,
+    squares: f64,
+}
+
+impl Tally {
+    fn add(&mut self, net: i64) {
+        self.blocks += 1;
+        self.net += net;
+        self.squares += (net as f64).powi(2);
+    }
+
+    fn merge(&mut self, other: &Tally) {
+        self.blocks += other.blocks;
+        self.net += other.net;
+        self.squares += other.squares;
+    }
+}
+
+impl Arena {
+    /// Constructs an `Arena` without agents, which plays `TABLES` tables
+    /// in parallel whatever the machine, so that a seed gives the same
+    /// results everywhere.
+    pub fn new(config: Config, stack: u32) -> Self {
+        Arena {
+            config,
+            stack,
+            duplicate: false,
+            tables: TABLES,
+            seed: 0,
+            entrants: Vec::new(),
+        }
+    }
+
+    /// Adds an agent under `name`. Every table builds its own agent with
+    /// `make`, from a seed it may use for its own randomness.
+    pub fn add<F>(&mut self, name: &str, make: F)
+    where
+        F: Fn(u64) -> Box<dyn Agent> + Send + Sync + 'static,
+    {
+        self.entrants.push((name.to_string(), Box::new(make)));
+    }
+
+    /// Plays at least `hands` hands, spread over all tables. In duplicate
+    /// mode the count is rounded up to whole deals, which are one hand per
+    /// agent.
+    ///
+    /// Returns an error if fewer than two agents were added, or if an agent
+    /// picks an illegal action.
+    pub fn run(&self, hands: u64) -> Result<Report, Error> {
+        let agents = self.entrants.len();
+        if agents < 2 {
+            return Err(Error::NotEnoughAgents { agents });
+        }
+
+        let rotations = self.rotations() as u64;
+        let blocks = (hands + rotations - 1) / rotations;
+        let tables = self.tables.max(1) as u64;
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let work: Vec<(u64, u64)> = (0..tables)
+            .map(|table| {
+                (
+                    blocks / tables + u64::from(table < blocks % tables),
+                    rng.gen(),
+                )
+            })
+            .collect();
+
+        let tallies = work
+            .into_par_iter()
+            .map(|(blocks, seed)| self.play_table(blocks, seed))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let mut total = vec![Tally::default(); agents];
+        for tally in &tallies {
+            for (total, tally) in total.iter_mut().zip(tally) {
+                total.merge(tally);
+            }
+        }
+
+        let big_blind = f64::from(self.config.big_blind.max(1));
+        let per_100 = 100.0 / rotations as f64 / big_blind;
+
+        let standings = self
+            .entrants
+            .iter()
+            .zip(&total)
+            .map(|((name, _), tally)| {
+                let n = tally.blocks.max(1) as f64;
+                let mean = tally.net as f64 / n;
+                let variance = if tally.blocks > 1 {
+                    ((tally.squares - n * mean * mean) / (n - 1.0)).max(0.0)
+                } else {
+                    0.0
+                };
+
+                Standing {
+                    name: name.clone(),
+                    net: tally.net,
+                    bb_per_100: mean * per_100,
+                    interval: 1.96 * (variance / n).sqrt() * per_100,
+                }
+            })
+            .collect();
+
+        Ok(Report {
+            hands: blocks * rotations,
+            standings,
+        })
+    }
+
+    /// Returns the number of hands each deal is played.
+    fn rotations(&self) -> usize {
+        if self.duplicate {
+            self.entrants.len()
+        } else {
+            1
+        }
+    }
+
+    /// Plays `blocks` deals at one table and tallies them by agent.
+    fn play_table(&self, blocks: u64, seed: u64) -> Result<Vec<Tally>, Error> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let seats = self.entrants.len();
+        let stacks = vec![self.stack; seats];
+
+        let mut agents: Vec<Box<dyn Agent>> = self
+            .entrants
+            .iter()
+            .map(|(_, make)| make(rng.gen()))
+            .collect();
+        // The entrant sitting in each seat.
+        let mut order: Vec<usize> = (0..seats).collect();
+        let mut tallies = vec![Tally::default(); seats];
+
+        for _ in 0..blocks {
+            let deal = rng.gen();
+            let mut nets = vec![0; seats];
+
+            for _ in 0..self.rotations() {
+                let mut table = Table::new_seeded(self.config, &stacks, deal);
+                let summary = table.play(&mut agents).map_err(|error| {
+                    let agent = match error {
+                        table::Error::IllegalAction { seat, .. } => {
+                            self.entrants[order[seat]].0.clone()
+                        }
+                        _ => String::new(),
+                    };
+                    Error::Table { agent, error }
+                })?;
+
+                for (seat, net) in summary.net.iter().enumerate() {
+                    nets[order[seat]] += net;
+                }
+
+                agents.rotate_right(1);
+                order.rotate_right(1);
+            }
+
+            for (tally, &net) in tallies.iter_mut().zip(&nets) {
+                tally.add(net);
+            }
+        }
+
+        Ok(tallies)
+    }
+}
@@ -0,0 +1,137 @@
+//! Headless matches between agents, for comparing bots over many hands.
+pub mod error;
+mod fmt;
+mod r#impl;
+
+pub use error::Error;
+
+use crate::player::Agent;
+use crate::table::Config;
+
+/// The number of tables an `Arena` plays unless told otherwise.
+pub const TABLES: usize = 8;
+
+/// Builds a fresh agent for a table from a seed.
+pub type Maker = Box<dyn Fn(u64) -> Box<dyn Agent> + Send + Sync>;
+
+/// Plays agents against each other at seeded tables, in parallel.
+///
+/// Every hand is played from fresh stacks, and the agents move one seat
+/// clockwise after each hand so that all of them play every position.
+/// In duplicate mode each deal is replayed once per seat, so every agent
+/// gets dealt the same cards in the same spot, which cancels out most of
+/// the luck of the cards.
+///
+/// # Example
+/// ```
+/// # use aces_high::{arena::Arena, bot::*, table::Config};
+/// let mut arena = Arena::new(Config::no_limit(1, 2), 200);
+/// arena.add("station", |_| Box::new(Station));
+/// arena.add("tag", |_| Box::new(Tag::default()));
+/// arena.duplicate = true;
+///
+/// let report = arena.run(1_000)?;
+/// assert_eq!(report.hands, 1_000);
+/// assert_eq!(report.standings[0].net + report.standings[1].net, 0);
+/// println!("{}", report);
+/// # Ok::<(), aces_high::arena::Error>(())
+/// ```
+pub struct Arena {
+    pub config: Config,
+    /// Chips every agent starts each hand with.
+    pub stack: u32,
+    /// Replays every deal with the agents rotated through all seats.
+    pub duplicate: bool,
+    /// Number of tables played in parallel. The hands dealt depend on it,
+    /// so it has to be the same for a run to be repeated.
+    pub tables: usize,
+    /// Seeds the cards dealt and the agents built,
+    /// so that a run can be repeated exactly.
+    pub seed: u64,
+
+    entrants: Vec<(String, Maker)>,
+}
+
+/// Results of a run.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    /// Hands played at each table, summed over all tables.
+    pub hands: u64,
+    /// One per agent, in the order they were added.
+    pub standings: Vec<Standing>,
+}
+
+/// Results of one agent.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Standing {
+    pub name: String,
+    /// Chips won, or lost if negative.
+    pub net: i64,
+    /// Big blinds won per 100 hands.
+    pub bb_per_100: f64,
+    /// Half width of the 95% confidence interval of `bb_per_100`.
+    pub interval: f64,
+}
+
+#[test]
+fn duplicate_mirror() {
+    use crate::bot::Station;
+
+    let mut arena = Arena::new(Config::no_limit(1, 2), 200);
+    arena.add("one", |_| Box::new(Station));
+    arena.add("two", |_| Box::new(Station));
+    arena.add("three", |_| Box::new(Station));
+    arena.duplicate = true;
+    arena.tables = 3;
+
+    // The same strategy in every seat of every deal can't win or lose.
+    let report = arena.run(100).unwrap();
+    assert_eq!(report.hands, 102);
+    for standing in &report.standings {
+        assert_eq!(standing.net, 0);
+        assert_eq!(standing.interval, 0.0);
+    }
+}
+
+#[test]
+fn repeatable() {
+    use crate::bot::{Equity, Random, Tag};
+
+    let mut arena = Arena::new(Config::no_limit(1, 2), 200);
+    arena.add("random", |seed| Box::new(Random::new_seeded(seed)));
+    arena.add("tag", |_| Box::new(Tag::default()));
+    arena.add("equity", |seed| Box::new(Equity::new_seeded(20, seed)));
+    arena.seed = 3;
+
+    let report = arena.run(300).unwrap();
+    assert_eq!(report.hands, 300);
+    assert_eq!(report.standings.iter().map(|s| s.net).sum::<i64>(), 0);
+    assert_eq!(report, arena.run(300).unwrap());
+
+    arena.tables = 1;
+    assert_eq!(arena.run(300).unwrap().hands, 300);
+}
+
+#[test]
+fn errors() {
+    use crate::bot::Station;
+    use crate::player::{Action, View};
+
+    struct Checker;
+
+    impl Agent for Checker {
+        fn decide(&mut self, _view: &View) -> Action {
+            Action::Check
+        }
+    }
+
+    let mut arena = Arena::new(Config::no_limit(1, 2), 200);
+    arena.add("checker", |_| Box::new(Checker));
+    assert_eq!(arena.run(10), Err(Error::NotEnoughAgents { agents: 1 }));
+
+    arena.add("station", |_| Box::new(Station));
+    match arena.run(10) {
+        Err(Error::Table { agent, .. }) => assert_eq!(agent, "checker"),
+        other => panic!("{:?}", other),
+    }
+}
@@ -0,0 +1,181 @@
+use super::{chen, Equity, Maniac, Random, Station, Tag};
+use crate::hand::{equity::equity, Category, Value};
+use crate::player::{Action, Agent, View};
+use crate::table::Street;
+use mimpl::mimpl;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+/// Returns checking if it's legal, otherwise folding.
+fn check_or_fold(view: &View) -> Action {
+    if view.legal.check {
+        Action::Check
+    } else {
+        Action::Fold
+    }
+}
+
+impl Random {
+    pub fn new() -> Self {
+        Random {
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// Constructs a `Random` which makes the same choices every time it's
+    /// constructed with the same `seed` and shown the same hands.
+    pub fn new_seeded(seed: u64) -> Self {
+        Random {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+mimpl!(Default; Random, Random::new);
+
+impl Agent for Random {
+    fn decide(&mut self, view: &View) -> Action {
+        let mut actions = Vec::with_capacity(3);
+
+        if view.legal.check {
+            actions.push(Action::Check);
+        } else {
+            actions.push(Action::Fold);
+        }
+        if let Some(amount) = view.legal.call {
+            actions.push(Action::Call(amount));
+        }
+        if let Some(limits) = view.legal.raise {
+            let to = self.rng.gen_range(limits.min, limits.max + 1);
+            actions.extend(view.raise_to(to));
+        }
+
+        *actions.choose(&mut self.rng).unwrap()
+    }
+}
+
+impl Agent for Station {
+    fn decide(&mut self, view: &View) -> Action {
+        view.check_or_call()
+    }
+}
+
+impl Agent for Maniac {
+    fn decide(&mut self, view: &View) -> Action {
+        view.raise_to(view.legal.pot)
+            .unwrap_or_else(|| view.check_or_call())
+    }
+}
+
+mimpl!(Default; Tag, || Tag { open: 8, reraise: 12 });
+
+impl Tag {
+    fn preflop(&self, view: &View) -> Action {
+        let score = chen(view.hole);
+        let to_match = view.to_match();
+        let opened = to_match > view.big_blind;
+
+        let raise = if score >= self.reraise {
+            view.raise_to(3 * to_match)
+        } else if score >= self.open && !opened {
+            view.raise_to(3 * view.big_blind)
+        } else {
+            None
+        };
+
+        match raise {
+            Some(action) => action,
+            None if score >= self.open => view.check_or_call(),
+            None if score >= self.open - 2 && !opened => view.check_or_call(),
+            None => check_or_fold(view),
+        }
+    }
+
+    fn postflop(&self, view: &View) -> Action {
+        let mut cards = view.hole.to_vec();
+        cards.extend_from_slice(view.board);
+
+        // What the board makes on its own is shared with everyone.
+        let made = Value::new(&cards).category();
+        let own = made > Value::new(view.board).category();
+        let to_call = view.to_call();
+
+        if own && made >= Category::TwoPair {
+            let to = if to_call == 0 {
+                view.pot * 2 / 3
+            } else {
+                3 * view.to_match()
+            };
+            view.raise_to(to).unwrap_or_else(|| view.check_or_call())
+        } else if own && made == Category::Pair {
+            if to_call == 0 {
+                view.raise_to(view.pot / 2).unwrap_or(Action::Check)
+            } else if to_call <= view.pot / 2 {
+                view.check_or_call()
+            } else {
+                Action::Fold
+            }
+        } else {
+            check_or_fold(view)
+        }
+    }
+}
+
+impl Agent for Tag {
+    fn decide(&mut self, view: &View) -> Action {
+        match view.street {
+            Street::Preflop => self.preflop(view),
+            _ => self.postflop(view),
+        }
+    }
+}
+
+impl Equity {
+    /// Constructs an `Equity` bot dealing `samples` boards per estimate,
+    /// which bets or raises with at least 60% equity.
+    pub fn new(samples: u32) -> Self {
+        Equity {
+            samples,
+            raise: 0.6,
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// Constructs an `Equity` bot which makes the same choices every time
+    /// it's constructed with the same `seed` and shown the same hands.
+    pub fn new_seeded(samples: u32, seed: u64) -> Self {
+        Equity {
+            rng: StdRng::seed_from_u64(seed),
+            ..Equity::new(samples)
+        }
+    }
+}
+
+mimpl!(Default; Equity, || Equity::new(500));
+
+impl Agent for Equity {
+    fn decide(&mut self, view: &View) -> Action {
+        let opponents = view.in_hand() - 1;
+        let equity = equity(
+            view.hole,
+            view.board,
+            opponents,
+            self.samples,
+            &mut self.rng,
+        );
+
+        let to_call = view.to_call();
+        let odds = f64::from(to_call) / f64::from(view.pot + to_call);
+
+        if equity >= self.raise {
+            if let Some(action) = view.raise_to(view.legal.pot) {
+                return action;
+            }
+        }
+
+        if to_call == 0 || equity >= odds {
+            view.check_or_call()
+        } else {
+            Action::Fold
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Agents to play against, or to measure other agents by.
+mod r#impl;
+
+use crate::card::Card;
+use crate::hand::value::strength;
+use rand::rngs::StdRng;
+
+/// Picks any legal action at random, except folding when it may check.
+/// Bets and raises are of a random legal size.
+#[derive(Clone, Debug)]
+pub struct Random {
+    rng: StdRng,
+}
+
+/// Never folds and never raises.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Station;
+
+/// Bets or raises the size of the pot whenever it may, otherwise calls.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Maniac;
+
+/// Tight-aggressive rule-based play. Preflop it goes by the `chen` score of
+/// its hole cards, postflop by the `Category` of the hand they make.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tag {
+    /// Smallest `chen` score to raise an unopened pot with.
+    pub open: i32,
+    /// Smallest `chen` score to reraise with.
+    pub reraise: i32,
+}
+
+/// Estimates its equity against everyone left in the hand by Monte Carlo
+/// simulation, and plays by comparing it to the pot odds.
+#[derive(Clone, Debug)]
+pub struct Equity {
+    /// Number of boards dealt out for each estimate.
+    pub samples: u32,
+    /// Smallest equity to bet or raise with.
+    pub raise: f64,
+    rng: StdRng,
+}
+
+/// Returns the Chen score of two hole cards, ranging from -1 for a seven
+/// deuce offsuit to 20 for a pair of aces. Higher is better.
+///
+/// # Example
+/// ```
+/// # use aces_high::{*, bot::chen, card::face::*};
+/// assert_eq!(chen(&cards!(Ace, Spades; Ace, Hearts)), 20);
+/// assert_eq!(chen(&cards!(Ace, Spades; King, Spades)), 12);
+/// assert_eq!(chen(&cards!(Seven, Spades; Two, Hearts)), -1);
+/// ```
+///
+/// # Panics
+/// Panics if not given exactly two cards.
+pub fn chen(hole: &[Card]) -> i32 {
+    assert_eq!(hole.len(), 2, "the Chen score is for two hole cards");
+
+    let (high, low) = {
+        let (a, b) = (strength(hole[0].rank), strength(hole[1].rank));
+        (a.max(b), a.min(b))
+    };
+
+    // Counted in halves to stay in integers.
+    let mut score = match high {
+        12 => 20,
+        11 => 16,
+        10 => 14,
+        9 => 12,
+        _ => i32::from(high) + 2,
+    };
+
+    if high == low {
+        return (2 * score).max(10) / 2;
+    }
+
+    if hole[0].suit == hole[1].suit {
+        score += 4;
+    }
+
+    let gap = high - low - 1;
+    score -= match gap {
+        0 => 0,
+        1 => 2,
+        2 => 4,
+        3 => 8,
+        _ => 10,
+    };
+
+    if gap <= 1 && high < 10 {
+        score += 2;
+    }
+
+    // Rounded up.
+    (score + 1).div_euclid(2)
+}
+
+#[cfg(test)]
+fn table(agents: &mut [Box<dyn crate::player::Agent>], hands: u32, seed: u64) {
+    use crate::table::{Config, Table};
+
+    let stacks = vec![200; agents.len()];
+    let mut table = Table::new_seeded(Config::no_limit(1, 2), &stacks, seed);
+
+    for _ in 0..hands {
+        if table
+            .players()
+            .iter()
+            .filter(|player| player.pot > 0)
+            .count()
+            < 2
+        {
+            for player in table.players_mut() {
+                player.pot = 200;
+            }
+        }
+
+        let summary = table.play(agents).unwrap();
+        assert_eq!(summary.net.iter().sum::<i64>(), 0);
+    }
+}
+
+#[test]
+fn chen_scores() {
+    use crate::card::face::*;
+
+    assert_eq!(chen(&cards!(King, Spades; King, Hearts)), 16);
+    assert_eq!(chen(&cards!(Five, Spades; Five, Hearts)), 5);
+    assert_eq!(chen(&cards!(Two, Spades; Two, Hearts)), 5);
+    assert_eq!(chen(&cards!(Ten, Spades; Ace, Hearts)), 6);
+    assert_eq!(chen(&cards!(Jack, Hearts; Ten, Hearts)), 9);
+    assert_eq!(chen(&cards!(Five, Clubs; Seven, Clubs)), 6);
+}
+
+#[test]
+fn bots_play_legally() {
+    let mut agents: Vec<Box<dyn crate::player::Agent>> = vec![
+        Box::new(Random::new_seeded(1)),
+        Box::new(Station),
+        Box::new(Maniac),
+        Box::new(Tag::default()),
+        Box::new(Equity::new_seeded(50, 2)),
+        Box::new(Random::new_seeded(3)),
+    ];
+
+    table(&mut agents, 100, 4);
+    table(&mut agents[..2], 100, 5);
+    table(&mut agents[2..4], 100, 6);
+}
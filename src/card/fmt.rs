@@ -14,13 +14,15 @@ impl fmt::Display for Card {
 }
 
 impl fmt::Debug for Card {
-    /// Formats `Card` into 2 characters.
-    /// First character depicts the cards `Rank`.
-    /// Second character depicts the cards `Suit`.
+    /// Formats `Card` into 2 characters, in standard notation.
+    /// First character depicts the cards `Rank`, with `T` for the ten.
+    /// Second character depicts the cards `Suit`, in lower case.
     /// # Example
     /// ```
     /// # use aces_high::{*, card::face::*};
-    /// assert_eq!(format!("{:?}", card!(King, Diamonds)), "KD");
+    /// assert_eq!(format!("{:?}", card!(King, Diamonds)), "Kd");
+    /// assert_eq!(format!("{:?}", card!(Ten, Hearts)), "Th");
+    /// assert_eq!(format!("{:?}", card!(Two, Clubs)), "2c");
     /// ```
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let chars: [char; 2] = [self.rank.into(), self.suit.into()];
@@ -28,6 +30,8 @@ impl fmt::Debug for Card {
     }
 }
 
+/// Formats `Card` as the digits of its rank and suit, such as `C3` for the
+/// King of Spades. Use `u8::from` for an index that can be converted back.
 impl fmt::UpperHex for Card {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:X}{:X}", self.rank, self.suit)
@@ -29,6 +29,12 @@ impl Card {
     pub fn random() -> Self {
         Card { rank: Rank::random(), suit: Suit::random() }
     }
+
+    /// Returns the card with its suit relabelled by `permutation`, see
+    /// `Suit::permute`.
+    pub fn permute(self, permutation: &[Suit; 4]) -> Self {
+        Card { rank: self.rank, suit: self.suit.permute(permutation) }
+    }
 }
 
 // -------------------------------------------------------------------------- //
@@ -60,6 +66,42 @@ impl Suit {
     pub fn random() -> Self {
         Clubs.step_by(rand::random::<isize>() % 4)
     }
+
+    /// Returns every suit, in order.
+    pub fn all() -> [Suit; 4] {
+        [Clubs, Diamonds, Hearts, Spades]
+    }
+
+    /// Returns the suit relabelled by `permutation`, which lists what each
+    /// suit becomes, in order.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use aces_high::card::face::*;
+    /// let permutation = [Spades, Hearts, Diamonds, Clubs];
+    /// assert_eq!(Clubs.permute(&permutation), Spades);
+    /// assert_eq!(Hearts.permute(&permutation), Diamonds);
+    /// ```
+    pub fn permute(self, permutation: &[Suit; 4]) -> Self {
+        permutation[self as usize]
+    }
+
+    /// Returns the 24 ways to relabel the suits, the identity first.
+    pub fn permutations() -> Vec<[Suit; 4]> {
+        let mut permutations = Vec::with_capacity(24);
+        let mut permutation = Suit::all();
+        // In lexicographic order, by the next permutation each time.
+        loop {
+            permutations.push(permutation);
+            let i = match (0..3).rev().find(|&i| permutation[i] < permutation[i + 1]) {
+                Some(i) => i,
+                None => return permutations,
+            };
+            let j = (i + 1..4).rev().find(|&j| permutation[i] < permutation[j]).unwrap();
+            permutation.swap(i, j);
+            permutation[i + 1..].reverse();
+        }
+    }
 }
 
 impl Rank {
@@ -94,10 +136,10 @@ mimpl!(Default; Rank, || Ace);
 
 mimpl!(From; Suit, char, |suit: Suit|
     match suit {
-        Clubs => 'C',
-        Diamonds => 'D',
-        Hearts => 'H',
-        Spades => 'S',
+        Clubs => 'c',
+        Diamonds => 'd',
+        Hearts => 'h',
+        Spades => 's',
     }
 );
 
@@ -135,11 +177,11 @@ impl TryFrom<u8> for Rank {
 mimpl!(From; Rank, char, |rank: Rank|
     match rank {
         Ace => 'A',
-        Ten => '1',
+        Ten => 'T',
         Jack => 'J',
         Queen => 'Q',
         King => 'K',
-        v => char::from_u32(v as u32).unwrap(),
+        v => char::from_digit(v as u32 + 1, 10).unwrap(),
     }
 );
 
@@ -147,16 +189,91 @@ mimpl!(TryFrom; char, Rank, String, |c: char| {
         use rand::Rng;
 
         let u: u8 = match c.to_ascii_uppercase() {
-            'A' => 1,
-            '1' => 10,
-            'J' => 11,
-            'Q' => 12,
-            'K' => 13,
+            'A' => 0,
+            'T' | '1' => 9,
+            'J' => 10,
+            'Q' => 11,
+            'K' => 12,
             '.' => rand::thread_rng().gen_range(0, 13),
-            c if c.is_digit(10) && c != '0' => c.to_digit(9).unwrap() as u8,
+            c @ '2'..='9' => c as u8 - b'1',
             c => return Err(format!("card::Rank can't be converted from char '{}'.", c)),
         };
 
         Ok(Rank::try_from(u).unwrap())
     }
 );
+
+mimpl!(From; Card, u8, |card: Card| card.rank as u8 * 4 + card.suit as u8);
+
+impl TryFrom<u8> for Card {
+    type Error = String;
+
+    /// Converts an index from 0 to 51 back to a `Card`, the inverse of
+    /// `u8::from`. Cards are numbered by rank first, then by suit, in the
+    /// same order as they sort.
+    ///
+    /// # Example
+    /// ```
+    /// # use aces_high::{*, card::face::*};
+    /// # use std::convert::TryFrom;
+    /// assert_eq!(u8::from(card!(Ace, Clubs)), 0);
+    /// assert_eq!(u8::from(card!(King, Spades)), 51);
+    /// assert_eq!(Card::try_from(6), Ok(card!(Two, Hearts)));
+    /// assert!(Card::try_from(52).is_err());
+    /// ```
+    fn try_from(u: u8) -> Result<Self, Self::Error> {
+        if u >= 52 {
+            return Err(format!("card::Card can't be converted from u8 '{}'.", u));
+        }
+
+        Ok(Card {
+            rank: Rank::try_from(u / 4)?,
+            suit: Suit::from_u8(u % 4).unwrap(),
+        })
+    }
+}
+
+impl std::str::FromStr for Card {
+    type Err = String;
+
+    /// Parses a card in standard notation, such as `As`, `Td` or `10h`.
+    /// Case is ignored.
+    ///
+    /// # Example
+    /// ```
+    /// # use aces_high::{*, card::face::*};
+    /// assert_eq!("Td".parse(), Ok(card!(Ten, Diamonds)));
+    /// assert_eq!("10D".parse(), Ok(card!(Ten, Diamonds)));
+    /// assert!("1x".parse::<Card>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || format!("card::Card can't be parsed from '{}'.", s);
+
+        let mut chars = s.chars();
+        let suit = match chars.next_back().map(|c| c.to_ascii_lowercase()) {
+            Some('c') => Clubs,
+            Some('d') => Diamonds,
+            Some('h') => Hearts,
+            Some('s') => Spades,
+            _ => return Err(error()),
+        };
+        let rank = match chars.as_str().to_ascii_uppercase().as_str() {
+            "A" => Ace,
+            "K" => King,
+            "Q" => Queen,
+            "J" => Jack,
+            "T" | "10" => Ten,
+            "9" => Nine,
+            "8" => Eight,
+            "7" => Seven,
+            "6" => Six,
+            "5" => Five,
+            "4" => Four,
+            "3" => Three,
+            "2" => Two,
+            _ => return Err(error()),
+        };
+
+        Ok(Card { rank, suit })
+    }
+}
@@ -3,10 +3,17 @@ mod fmt;
 mod r#impl;
 #[macro_use]
 pub mod macros;
+#[cfg(feature = "serde")]
+mod serde;
 
 use num_derive::FromPrimitive;
+#[cfg(feature = "serde")]
+use ::serde::{Deserialize, Serialize};
 
 /// Basic Card struct.
+///
+/// With the `serde` feature, it's serialized in standard notation, such as
+/// `"As"`.
 #[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Card {
     pub rank: Rank,
@@ -18,11 +25,12 @@ pub struct Card {
 ///
 /// | enum     | u8 | char |
 /// | -------- | -- | ---- |
-/// | Clubs    | 0  | W    |
-/// | Diamonds | 1  | A    |
-/// | Hearts   | 2  | 2    |
-/// | Spades   | 3  | 3    |
+/// | Clubs    | 0  | c    |
+/// | Diamonds | 1  | d    |
+/// | Hearts   | 2  | h    |
+/// | Spades   | 3  | s    |
 #[derive(Copy, Clone, Debug, FromPrimitive, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Suit {
     Clubs,
     Diamonds,
@@ -44,11 +52,12 @@ pub enum Suit {
 /// | Seven | 6  | 7    |
 /// | Eight | 7  | 8    |
 /// | Nine  | 8  | 9    |
-/// | Ten   | 9  | 1    |
+/// | Ten   | 9  | T    |
 /// | Jack  | 10 | J    |
 /// | Queen | 11 | Q    |
 /// | King  | 12 | K    |
 #[derive(Copy, Clone, Debug, FromPrimitive, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Rank {
     Ace,
     Two,
@@ -64,3 +73,14 @@ pub enum Rank {
     Queen,
     King,
 }
+
+#[test]
+fn notation_round_trips() {
+    for rank in &ranks!() {
+        for suit in &suits!() {
+            let card = card!(*rank, *suit);
+            assert_eq!(std::convert::TryFrom::try_from(char::from(*rank)), Ok(*rank));
+            assert_eq!(format!("{:?}", card).parse(), Ok(card));
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use super::Card;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+impl Serialize for Card {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{:?}", self))
+    }
+}
+
+impl<'de> Deserialize<'de> for Card {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+#[test]
+fn json() {
+    use super::face::*;
+
+    let cards = cards!(Ace, Spades; Ten, Hearts);
+    let json = serde_json::to_string(&cards).unwrap();
+    assert_eq!(json, r#"["As","Th"]"#);
+    assert_eq!(serde_json::from_str::<Vec<Card>>(&json).unwrap(), cards);
+
+    assert_eq!(serde_json::to_string(&Ace).unwrap(), r#""Ace""#);
+    assert!(serde_json::from_str::<Card>(r#""Xs""#).is_err());
+}
@@ -0,0 +1,29 @@
+use crate::card::Card;
+use std::fmt;
+
+/// Why cards couldn't be dealt or moved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Asked for more cards than are left.
+    NotEnough { wanted: usize, left: usize },
+    /// A card asked for isn't in the deck.
+    Missing(Card),
+    /// A position beyond the bottom of the deck.
+    OutOfRange { at: usize, len: usize },
+}
+
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotEnough { wanted, left } => {
+                write!(f, "can't deal {} cards with {} left", wanted, left)
+            }
+            Error::Missing(card) => write!(f, "{:?} isn't in the deck", card),
+            Error::OutOfRange { at, len } => {
+                write!(f, "position {} is beyond the {} cards left", at, len)
+            }
+        }
+    }
+}
@@ -0,0 +1,283 @@
+//! Shuffles that players can check, by commit and reveal.
+//!
+//! 1. The dealer picks a secret 32 byte seed and salt, and shuffles a sorted
+//!    deck with the seed. It publishes the `Commitment`, the SHA-256 of the
+//!    salt followed by that order.
+//! 2. Players may each contribute entropy, which the dealer can't predict.
+//!    The order committed to is shuffled again with the SHA-256 of the seed
+//!    followed by every contribution, each prefixed by its length as 8 bytes
+//!    little endian. That's the deck the hand is dealt from.
+//! 3. After the hand, the dealer reveals the seed and salt. Anyone can then
+//!    check the commitment and deal the deck again, with `Reveal::verify`.
+//!
+//! Orders are written as one byte a card, as numbered by `u8::from(card)`,
+//! and list the cards from the bottom of the deck to the top, the way
+//! `Deck::new_custom` takes them. Shuffles are as by `shuffle`.
+use super::Deck;
+use crate::card::Card;
+use rand::{rngs::OsRng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use sha2::{Digest, Sha256};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// The hash a dealer publishes before dealing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Commitment(pub [u8; 32]);
+
+/// Deals a hand from a shuffle it has committed to.
+///
+/// # Example
+/// ```
+/// # use aces_high::deck::fair::*;
+/// let mut dealer = Dealer::new();
+/// let commitment = dealer.commitment();
+/// dealer.contribute(b"alice's dice");
+/// dealer.contribute(b"bob's coin flips");
+///
+/// let mut deck = dealer.deck();
+/// let dealt = deck.deal(9).unwrap();
+///
+/// let reveal = dealer.reveal();
+/// assert_eq!(reveal.verify(&commitment, &dealt), Ok(()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Dealer {
+    seed: [u8; 32],
+    salt: [u8; 32],
+    entropy: Vec<Vec<u8>>,
+}
+
+/// What a dealer reveals after the hand, for anyone to check it with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reveal {
+    pub seed: [u8; 32],
+    pub salt: [u8; 32],
+    /// What the players contributed, in order.
+    pub entropy: Vec<Vec<u8>>,
+}
+
+/// Why a revealed shuffle doesn't check out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The seed and salt don't hash to the commitment.
+    Commitment,
+    /// The card dealt at `index` isn't the one the shuffle puts there.
+    Dealt { index: usize, expected: Card },
+    /// More cards were dealt than a deck has.
+    TooMany,
+}
+
+impl Dealer {
+    /// Constructs a `Dealer` with a seed and salt from the operating system.
+    pub fn new() -> Self {
+        let mut seed = [0; 32];
+        let mut salt = [0; 32];
+        OsRng.fill_bytes(&mut seed);
+        OsRng.fill_bytes(&mut salt);
+        Dealer::from_secrets(seed, salt)
+    }
+
+    /// Constructs a `Dealer` with a seed and salt that must be kept secret
+    /// until the hand is over.
+    pub fn from_secrets(seed: [u8; 32], salt: [u8; 32]) -> Self {
+        Dealer {
+            seed,
+            salt,
+            entropy: Vec::new(),
+        }
+    }
+
+    pub fn commitment(&self) -> Commitment {
+        commit(&self.salt, &committed(&self.seed))
+    }
+
+    /// Adds entropy from a player. Contributions must be made before the
+    /// deck is dealt, and in an order every player agrees on.
+    pub fn contribute(&mut self, entropy: &[u8]) {
+        self.entropy.push(entropy.to_vec());
+    }
+
+    /// Returns the deck to deal from, with the contributions so far.
+    pub fn deck(&self) -> Deck {
+        Deck::new_custom(&dealt(&self.seed, &self.entropy))
+    }
+
+    /// Ends the hand, giving up the secrets.
+    pub fn reveal(self) -> Reveal {
+        Reveal {
+            seed: self.seed,
+            salt: self.salt,
+            entropy: self.entropy,
+        }
+    }
+}
+
+impl Default for Dealer {
+    fn default() -> Self {
+        Dealer::new()
+    }
+}
+
+impl Reveal {
+    /// Returns the deck the hand was dealt from.
+    pub fn deck(&self) -> Deck {
+        Deck::new_custom(&dealt(&self.seed, &self.entropy))
+    }
+
+    /// Checks the secrets against `commitment`, and that `dealt` are the
+    /// cards drawn from the top of the deck, in order, burns included.
+    pub fn verify(&self, commitment: &Commitment, dealt: &[Card]) -> Result<(), Error> {
+        if commit(&self.salt, &committed(&self.seed)) != *commitment {
+            return Err(Error::Commitment);
+        }
+
+        let mut deck = self.deck();
+        if dealt.len() > deck.len() {
+            return Err(Error::TooMany);
+        }
+        for (index, &card) in dealt.iter().enumerate() {
+            let expected = deck.draw().unwrap();
+            if card != expected {
+                return Err(Error::Dealt { index, expected });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Shuffles `cards` by Fisher-Yates, drawing from ChaCha20 seeded with
+/// `seed`, as `rand_chacha::ChaCha20Rng::from_seed` does.
+///
+/// For `i` from the last index down to 1, a 32 bit number `r` is drawn,
+/// as `next_u32` does, until `r` is below `2^32 - 2^32 % (i + 1)`. Then the
+/// card at `i` is swapped with the card at `r % (i + 1)`.
+pub fn shuffle(cards: &mut [Card], seed: [u8; 32]) {
+    let mut rng = ChaCha20Rng::from_seed(seed);
+
+    for i in (1..cards.len()).rev() {
+        let bound = i as u64 + 1;
+        let zone = (1 << 32) - (1 << 32) % bound;
+        let r = loop {
+            let r = u64::from(rng.next_u32());
+            if r < zone {
+                break r;
+            }
+        };
+        cards.swap(i, (r % bound) as usize);
+    }
+}
+
+/// Returns the order committed to.
+fn committed(seed: &[u8; 32]) -> Vec<Card> {
+    let mut cards: Vec<Card> = (0..52).map(|i| Card::try_from(i).unwrap()).collect();
+    shuffle(&mut cards, *seed);
+    cards
+}
+
+/// Returns the order dealt from, once the players have contributed.
+fn dealt(seed: &[u8; 32], entropy: &[Vec<u8>]) -> Vec<Card> {
+    let mut hasher = Sha256::new();
+    hasher.update(seed);
+    for contribution in entropy {
+        hasher.update((contribution.len() as u64).to_le_bytes());
+        hasher.update(contribution);
+    }
+
+    let mut cards = committed(seed);
+    shuffle(&mut cards, hasher.finalize().into());
+    cards
+}
+
+fn commit(salt: &[u8; 32], cards: &[Card]) -> Commitment {
+    let mut hasher = Sha256::new();
+    hasher.update(salt);
+    for &card in cards {
+        hasher.update([u8::from(card)]);
+    }
+    Commitment(hasher.finalize().into())
+}
+
+impl fmt::Display for Commitment {
+    /// Formats the hash in lower case hexadecimal.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Commitment => write!(f, "the secrets don't match the commitment"),
+            Error::Dealt { index, expected } => {
+                write!(f, "card {} dealt should be {:?}", index, expected)
+            }
+            Error::TooMany => write!(f, "more cards dealt than in a deck"),
+        }
+    }
+}
+
+#[test]
+fn known_shuffle() {
+    let dealer = Dealer::from_secrets([1; 32], [2; 32]);
+    let mut order = committed(&[1; 32]);
+    assert_ne!(order, committed(&[3; 32]));
+    order.sort();
+    assert!(order
+        .iter()
+        .enumerate()
+        .all(|(i, &card)| u8::from(card) as usize == i));
+
+    // The algorithm is part of the format, so its output mustn't change.
+    let mut cards: Vec<Card> = (0..8).map(|i| Card::try_from(i).unwrap()).collect();
+    shuffle(&mut cards, [0; 32]);
+    let indices: Vec<u8> = cards.iter().map(|&card| u8::from(card)).collect();
+    assert_eq!(indices, [3, 2, 5, 7, 1, 0, 4, 6]);
+
+    assert_eq!(dealer.commitment().to_string().len(), 64);
+    assert_eq!(dealer.deck().len(), 52);
+}
+
+#[test]
+fn catches_cheating() {
+    let mut dealer = Dealer::from_secrets([5; 32], [6; 32]);
+    let commitment = dealer.commitment();
+    let before = dealer.deck().deal(5).unwrap();
+    dealer.contribute(b"player one");
+    let dealt = dealer.deck().deal(5).unwrap();
+    assert_ne!(before, dealt);
+
+    let reveal = dealer.reveal();
+    assert_eq!(reveal.verify(&commitment, &dealt), Ok(()));
+    assert_eq!(reveal.verify(&commitment, &[]), Ok(()));
+
+    let mut other = reveal.clone();
+    other.salt[0] ^= 1;
+    assert_eq!(other.verify(&commitment, &dealt), Err(Error::Commitment));
+
+    let mut other = reveal.clone();
+    other.entropy[0] = b"player two".to_vec();
+    assert!(matches!(
+        other.verify(&commitment, &dealt),
+        Err(Error::Dealt { index: 0, .. })
+    ));
+
+    let mut swapped = dealt.clone();
+    swapped.swap(3, 4);
+    assert_eq!(
+        reveal.verify(&commitment, &swapped),
+        Err(Error::Dealt {
+            index: 3,
+            expected: dealt[3]
+        })
+    );
+    assert_eq!(
+        reveal.verify(&commitment, &[dealt[0]; 53]),
+        Err(Error::TooMany)
+    );
+}
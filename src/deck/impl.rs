@@ -1,34 +1,200 @@
 extern crate rand;
 
-use super::Deck;
-use crate::card::{macros, Card};
+use super::{Composition, Deck, Error};
+use crate::card::{macros, Card, Rank};
 use rand::prelude::SliceRandom;
 use std::fmt;
 
 impl Deck {
     /// Draw the top card from the deck, or return None if empty
     pub fn draw(&mut self) -> Option<Card> {
-        self.inner_deck.pop()
+        take(&mut self.inner_deck, &mut self.dealt)
     }
 
     /// Draw some cards and return them as a Vector
-    pub fn deal(&mut self, size: usize) -> Option<Vec<Card>> {
-        if self.inner_deck.len() >= size {
-            let mut cards = Vec::<Card>::new();
+    pub fn deal(&mut self, size: usize) -> Result<Vec<Card>, Error> {
+        self.enough(size)?;
+        Ok((0..size).map(|_| self.draw().unwrap()).collect())
+    }
 
-            for _ in 0..size {
-                cards.push(self.inner_deck.pop().unwrap());
-            }
+    /// Deals `size` cards to each of `players`, one at a time, going round
+    /// from the first player.
+    ///
+    /// # Example
+    /// ```
+    /// # use aces_high::prelude::*;
+    /// let mut deck = Deck::new_sorted();
+    /// let top = deck.peek(6).unwrap();
+    /// let hands = deck.deal_round(3, 2).unwrap();
+    /// assert_eq!(hands[0], [top[0], top[3]]);
+    /// assert_eq!(hands[2], [top[2], top[5]]);
+    /// assert!(deck.deal_round(10, 5).is_err());
+    /// ```
+    pub fn deal_round(&mut self, players: usize, size: usize) -> Result<Vec<Vec<Card>>, Error> {
+        self.enough(players * size)?;
 
-            Some(cards)
-        } else {
-            None
+        let mut hands = vec![Vec::with_capacity(size); players];
+        for _ in 0..size {
+            for hand in hands.iter_mut() {
+                hand.push(self.draw().unwrap());
+            }
         }
+        Ok(hands)
+    }
+
+    /// Returns the top `size` cards, in the order they would be drawn,
+    /// without taking them.
+    pub fn peek(&self, size: usize) -> Result<Vec<Card>, Error> {
+        self.enough(size)?;
+        Ok(self.inner_deck.iter().rev().take(size).copied().collect())
+    }
+
+    /// Burns the top card of the deck, unseen, and returns it.
+    pub fn burn(&mut self) -> Result<Card, Error> {
+        self.enough(1)?;
+        Ok(take(&mut self.inner_deck, &mut self.burned).unwrap())
     }
 
-    /// Deletes top card from deck
+    /// Burns the top card of the deck, if there is one.
     pub fn discard(&mut self) {
-        self.inner_deck.pop();
+        let _ = self.burn();
+    }
+
+    /// Moves the top `at` cards under the rest.
+    ///
+    /// # Example
+    /// ```
+    /// # use aces_high::prelude::*;
+    /// let mut deck = Deck::new_sorted();
+    /// let top = deck.peek(52).unwrap();
+    /// deck.cut(10).unwrap();
+    /// assert_eq!(deck.peek(1).unwrap(), [top[10]]);
+    /// assert!(deck.cut(53).is_err());
+    /// ```
+    pub fn cut(&mut self, at: usize) -> Result<(), Error> {
+        let len = self.inner_deck.len();
+        if at > len {
+            return Err(Error::OutOfRange { at, len });
+        }
+        self.inner_deck.rotate_right(at);
+        Ok(())
+    }
+
+    /// Takes `cards` out of the deck, wherever they are, such as dead cards
+    /// or hole cards already known. They count as dealt. Nothing is taken
+    /// if any of them is missing.
+    ///
+    /// # Example
+    /// ```
+    /// # use aces_high::{card::face::*, deck::*};
+    /// let mut deck = Deck::new_shuffled();
+    /// let known = [Card::new(Ace, Spades), Card::new(Ace, Hearts)];
+    /// deck.remove(&known).unwrap();
+    /// assert_eq!(deck.len(), 50);
+    /// assert_eq!(deck.remove(&known[..1]), Err(Error::Missing(known[0])));
+    /// ```
+    pub fn remove(&mut self, cards: &[Card]) -> Result<(), Error> {
+        let mut left = self.inner_deck.clone();
+        for &card in cards {
+            match left.iter().position(|&other| other == card) {
+                Some(i) => {
+                    left.remove(i);
+                }
+                None => return Err(Error::Missing(card)),
+            }
+        }
+
+        self.inner_deck = left;
+        self.dealt.extend_from_slice(cards);
+        Ok(())
+    }
+
+    /// Puts `card` back `at` cards from the top, so that it's drawn after
+    /// them.
+    pub fn insert(&mut self, at: usize, card: Card) -> Result<(), Error> {
+        let len = self.inner_deck.len();
+        if at > len {
+            return Err(Error::OutOfRange { at, len });
+        }
+        self.untrack(card);
+        self.inner_deck.insert(len - at, card);
+        Ok(())
+    }
+
+    /// Puts `cards` back under the deck, the first of them at the very
+    /// bottom.
+    pub fn put_bottom(&mut self, cards: &[Card]) {
+        for &card in cards {
+            self.untrack(card);
+        }
+        self.inner_deck.splice(0..0, cards.iter().copied());
+    }
+
+    /// Stops counting `card` as taken out, since it's back in the deck.
+    fn untrack(&mut self, card: Card) {
+        // Cards given back were dealt first, so they are counted twice.
+        if let Some(i) = self.discarded.iter().rposition(|&other| other == card) {
+            self.discarded.remove(i);
+        }
+        for taken in [&mut self.dealt, &mut self.burned].iter_mut() {
+            if let Some(i) = taken.iter().rposition(|&other| other == card) {
+                taken.remove(i);
+                return;
+            }
+        }
+    }
+
+    fn enough(&self, wanted: usize) -> Result<(), Error> {
+        let left = self.inner_deck.len();
+        if wanted > left {
+            return Err(Error::NotEnough { wanted, left });
+        }
+        Ok(())
+    }
+
+    /// Takes back `cards` that players gave up, such as the discards of
+    /// draw games. They stay out of the deck until gathered.
+    pub fn muck(&mut self, cards: &[Card]) {
+        self.discarded.extend_from_slice(cards);
+    }
+
+    /// Returns the cards drawn so far, in order.
+    pub fn dealt(&self) -> &[Card] {
+        &self.dealt
+    }
+
+    /// Returns the cards burned so far, in order.
+    pub fn burned(&self) -> &[Card] {
+        &self.burned
+    }
+
+    /// Returns the cards given back so far, in order.
+    pub fn discarded(&self) -> &[Card] {
+        &self.discarded
+    }
+
+    /// Puts every card taken out back at the bottom of the deck, ready to
+    /// be shuffled for the next hand.
+    ///
+    /// # Example
+    /// ```
+    /// # use aces_high::prelude::*;
+    /// let mut deck = Deck::new_shuffled();
+    /// let hand = deck.deal(5).unwrap();
+    /// deck.discard();
+    /// deck.muck(&hand[..2]);
+    /// assert_eq!((deck.len(), deck.dealt().len(), deck.burned().len()), (46, 5, 1));
+    ///
+    /// deck.gather();
+    /// assert_eq!((deck.len(), deck.dealt().len()), (52, 0));
+    /// ```
+    pub fn gather(&mut self) {
+        gather(
+            &mut self.inner_deck,
+            &mut self.dealt,
+            &mut self.burned,
+            &mut self.discarded,
+        );
     }
 
     /// Shuffle the deck
@@ -36,6 +202,12 @@ impl Deck {
         self.inner_deck.shuffle(&mut rand::thread_rng());
     }
 
+    /// Shuffle the deck using `rng`, so that a seeded `rng` always gives the
+    /// same order.
+    pub fn shuffle_with<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.inner_deck.shuffle(rng);
+    }
+
     /// Returns the length of deck
     pub fn len(&self) -> usize {
         self.inner_deck.len()
@@ -51,17 +223,7 @@ impl Deck {
     /// //assert!(deck.is_sorted());
     /// ```
     pub fn new_sorted() -> Deck {
-        let mut deck = vec![];
-
-        for rank in &ranks!() {
-            for suit in &suits!() {
-                deck.push(card!(*rank, *suit));
-            }
-        }
-
-        deck.sort();
-
-        Deck { inner_deck: deck }
+        Deck::new_composed(Composition::Standard)
     }
 
     /// Constructs a new, full `Deck` of 52 unsorted unique cards.
@@ -90,9 +252,24 @@ impl Deck {
     pub fn new_custom(cards: &[Card]) -> Deck {
         Deck {
             inner_deck: cards.to_vec(),
+            dealt: Vec::new(),
+            burned: Vec::new(),
+            discarded: Vec::new(),
         }
     }
 
+    /// Constructs a new `Deck` of the cards of `composition`, sorted.
+    ///
+    /// # Example
+    /// ```
+    /// # use aces_high::deck::*;
+    /// assert_eq!(Deck::new_composed(Composition::Short).len(), 36);
+    /// assert_eq!(Deck::new_composed(Composition::Pinochle).len(), 48);
+    /// ```
+    pub fn new_composed(composition: Composition) -> Deck {
+        Deck::new_custom(&composition.cards())
+    }
+
     /// Returns `true` if the deck contains no cards.
     pub fn is_empty(&self) -> bool {
         self.inner_deck.is_empty()
@@ -104,6 +281,55 @@ impl Deck {
     //} // Unstable for now.
 }
 
+/// Takes the top card of `cards`, and keeps it in `taken`.
+pub(super) fn take<T: Copy>(cards: &mut Vec<T>, taken: &mut Vec<T>) -> Option<T> {
+    let card = cards.pop()?;
+    taken.push(card);
+    Some(card)
+}
+
+/// Puts the cards `dealt` and `burned` back at the bottom of `cards`, and
+/// forgets the `discarded` ones.
+pub(super) fn gather<T>(
+    cards: &mut Vec<T>,
+    dealt: &mut Vec<T>,
+    burned: &mut Vec<T>,
+    discarded: &mut Vec<T>,
+) {
+    // Discards were dealt first, so they are only counted once.
+    let mut back = Vec::with_capacity(dealt.len() + burned.len() + cards.len());
+    back.append(dealt);
+    back.append(burned);
+    discarded.clear();
+    back.append(cards);
+    *cards = back;
+}
+
+impl Composition {
+    /// Returns the cards, sorted.
+    pub fn cards(self) -> Vec<Card> {
+        let (lowest, copies) = match self {
+            Composition::Standard => (Rank::Two, 1),
+            Composition::Short => (Rank::Six, 1),
+            Composition::Euchre => (Rank::Nine, 1),
+            Composition::Pinochle => (Rank::Nine, 2),
+        };
+
+        let mut cards = Vec::new();
+        for rank in &ranks!() {
+            if *rank == Rank::Ace || *rank >= lowest {
+                for suit in &suits!() {
+                    for _ in 0..copies {
+                        cards.push(card!(*rank, *suit));
+                    }
+                }
+            }
+        }
+        cards.sort();
+        cards
+    }
+}
+
 impl Default for Deck {
     fn default() -> Self {
         Deck::new_shuffled()
@@ -115,3 +341,33 @@ impl fmt::Debug for Deck {
         write!(f, "Deck {:?}", self.inner_deck)
     }
 }
+
+#[test]
+fn puts_cards_back() {
+    let mut deck = Deck::new_sorted();
+    let top = deck.peek(52).unwrap();
+
+    let hand = deck.deal(2).unwrap();
+    let burned = deck.burn().unwrap();
+    deck.muck(&hand);
+    deck.put_bottom(&hand);
+    assert_eq!(deck.len(), 51);
+    assert_eq!((deck.dealt(), deck.discarded()), (&[][..], &[][..]));
+    assert_eq!(deck.inner_deck[..2], hand[..]);
+
+    deck.insert(1, burned).unwrap();
+    assert_eq!(deck.burned(), &[]);
+    assert_eq!(deck.peek(2).unwrap(), [top[3], burned]);
+    assert_eq!(deck.insert(60, burned), Err(Error::OutOfRange { at: 60, len: 52 }));
+
+    let mut shorter = deck.clone();
+    assert!(shorter.remove(&[top[0], top[0]]).is_err());
+    assert_eq!(shorter.len(), 52);
+    assert_eq!(deck.deal(53), Err(Error::NotEnough { wanted: 53, left: 52 }));
+
+    deck.deal_round(4, 3).unwrap();
+    deck.gather();
+    let mut cards = deck.peek(52).unwrap();
+    cards.sort();
+    assert_eq!(cards, Composition::Standard.cards());
+}
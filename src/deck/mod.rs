@@ -1,7 +1,42 @@
 #[macro_use]
 pub mod macros;
+pub mod error;
+pub mod fair;
 mod r#impl;
+pub mod shoe;
 
+pub use error::Error;
+
+use crate::card::Card;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A pack of cards, drawn from the top, which keeps the cards taken out of
+/// it until they are gathered back.
+///
+/// With the `serde` feature, it's serialized with its cards and the ones
+/// taken out.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Deck {
-    inner_deck: Vec<crate::card::Card>,
+    inner_deck: Vec<Card>,
+    /// Cards drawn, in order.
+    dealt: Vec<Card>,
+    /// Cards discarded unseen from the top.
+    burned: Vec<Card>,
+    /// Cards players gave back, such as in draw games.
+    discarded: Vec<Card>,
+}
+
+/// The cards a deck is made of.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Composition {
+    /// All 52 cards.
+    Standard,
+    /// The 36 cards from six to ace, for short deck hold'em.
+    Short,
+    /// The 24 cards from nine to ace, for euchre.
+    Euchre,
+    /// Two of each of the cards from nine to ace, 48 in all, for pinochle.
+    Pinochle,
 }
@@ -0,0 +1,259 @@
+//! Shoes of several decks, with jokers and a cut card.
+use super::r#impl::{gather, take};
+use super::{Composition, Error};
+use crate::card::Card;
+use rand::{seq::SliceRandom, Rng};
+
+/// A card drawn from a `Shoe`, which may be a joker.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Drawn {
+    Card(Card),
+    Joker,
+}
+
+impl Drawn {
+    /// Returns the card, or `None` for a joker.
+    pub fn card(self) -> Option<Card> {
+        match self {
+            Drawn::Card(card) => Some(card),
+            Drawn::Joker => None,
+        }
+    }
+}
+
+/// Decks shuffled together, dealt until the cut card comes out.
+///
+/// # Example
+/// ```
+/// # use aces_high::{card::face::*, deck::shoe::*};
+/// let mut shoe = Shoe::builder()
+///     .decks(6)
+///     .without(&[Card::new(Ace, Spades)])
+///     .jokers(2)
+///     .cut(52)
+///     .build();
+/// assert_eq!(shoe.len(), 6 * 52 - 1 + 2);
+///
+/// while !shoe.is_cut() {
+///     shoe.draw().unwrap();
+/// }
+/// assert_eq!(shoe.len(), 52);
+/// assert_eq!(shoe.dealt().len(), 6 * 52 + 1 - 52);
+///
+/// shoe.shuffle();
+/// assert_eq!(shoe.len(), 6 * 52 + 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Shoe {
+    cards: Vec<Drawn>,
+    /// Cards left when the cut card comes out.
+    cut: Option<usize>,
+    dealt: Vec<Drawn>,
+    burned: Vec<Drawn>,
+    discarded: Vec<Drawn>,
+}
+
+/// Builds a `Shoe`, of one standard deck unless told otherwise.
+#[derive(Clone, Debug)]
+pub struct Builder {
+    decks: usize,
+    composition: Composition,
+    without: Vec<Card>,
+    jokers: usize,
+    cut: Option<usize>,
+}
+
+impl Shoe {
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Constructs a shuffled `Shoe` of `decks` standard decks, without a
+    /// cut card.
+    pub fn new(decks: usize) -> Self {
+        Shoe::builder().decks(decks).build()
+    }
+
+    /// Draws the top card, or returns `None` if the shoe is empty.
+    pub fn draw(&mut self) -> Option<Drawn> {
+        take(&mut self.cards, &mut self.dealt)
+    }
+
+    /// Draws `size` cards, or none if there aren't enough.
+    pub fn deal(&mut self, size: usize) -> Result<Vec<Drawn>, Error> {
+        let left = self.cards.len();
+        if size > left {
+            return Err(Error::NotEnough { wanted: size, left });
+        }
+        Ok((0..size).map(|_| self.draw().unwrap()).collect())
+    }
+
+    /// Burns the top card, unseen, and returns it.
+    pub fn burn(&mut self) -> Result<Drawn, Error> {
+        take(&mut self.cards, &mut self.burned).ok_or(Error::NotEnough { wanted: 1, left: 0 })
+    }
+
+    /// Takes back `cards` that players gave up. They stay out of the shoe
+    /// until it's shuffled.
+    pub fn muck(&mut self, cards: &[Drawn]) {
+        self.discarded.extend_from_slice(cards);
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// Returns `true` once the cut card has come out, which is when the
+    /// shoe should be shuffled before the next hand.
+    pub fn is_cut(&self) -> bool {
+        self.cut.map_or(false, |cut| self.cards.len() <= cut)
+    }
+
+    pub fn dealt(&self) -> &[Drawn] {
+        &self.dealt
+    }
+
+    pub fn burned(&self) -> &[Drawn] {
+        &self.burned
+    }
+
+    pub fn discarded(&self) -> &[Drawn] {
+        &self.discarded
+    }
+
+    /// Gathers every card back and shuffles them.
+    pub fn shuffle(&mut self) {
+        self.shuffle_with(&mut rand::thread_rng());
+    }
+
+    /// Gathers every card back and shuffles them using `rng`.
+    pub fn shuffle_with<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        gather(
+            &mut self.cards,
+            &mut self.dealt,
+            &mut self.burned,
+            &mut self.discarded,
+        );
+        self.cards.shuffle(rng);
+    }
+}
+
+impl Builder {
+    /// Sets how many decks are shuffled together.
+    pub fn decks(mut self, decks: usize) -> Self {
+        self.decks = decks;
+        self
+    }
+
+    /// Sets the cards of each deck.
+    pub fn composition(mut self, composition: Composition) -> Self {
+        self.composition = composition;
+        self
+    }
+
+    /// Takes `cards` out of the shoe, one copy for each time a card is
+    /// listed.
+    pub fn without(mut self, cards: &[Card]) -> Self {
+        self.without.extend_from_slice(cards);
+        self
+    }
+
+    /// Adds `jokers` to the shoe, in all.
+    pub fn jokers(mut self, jokers: usize) -> Self {
+        self.jokers = jokers;
+        self
+    }
+
+    /// Places the cut card so that it comes out with `left` cards left.
+    pub fn cut(mut self, left: usize) -> Self {
+        self.cut = Some(left);
+        self
+    }
+
+    /// Builds the shoe, shuffled.
+    pub fn build(&self) -> Shoe {
+        self.build_with(&mut rand::thread_rng())
+    }
+
+    /// Builds the shoe, shuffled using `rng`.
+    pub fn build_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Shoe {
+        let mut cards = Vec::new();
+        for _ in 0..self.decks {
+            cards.extend(self.composition.cards());
+        }
+        for card in &self.without {
+            if let Some(i) = cards.iter().position(|other| other == card) {
+                cards.swap_remove(i);
+            }
+        }
+
+        let mut cards: Vec<Drawn> = cards.into_iter().map(Drawn::Card).collect();
+        cards.extend((0..self.jokers).map(|_| Drawn::Joker));
+        cards.shuffle(rng);
+
+        Shoe {
+            cards,
+            cut: self.cut,
+            dealt: Vec::new(),
+            burned: Vec::new(),
+            discarded: Vec::new(),
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Builder {
+            decks: 1,
+            composition: Composition::Standard,
+            without: Vec::new(),
+            jokers: 0,
+            cut: None,
+        }
+    }
+}
+
+#[test]
+fn compositions() {
+    use crate::card::{Rank, Suit};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(1);
+    let shoe = Shoe::builder()
+        .decks(2)
+        .composition(Composition::Euchre)
+        .without(&[Card::new(Rank::Nine, Suit::Clubs); 3])
+        .build_with(&mut rng);
+    assert_eq!(shoe.len(), 2 * 24 - 2);
+    assert!(shoe.cards.iter().all(|card| match card {
+        Drawn::Card(card) => card.rank == Rank::Ace || card.rank >= Rank::Nine,
+        Drawn::Joker => false,
+    }));
+    assert!(!shoe
+        .cards
+        .contains(&Drawn::Card(Card::new(Rank::Nine, Suit::Clubs))));
+
+    let mut shoe = Shoe::builder().jokers(1).build_with(&mut rng);
+    assert_eq!(shoe.len(), 53);
+    assert!(!shoe.is_cut());
+    shoe.burn().unwrap();
+    let hand = shoe.deal(52).unwrap();
+    let jokers = hand
+        .iter()
+        .chain(shoe.burned())
+        .filter(|card| card.card().is_none());
+    assert_eq!(jokers.count(), 1);
+    assert_eq!(shoe.deal(1), Err(Error::NotEnough { wanted: 1, left: 0 }));
+
+    shoe.muck(&hand[..3]);
+    assert_eq!(shoe.discarded().len(), 3);
+    shoe.shuffle_with(&mut rng);
+    assert_eq!(
+        (shoe.len(), shoe.dealt().len(), shoe.discarded().len()),
+        (53, 0, 0)
+    );
+}
@@ -0,0 +1,27 @@
+use crate::table;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A hand couldn't be dealt, or an action wasn't legal.
+    Table(table::Error),
+    /// The hand is over, and a new one has to be dealt by `reset`.
+    Over,
+}
+
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Table(error) => write!(f, "{}", error),
+            Error::Over => write!(f, "the hand is over"),
+        }
+    }
+}
+
+impl From<table::Error> for Error {
+    fn from(error: table::Error) -> Self {
+        Error::Table(error)
+    }
+}
@@ -0,0 +1,380 @@
+use super::{Choice, Env, Error, Hand, Observation, Policy, HISTORY};
+use crate::card::Card;
+use crate::deck::Deck;
+use crate::player::{Action, Agent, View};
+use crate::table::{self, state::State, structure::Legal, Config, Street, Summary};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+impl Choice {
+    /// Returns every choice, in the order of `Observation::mask`.
+    pub fn all() -> [Choice; 5] {
+        [
+            Choice::Fold,
+            Choice::CheckCall,
+            Choice::MinRaise,
+            Choice::PotRaise,
+            Choice::AllIn,
+        ]
+    }
+}
+
+impl Observation {
+    /// Returns the length of the features of a table of `seats` seats.
+    pub fn len(seats: usize) -> usize {
+        4 * 52 + 6 * seats + 8 + HISTORY * (seats + 10)
+    }
+
+    /// Encodes what `view` shows.
+    pub fn new(view: &View) -> Self {
+        let seats = view.seats.len();
+        let big_blind = view.big_blind.max(1) as f32;
+        let chips = |chips: u32| chips as f32 / big_blind;
+        let mut features = Vec::with_capacity(Observation::len(seats));
+
+        one_hot(&mut features, view.hole);
+        for range in &[0..3, 3..4, 4..5] {
+            one_hot(&mut features, view.board.get(range.clone()).unwrap_or(&[]));
+        }
+
+        for i in 0..seats {
+            let seat = (view.seat + i) % seats;
+            let info = &view.seats[seat];
+            features.extend_from_slice(&[
+                chips(info.stack),
+                chips(info.bet),
+                chips(info.tot_bet),
+                flag(info.in_hand()),
+                flag(info.is_all_in()),
+                flag(seat == view.button),
+            ]);
+        }
+
+        let legal = view.legal;
+        let (min, max) = legal
+            .raise
+            .map_or((0, 0), |limits| (limits.min, limits.max));
+        features.extend_from_slice(&[
+            chips(view.pot),
+            chips(view.to_call()),
+            chips(min),
+            chips(max),
+        ]);
+        features.extend((0..4).map(|street| flag(street == view.street as usize)));
+
+        let start = view.history.len().saturating_sub(HISTORY);
+        for decision in &view.history[start..] {
+            let seat = (decision.seat + seats - view.seat) % seats;
+            features.extend((0..seats).map(|other| flag(other == seat)));
+            features.extend((0..4).map(|street| flag(street == decision.street as usize)));
+            let (kind, amount) = match decision.action {
+                Action::Fold => (0, 0),
+                Action::Check => (1, 0),
+                Action::Call(amount) => (2, amount),
+                Action::Bet(to) => (3, to),
+                Action::Raise(to) => (4, to),
+            };
+            features.extend((0..5).map(|other| flag(other == kind)));
+            features.push(chips(amount));
+        }
+        features.resize(Observation::len(seats), 0.0);
+
+        let raise = legal.raise.is_some();
+        Observation {
+            seat: view.seat,
+            features,
+            mask: [legal.call.is_some(), true, raise, raise, raise],
+            legal,
+            to_match: view.to_match(),
+        }
+    }
+
+    /// Returns the action `choice` stands for, or `None` if it isn't legal.
+    pub fn action(&self, choice: Choice) -> Option<Action> {
+        if !self.mask[choice as usize] {
+            return None;
+        }
+
+        let limits = self.legal.raise;
+        let to = match choice {
+            Choice::Fold => return Some(Action::Fold),
+            Choice::CheckCall => {
+                return Some(self.legal.call.map_or(Action::Check, Action::Call));
+            }
+            Choice::MinRaise => limits?.min,
+            Choice::PotRaise => self.legal.pot.max(limits?.min).min(limits?.max),
+            Choice::AllIn => limits?.max,
+        };
+        Some(if self.to_match == 0 {
+            Action::Bet(to)
+        } else {
+            Action::Raise(to)
+        })
+    }
+}
+
+/// Adds a plane of 52 with `cards` set.
+fn one_hot(features: &mut Vec<f32>, cards: &[Card]) {
+    let mut plane = [0.0; 52];
+    for &card in cards {
+        plane[usize::from(u8::from(card))] = 1.0;
+    }
+    features.extend_from_slice(&plane);
+}
+
+fn flag(flag: bool) -> f32 {
+    if flag {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+impl<F: FnMut(&Observation) -> Action> Agent for Policy<F> {
+    fn decide(&mut self, view: &View) -> Action {
+        (self.0)(&Observation::new(view))
+    }
+}
+
+impl Env {
+    /// Constructs an environment dealing every hand from `stacks`, with the
+    /// learner playing every seat.
+    ///
+    /// # Panics
+    /// Panics if there are more than `table::MAX_SEATS` stacks.
+    pub fn new(config: Config, stacks: &[u32]) -> Self {
+        assert!(
+            stacks.len() <= table::MAX_SEATS,
+            "a table has at most {} seats, not {}",
+            table::MAX_SEATS,
+            stacks.len()
+        );
+        Env {
+            config,
+            stacks: stacks.to_vec(),
+            agents: stacks.iter().map(|_| None).collect(),
+            hand: None,
+        }
+    }
+
+    /// Lets `agent` play `seat` from the next hand on.
+    pub fn set_agent(&mut self, seat: usize, agent: Box<dyn Agent>) {
+        self.agents[seat] = Some(agent);
+    }
+
+    /// Gives `seat` back to the learner from the next hand on.
+    pub fn remove_agent(&mut self, seat: usize) -> Option<Box<dyn Agent>> {
+        self.agents[seat].take()
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn stacks(&self) -> &[u32] {
+        &self.stacks
+    }
+
+    /// Deals a new hand, the button and the cards coming from `seed`, and
+    /// returns what the first seat of the learner to act observes.
+    ///
+    /// If the learner doesn't get to act, the hand is over already and the
+    /// observation is that of its first seat dealt in, see `is_done`.
+    pub fn reset(&mut self, seed: u64) -> Result<Observation, Error> {
+        self.hand = None;
+        let mut state = State::new(self.stacks.iter().copied());
+        let dealt: Vec<usize> = (0..self.stacks.len())
+            .filter(|&seat| state.seats[seat].dealt)
+            .collect();
+        if dealt.len() < 2 {
+            return Err(table::Error::NotEnoughPlayers { dealt: dealt.len() }.into());
+        }
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let button = *dealt.choose(&mut rng).unwrap();
+        let (_, big) = state.post_blinds(&self.config, button);
+        let mut deck = Deck::new_sorted();
+        deck.shuffle_with(&mut rng);
+
+        let holes = state.deal_holes(&mut deck, button);
+
+        let mut hand = Hand {
+            state,
+            holes,
+            deck,
+            button,
+            to_act: None,
+            rewards: vec![0.0; self.stacks.len()],
+        };
+        for &seat in &dealt {
+            if let Some(agent) = &mut self.agents[seat] {
+                agent.hand_start(&hand.view(&self.config, seat, Legal::default()));
+            }
+        }
+        hand.advance(&self.config, &mut self.agents, &self.stacks, big)?;
+
+        let seat = hand.to_act.unwrap_or_else(|| {
+            let learner = dealt.iter().find(|&&seat| self.agents[seat].is_none());
+            *learner.unwrap_or(&dealt[0])
+        });
+        let observation = hand.observe(&self.config, seat);
+        self.hand = Some(hand);
+        Ok(observation)
+    }
+
+    /// Takes `action` for the seat to act, and plays on until a seat of the
+    /// learner has to act or the hand is over.
+    ///
+    /// Returns what that seat observes, or once the hand is over, what the
+    /// seat that took `action` does. The reward is what the seat that took
+    /// `action` won, in big blinds, given once the hand is over. An illegal
+    /// `action` changes nothing, but an agent picking one ends the hand.
+    pub fn step(&mut self, action: Action) -> Result<(Observation, f64, bool), Error> {
+        let hand = self.hand.as_mut().ok_or(Error::Over)?;
+        let seat = hand.to_act.ok_or(Error::Over)?;
+        hand.state.act(&self.config, seat, action)?;
+
+        if let Err(error) = hand.advance(&self.config, &mut self.agents, &self.stacks, seat) {
+            self.hand = None;
+            return Err(error.into());
+        }
+        Ok(match hand.to_act {
+            Some(next) => (hand.observe(&self.config, next), 0.0, false),
+            None => (hand.observe(&self.config, seat), hand.rewards[seat], true),
+        })
+    }
+
+    /// Returns the seat to act, or `None` if no hand is in progress.
+    pub fn to_act(&self) -> Option<usize> {
+        self.hand.as_ref().and_then(|hand| hand.to_act)
+    }
+
+    /// Returns `true` unless a hand is in progress.
+    pub fn is_done(&self) -> bool {
+        self.to_act().is_none()
+    }
+
+    /// Returns what `seat` observes of the hand, such as at the end of a
+    /// hand for each seat, or `None` if no hand was dealt.
+    pub fn observe(&self, seat: usize) -> Option<Observation> {
+        let hand = self.hand.as_ref()?;
+        Some(hand.observe(&self.config, seat))
+    }
+
+    /// Returns the chips won or lost by each seat in the hand, in big
+    /// blinds, which are all 0 until it's over.
+    pub fn rewards(&self) -> &[f64] {
+        self.hand.as_ref().map_or(&[], |hand| &hand.rewards)
+    }
+}
+
+impl Hand {
+    fn view(&self, config: &Config, seat: usize, legal: Legal) -> View<'_> {
+        View {
+            seat,
+            button: self.button,
+            street: self.state.street,
+            big_blind: config.big_blind,
+            hole: &self.holes[seat],
+            board: &self.state.board,
+            pot: self.state.pot(),
+            seats: &self.state.seats,
+            history: &self.state.history,
+            legal,
+        }
+    }
+
+    /// Returns what `seat` observes, with the legal actions if it's to act.
+    fn observe(&self, config: &Config, seat: usize) -> Observation {
+        let legal = if self.to_act == Some(seat) {
+            self.state.legal(config, seat)
+        } else {
+            Legal::default()
+        };
+        let mut observation = Observation::new(&self.view(config, seat, legal));
+        if self.to_act != Some(seat) {
+            observation.mask = [false; 5];
+        }
+        observation
+    }
+
+    /// Lets the agents act and deals the streets, starting after `after`,
+    /// until a seat of the learner has to act or the hand is over.
+    fn advance(
+        &mut self,
+        config: &Config,
+        agents: &mut [Option<Box<dyn Agent>>],
+        stacks: &[u32],
+        after: usize,
+    ) -> Result<(), table::Error> {
+        self.to_act = None;
+        let mut seat = after;
+        loop {
+            if let Some(next) = self.state.to_act(seat) {
+                seat = next;
+                match &mut agents[seat] {
+                    Some(agent) => {
+                        let legal = self.state.legal(config, seat);
+                        let action = agent.decide(&self.view(config, seat, legal));
+                        self.state.act(config, seat, action)?;
+                    }
+                    None => {
+                        self.to_act = Some(seat);
+                        return Ok(());
+                    }
+                }
+                continue;
+            }
+
+            if self.state.deal_street(&mut self.deck).is_none() {
+                break;
+            }
+            seat = self.button;
+        }
+
+        self.finish(config, agents, stacks);
+        Ok(())
+    }
+
+    /// Shows down and awards the pots.
+    fn finish(&mut self, config: &Config, agents: &mut [Option<Box<dyn Agent>>], stacks: &[u32]) {
+        let showdown = self.state.showdown(&self.holes);
+        let dealt: Vec<usize> = (0..stacks.len())
+            .filter(|&seat| self.state.seats[seat].dealt)
+            .collect();
+        if !showdown.shown.is_empty() {
+            for &seat in &dealt {
+                if let Some(agent) = &mut agents[seat] {
+                    let view = self.view(config, seat, Legal::default());
+                    agent.showdown(&view, &showdown.shown);
+                }
+            }
+        }
+
+        let award = self.state.award(self.button, &showdown.values);
+        let net: Vec<i64> = self
+            .state
+            .seats
+            .iter()
+            .zip(stacks)
+            .map(|(info, &stack)| i64::from(info.stack) - i64::from(stack))
+            .collect();
+        for (reward, &net) in self.rewards.iter_mut().zip(&net) {
+            *reward = net as f64 / f64::from(config.big_blind.max(1));
+        }
+
+        let summary = Summary {
+            button: self.button,
+            stacks: stacks.to_vec(),
+            history: self.state.history.clone(),
+            board: self.state.board.clone(),
+            shown: showdown.shown,
+            won: award.won,
+            net,
+        };
+        for &seat in &dealt {
+            if let Some(agent) = &mut agents[seat] {
+                agent.hand_end(&self.view(config, seat, Legal::default()), &summary);
+            }
+        }
+    }
+}
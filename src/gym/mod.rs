@@ -0,0 +1,179 @@
+//! An environment for reinforcement learning, which deals hands one
+//! decision at a time.
+//!
+//! Each hand is an episode. `Env::reset` deals one from a seed, and
+//! `Env::step` takes the action of the seat to act and plays on until
+//! another seat the learner plays has to act. Seats given an `Agent` play
+//! themselves, and the learner plays every other seat, which is self-play
+//! when no seat has an agent.
+//!
+//! Observations are the same length for every seat of a table, and are laid
+//! out as follows, chips being counted in big blinds and seats going
+//! clockwise from the one observing:
+//!
+//! | Features              | Length        |
+//! | --------------------- | ------------- |
+//! | Hole cards            | 52            |
+//! | Flop, turn and river  | 52 each       |
+//! | Each seat             | 6 per seat    |
+//! | The pot and the bet   | 8             |
+//! | The last actions      | `HISTORY` × (seats + 10) |
+//!
+//! Cards are one-hot, numbered as by `u8::from(card)`. A seat has its
+//! stack, bet on the street, bet in the hand, whether it's in the hand, all
+//! in and on the button. Then come the pot, what it costs to call, the
+//! least and most a raise may go to, and the street, one-hot. An action has
+//! its seat and street one-hot, what was done one-hot among fold, check,
+//! call, bet and raise, and its amount. Actions not yet taken are zeros.
+mod error;
+mod r#impl;
+
+pub use error::Error;
+
+use crate::card::Card;
+use crate::deck::Deck;
+use crate::player::{Action, Agent};
+use crate::table::{state::State, structure::Legal, Config};
+
+/// The number of actions an observation remembers.
+pub const HISTORY: usize = 24;
+
+/// The actions a policy with a fixed number of outputs picks from, which
+/// `Observation::action` turns into actions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Choice {
+    Fold,
+    CheckCall,
+    /// The smallest bet or raise allowed.
+    MinRaise,
+    /// A pot sized bet or raise.
+    PotRaise,
+    AllIn,
+}
+
+/// What the seat to act knows, as numbers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Observation {
+    /// The seat observing.
+    pub seat: usize,
+    pub features: Vec<f32>,
+    /// Which of `Choice::all()` are legal.
+    pub mask: [bool; 5],
+    /// The legal actions, to pick from exactly.
+    pub legal: Legal,
+    /// The largest bet on the street, which tells bets from raises.
+    to_match: u32,
+}
+
+/// An agent playing by a policy on observations, so that what was learned
+/// can sit at a `Table`.
+///
+/// # Example
+/// ```
+/// # use aces_high::{bot::Station, gym::*, player::Agent, table::*};
+/// let mut table = Table::new_seeded(Config::no_limit(1, 2), &[200, 200], 4);
+/// let policy = Policy(|observation: &Observation| {
+///     observation.action(Choice::PotRaise).unwrap_or_else(|| observation.action(Choice::CheckCall).unwrap())
+/// });
+/// let mut agents: Vec<Box<dyn Agent>> = vec![Box::new(policy), Box::new(Station)];
+/// table.play(&mut agents)?;
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Policy<F>(pub F);
+
+/// Hands dealt one decision at a time, for learning to play them.
+///
+/// # Example
+/// ```
+/// # use aces_high::{bot::Station, gym::*, table::Config};
+/// let mut env = Env::new(Config::no_limit(1, 2), &[200, 200, 200]);
+/// env.set_agent(2, Box::new(Station));
+///
+/// let mut observation = env.reset(7)?;
+/// let len = observation.features.len();
+/// loop {
+///     assert!(observation.seat < 2 && observation.features.len() == len);
+///     let action = observation.action(Choice::CheckCall).unwrap();
+///     let (next, reward, done) = env.step(action)?;
+///     if done {
+///         assert_eq!(reward, env.rewards()[observation.seat]);
+///         break;
+///     }
+///     observation = next;
+/// }
+/// assert_eq!(env.rewards().iter().sum::<f64>(), 0.0);
+/// # Ok::<(), Error>(())
+/// ```
+pub struct Env {
+    config: Config,
+    /// Chips every seat starts each hand with.
+    stacks: Vec<u32>,
+    agents: Vec<Option<Box<dyn Agent>>>,
+    hand: Option<Hand>,
+}
+
+/// A hand in progress.
+#[derive(Debug)]
+struct Hand {
+    state: State,
+    holes: Vec<Vec<Card>>,
+    deck: Deck,
+    button: usize,
+    /// The seat to act, or `None` once the hand is over.
+    to_act: Option<usize>,
+    /// Chips won or lost by each seat, in big blinds, once the hand is over.
+    rewards: Vec<f64>,
+}
+
+#[test]
+fn self_play() {
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+    let mut env = Env::new(Config::no_limit(5, 10), &[1_000, 400, 0, 1_000]);
+    let len = Observation::len(4);
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut hands = Vec::new();
+
+    for seed in 0..200 {
+        let mut observation = env.reset(seed).unwrap();
+        let mut actions = Vec::new();
+        loop {
+            assert_eq!(observation.features.len(), len);
+            assert_ne!(observation.seat, 2);
+            assert!(observation.mask[Choice::CheckCall as usize]);
+            let choices: Vec<Choice> = Choice::all()
+                .iter()
+                .copied()
+                .filter(|&choice| observation.mask[choice as usize])
+                .collect();
+            let choice = *choices.choose(&mut rng).unwrap();
+            let action = observation.action(choice).unwrap();
+            assert!(observation.legal.allows(action));
+            actions.push(action);
+
+            let (next, reward, done) = env.step(action).unwrap();
+            if done {
+                assert_eq!(reward, env.rewards()[observation.seat]);
+                break;
+            }
+            assert_eq!(reward, 0.0);
+            observation = next;
+        }
+        assert_eq!(env.step(Action::Fold), Err(Error::Over));
+        assert!(env.rewards().iter().sum::<f64>().abs() < 1e-9);
+        assert_eq!(env.rewards()[2], 0.0);
+        hands.push((actions, env.rewards().to_vec()));
+    }
+
+    // The same seed deals the same hand.
+    let (actions, rewards) = &hands[17];
+    env.reset(17).unwrap();
+    for &action in actions {
+        env.step(action).unwrap();
+    }
+    assert_eq!(env.rewards(), &rewards[..]);
+
+    let mut env = Env::new(Config::no_limit(5, 10), &[1_000, 0]);
+    assert!(env.reset(0).is_err());
+}
@@ -0,0 +1,268 @@
+//! The 169 classes of starting hands, which play the same before the flop.
+use super::value::strength;
+use crate::card::{Card, Rank};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// Two hole cards without their suits: a pair, or two ranks either suited
+/// or offsuit.
+///
+/// Classes are numbered as the cells of the usual 13 by 13 chart, row by
+/// row from aces down: pairs on the diagonal, suited hands above it and
+/// offsuit hands below it.
+///
+/// # Example
+/// ```
+/// # use aces_high::{card::face::*, hand::class::Class};
+/// let class = Class::of(Card::new(King, Spades), Card::new(Ace, Spades));
+/// assert_eq!(class.to_string(), "AKs");
+/// assert_eq!(class.index(), 1);
+/// assert_eq!(class.combos().len(), 4);
+/// assert_eq!("AKo".parse::<Class>().unwrap().index(), 13);
+/// assert_eq!(Class::all().map(|class| class.combos().len()).sum::<usize>(), 1_326);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Class {
+    high: Rank,
+    low: Rank,
+    suited: bool,
+}
+
+/// The number of classes.
+pub const COUNT: usize = 169;
+
+/// How often each class plays a hand, such as going all in, from 0 to 1.
+///
+/// Prints as a chart of the classes played at least half the time, or
+/// with `{:#}` as a chart of percentages.
+///
+/// # Example
+/// ```
+/// # use aces_high::hand::class::*;
+/// let mut range = Range::default();
+/// for class in &["AA", "KK", "AKs"] {
+///     range.set(class.parse().unwrap(), 1.0);
+/// }
+/// assert!(range.contains("KK".parse().unwrap()));
+/// assert_eq!(range.combos(), 16.0);
+/// assert!(range.to_string().starts_with("AA  AKs .   "));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Range {
+    weights: Vec<f64>,
+}
+
+impl Class {
+    /// Constructs the class of `first` and `second`, in any order. Pairs are
+    /// never suited.
+    pub fn new(first: Rank, second: Rank, suited: bool) -> Self {
+        let (high, low) = if strength(first) >= strength(second) {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        Class {
+            high,
+            low,
+            suited: suited && high != low,
+        }
+    }
+
+    /// Returns the class of two hole cards.
+    pub fn of(first: Card, second: Card) -> Self {
+        Class::new(first.rank, second.rank, first.suit == second.suit)
+    }
+
+    /// Returns the class numbered `index`, if there is one.
+    pub fn from_index(index: usize) -> Option<Self> {
+        if index >= COUNT {
+            return None;
+        }
+        let rank = |i: usize| Rank::try_from(((13 - i) % 13) as u8).unwrap();
+        let (row, column) = (index / 13, index % 13);
+        Some(Class::new(rank(row), rank(column), row < column))
+    }
+
+    /// Returns every class, in order.
+    pub fn all() -> impl Iterator<Item = Class> {
+        (0..COUNT).map(|index| Class::from_index(index).unwrap())
+    }
+
+    pub fn index(self) -> usize {
+        let (high, low) = (12 - strength(self.high), 12 - strength(self.low));
+        let (row, column) = if self.suited {
+            (high, low)
+        } else {
+            (low, high)
+        };
+        usize::from(row) * 13 + usize::from(column)
+    }
+
+    pub fn high(self) -> Rank {
+        self.high
+    }
+
+    pub fn low(self) -> Rank {
+        self.low
+    }
+
+    pub fn is_pair(self) -> bool {
+        self.high == self.low
+    }
+
+    pub fn is_suited(self) -> bool {
+        self.suited
+    }
+
+    /// Returns the number of ways the class can be dealt.
+    pub fn count(self) -> usize {
+        match (self.is_pair(), self.suited) {
+            (true, _) => 6,
+            (false, true) => 4,
+            (false, false) => 12,
+        }
+    }
+
+    /// Returns every pair of hole cards in the class: 6 for a pair, 4 for
+    /// suited hands and 12 for offsuit hands.
+    pub fn combos(self) -> Vec<[Card; 2]> {
+        let mut combos = Vec::with_capacity(12);
+        for (i, &first) in suits!().iter().enumerate() {
+            for (j, &second) in suits!().iter().enumerate() {
+                let wanted = match (self.is_pair(), self.suited) {
+                    (true, _) => i < j,
+                    (false, true) => i == j,
+                    (false, false) => i != j,
+                };
+                if wanted {
+                    combos.push([Card::new(self.high, first), Card::new(self.low, second)]);
+                }
+            }
+        }
+        combos
+    }
+}
+
+impl Range {
+    /// Constructs a `Range` playing every class all the time.
+    pub fn full() -> Self {
+        Range {
+            weights: vec![1.0; COUNT],
+        }
+    }
+
+    pub fn weight(&self, class: Class) -> f64 {
+        self.weights[class.index()]
+    }
+
+    pub fn set(&mut self, class: Class, weight: f64) {
+        self.weights[class.index()] = weight;
+    }
+
+    /// Returns `true` if `class` is played at least half the time.
+    pub fn contains(&self, class: Class) -> bool {
+        self.weight(class) >= 0.5
+    }
+
+    /// Returns the weights, by class index.
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Returns the number of combos played, counting each by its weight.
+    pub fn combos(&self) -> f64 {
+        Class::all()
+            .map(|class| self.weight(class) * class.count() as f64)
+            .sum()
+    }
+}
+
+impl Default for Range {
+    /// Constructs a `Range` playing nothing.
+    fn default() -> Self {
+        Range {
+            weights: vec![0.0; COUNT],
+        }
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..13 {
+            let mut line = String::new();
+            for column in 0..13 {
+                let class = Class::from_index(row * 13 + column).unwrap();
+                let cell = if f.alternate() {
+                    format!("{:>4}", (self.weight(class) * 100.0).round())
+                } else if self.contains(class) {
+                    format!("{:<4}", class.to_string())
+                } else {
+                    format!("{:<4}", ".")
+                };
+                line.push_str(&cell);
+            }
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Class {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", char::from(self.high), char::from(self.low))?;
+        match (self.is_pair(), self.suited) {
+            (true, _) => Ok(()),
+            (false, true) => write!(f, "s"),
+            (false, false) => write!(f, "o"),
+        }
+    }
+}
+
+impl FromStr for Class {
+    type Err = String;
+
+    /// Parses a class such as `AA`, `AKs` or `T9o`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.chars().collect();
+        let error = || format!("\"{}\" is not a starting hand class", s);
+        let rank = |c: char| match c {
+            '.' => Err(error()),
+            c => Rank::try_from(c).map_err(|_| error()),
+        };
+
+        match chars[..] {
+            [first, second] if first.eq_ignore_ascii_case(&second) => {
+                Ok(Class::new(rank(first)?, rank(second)?, false))
+            }
+            [first, second, kind] if !first.eq_ignore_ascii_case(&second) => {
+                let suited = match kind {
+                    's' => true,
+                    'o' => false,
+                    _ => return Err(error()),
+                };
+                Ok(Class::new(rank(first)?, rank(second)?, suited))
+            }
+            _ => Err(error()),
+        }
+    }
+}
+
+#[test]
+fn numbering() {
+    for (index, class) in Class::all().enumerate() {
+        assert_eq!(class.index(), index);
+        assert_eq!(class.to_string().parse(), Ok(class));
+        assert_eq!(class.combos().len(), class.count());
+        for [first, second] in class.combos() {
+            assert_eq!(Class::of(second, first), class);
+        }
+    }
+    assert_eq!(Class::from_index(0).unwrap().to_string(), "AA");
+    assert_eq!(Class::from_index(12).unwrap().to_string(), "A2s");
+    assert_eq!(Class::from_index(168).unwrap().to_string(), "22");
+    assert_eq!(Class::from_index(COUNT), None);
+    assert!("AAs".parse::<Class>().is_err());
+    assert!("AK".parse::<Class>().is_err());
+    assert!("A.s".parse::<Class>().is_err());
+}
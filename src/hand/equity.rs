@@ -0,0 +1,132 @@
+use super::Value;
+use crate::card::Card;
+use rand::{seq::SliceRandom, Rng};
+
+/// Estimates the share of the pot `hole` wins at showdown against
+/// `opponents` random hands, by dealing out the rest of the board `samples`
+/// times. Split pots count as the fraction of the pot won.
+///
+/// # Example
+/// ```
+/// # use aces_high::{*, hand::equity::equity, card::face::*};
+/// # use rand::{rngs::StdRng, SeedableRng};
+/// let mut rng = StdRng::seed_from_u64(1);
+/// let aces = equity(&cards!(Ace, Spades; Ace, Hearts), &[], 1, 2_000, &mut rng);
+/// assert!(aces > 0.8 && aces < 0.9);
+/// ```
+///
+/// # Panics
+/// Panics if there aren't enough cards left to deal to everyone.
+pub fn equity<R: Rng + ?Sized>(
+    hole: &[Card],
+    board: &[Card],
+    opponents: usize,
+    samples: u32,
+    rng: &mut R,
+) -> f64 {
+    let mut deck = remaining(&[hole, board]);
+    let missing = 5usize.saturating_sub(board.len());
+    let needed = missing + 2 * opponents;
+    assert!(deck.len() >= needed, "not enough cards left to deal");
+
+    let mut cards = Vec::with_capacity(hole.len() + 5);
+    let mut won = 0.0;
+
+    for _ in 0..samples {
+        let (dealt, _) = deck.partial_shuffle(rng, needed);
+        let (rest, others) = dealt.split_at(missing);
+
+        let mut value = |hole: &[Card]| {
+            cards.clear();
+            cards.extend_from_slice(hole);
+            cards.extend_from_slice(board);
+            cards.extend_from_slice(rest);
+            Value::new(&cards)
+        };
+
+        let own = value(hole);
+        let mut ties = 0;
+        let mut beaten = false;
+
+        for other in others.chunks(2) {
+            let other = value(other);
+            if other > own {
+                beaten = true;
+                break;
+            } else if other == own {
+                ties += 1;
+            }
+        }
+
+        if !beaten {
+            won += 1.0 / f64::from(ties + 1);
+        }
+    }
+
+    won / f64::from(samples.max(1))
+}
+
+/// Returns the cards of a standard deck which aren't among `known`.
+pub fn remaining(known: &[&[Card]]) -> Vec<Card> {
+    let mut deck = Vec::with_capacity(52);
+
+    for rank in &ranks!() {
+        for suit in &suits!() {
+            let card = card!(*rank, *suit);
+            if !known.iter().any(|cards| cards.contains(&card)) {
+                deck.push(card);
+            }
+        }
+    }
+
+    deck
+}
+
+#[test]
+fn made_hands() {
+    use crate::card::face::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(1);
+    let board = cards!(Ace, Hearts; King, Hearts; Queen, Hearts; Jack, Hearts; Two, Clubs);
+
+    let royal = equity(
+        &cards!(Ten, Hearts; Three, Spades),
+        &board,
+        3,
+        200,
+        &mut rng,
+    );
+    assert_eq!(royal, 1.0);
+
+    // Nothing beats the straight on the board.
+    let board = cards!(Ace, Spades; King, Clubs; Queen, Diamonds; Jack, Hearts; Ten, Clubs);
+    let chop = equity(
+        &cards!(Two, Spades; Three, Diamonds),
+        &board,
+        1,
+        200,
+        &mut rng,
+    );
+    assert_eq!(chop, 0.5);
+}
+
+#[test]
+fn coin_flip() {
+    use crate::card::face::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(2);
+    let pair = equity(&cards!(Two, Spades; Two, Hearts), &[], 1, 4_000, &mut rng);
+    assert!((pair - 0.5).abs() < 0.05, "{}", pair);
+}
+
+#[test]
+fn remaining_cards() {
+    use crate::card::face::*;
+
+    let hole = cards!(Ace, Spades; Ace, Hearts);
+    let deck = remaining(&[&hole, &[]]);
+    assert_eq!(deck.len(), 50);
+    assert!(!deck.contains(&card!(Ace, Spades)));
+}
@@ -1,4 +1,4 @@
-use super::Hand;
+use super::{Category, Hand};
 use std::fmt;
 
 impl fmt::Display for Hand {
@@ -6,3 +6,20 @@ impl fmt::Display for Hand {
         write!(f, "{:?}", self.cards)
     }
 }
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Category::High => write!(f, "High card"),
+            Category::Pair => write!(f, "Pair"),
+            Category::TwoPair => write!(f, "Two pairs"),
+            Category::Trips => write!(f, "Three of a kind"),
+            Category::Straight => write!(f, "Straight"),
+            Category::Flush => write!(f, "Flush"),
+            Category::House => write!(f, "Full house"),
+            Category::Quads => write!(f, "Four of a kind"),
+            Category::StraightFlush => write!(f, "Straight flush"),
+            Category::Fives => write!(f, "Five of a kind"),
+        }
+    }
+}
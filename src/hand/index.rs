@@ -0,0 +1,451 @@
+//! Numbering of hands up to their suits.
+//!
+//! Hands that only differ by a relabelling of the suits play the same, so a
+//! solver or a lookup table needs only one of them. An `Indexer` numbers
+//! them from 0, each class of hands getting the same number, and turns the
+//! numbers back into a hand of the class.
+//!
+//! Cards are dealt in rounds, such as the hole cards then the flop. The
+//! cards of a round are a set, but rounds stay apart: a heart dealt with the
+//! hole cards isn't the same as one dealt on the flop.
+use super::value::strength;
+use crate::card::{Card, Rank, Suit};
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+
+/// Numbers hands dealt in rounds, up to their suits.
+///
+/// # Example
+/// ```
+/// # use aces_high::{*, card::face::*, hand::index::Indexer};
+/// let preflop = Indexer::new(&[2]);
+/// assert_eq!(preflop.len(), 169);
+/// let flops = Indexer::new(&[3]);
+/// assert_eq!(flops.len(), 1755);
+///
+/// let flop = Indexer::hand(3);
+/// let index = flop.index(&cards!(Ace, Spades; King, Spades; Queen, Spades; Seven, Hearts; Two, Diamonds));
+/// let same = flop.index(&cards!(King, Clubs; Ace, Clubs; Two, Hearts; Queen, Clubs; Seven, Spades));
+/// assert_eq!(index, same);
+///
+/// let hand = flop.unindex(index);
+/// assert_eq!(flop.index(&hand), index);
+/// assert_eq!(flop.weight(index), 24);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Indexer {
+    rounds: Vec<usize>,
+    /// How many cards of each suit each round has, for every way there is,
+    /// the suits with the most cards first.
+    shapes: Vec<Shape>,
+    /// The first index of each shape, then the number of indices.
+    offsets: Vec<u64>,
+}
+
+/// The number of cards of each of the four suits in each round.
+type Shape = Vec<Vec<usize>>;
+
+/// A suit of a hand: the ranks it has in each round, as its number of cards
+/// in each round and the number of those ranks among all there could be.
+type Part = (Vec<usize>, u64);
+
+impl Indexer {
+    /// Constructs an `Indexer` of hands dealt `rounds[0]` cards, then
+    /// `rounds[1]` and so on.
+    ///
+    /// # Panics
+    /// If more than 52 cards are dealt.
+    pub fn new(rounds: &[usize]) -> Self {
+        assert!(rounds.iter().sum::<usize>() <= 52, "more cards than a deck");
+
+        let mut shapes = BTreeSet::new();
+        add_shapes(rounds, &mut vec![Vec::new(); 4], &mut shapes);
+        let shapes: Vec<Shape> = shapes.into_iter().collect();
+
+        let mut offsets = vec![0];
+        for shape in &shapes {
+            let len = groups(shape)
+                .map(|(counts, suits)| multichoose(suit_size(counts), suits as u64))
+                .product::<u64>();
+            offsets.push(offsets.last().unwrap() + len);
+        }
+
+        Indexer {
+            rounds: rounds.to_vec(),
+            shapes,
+            offsets,
+        }
+    }
+
+    /// Constructs an `Indexer` of hole cards and `board` cards of a board
+    /// dealt as in hold'em, the flop being one round and the turn and
+    /// river one each.
+    ///
+    /// # Panics
+    /// If there can't be `board` cards on the board.
+    pub fn hand(board: usize) -> Self {
+        match board {
+            0 => Indexer::new(&[2]),
+            3 => Indexer::new(&[2, 3]),
+            4 => Indexer::new(&[2, 3, 1]),
+            5 => Indexer::new(&[2, 3, 1, 1]),
+            _ => panic!("no street has {} cards on the board", board),
+        }
+    }
+
+    pub fn rounds(&self) -> &[usize] {
+        &self.rounds
+    }
+
+    /// Returns the number of classes of hands.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> u64 {
+        *self.offsets.last().unwrap()
+    }
+
+    /// Returns the index of the class of `cards`, the cards of each round
+    /// one after the other.
+    ///
+    /// # Panics
+    /// If there aren't as many cards as the rounds deal, or a card is
+    /// dealt twice.
+    pub fn index(&self, cards: &[Card]) -> u64 {
+        assert_eq!(
+            cards.len(),
+            self.rounds.iter().sum::<usize>(),
+            "wrong number of cards"
+        );
+
+        let mut masks = vec![vec![0u16; self.rounds.len()]; 4];
+        let mut dealt = 0u64;
+        let mut cards = cards.iter();
+        for (round, &len) in self.rounds.iter().enumerate() {
+            for card in cards.by_ref().take(len) {
+                let bit = 1u64 << u8::from(*card);
+                assert!(dealt & bit == 0, "{} dealt twice", card);
+                dealt |= bit;
+                masks[card.suit as usize][round] |= 1 << strength(card.rank);
+            }
+        }
+
+        let mut parts: Vec<Part> = masks.iter().map(|masks| rank_suit(masks)).collect();
+        parts.sort_by(|a, b| (Reverse(&a.0), a.1).cmp(&(Reverse(&b.0), b.1)));
+        let shape: Shape = parts.iter().map(|(counts, _)| counts.clone()).collect();
+        let i = self.shapes.binary_search(&shape).unwrap();
+
+        let mut index = 0;
+        let mut parts = parts.iter();
+        for (counts, suits) in groups(&shape) {
+            let n = suit_size(counts);
+            let numbers: Vec<u64> = parts.by_ref().take(suits).map(|part| part.1).collect();
+            index = index * multichoose(n, suits as u64) + rank_multiset(&numbers);
+        }
+        self.offsets[i] + index
+    }
+
+    /// Returns a hand of the class numbered `index`, the cards of each round
+    /// one after the other, from the highest.
+    ///
+    /// # Panics
+    /// If there's no class numbered `index`.
+    pub fn unindex(&self, index: u64) -> Vec<Card> {
+        let parts = self.parts(index);
+        let mut cards = Vec::with_capacity(self.rounds.iter().sum());
+
+        let masks: Vec<Vec<u16>> = parts
+            .iter()
+            .map(|(counts, index)| unrank_suit(counts, *index))
+            .collect();
+        for round in 0..self.rounds.len() {
+            for strength in (0..13).rev() {
+                for (masks, &suit) in masks.iter().zip(&Suit::all()) {
+                    if masks[round] & 1 << strength != 0 {
+                        let rank = Rank::try_from(((strength + 1) % 13) as u8).unwrap();
+                        cards.push(Card::new(rank, suit));
+                    }
+                }
+            }
+        }
+        cards
+    }
+
+    /// Returns the number of hands in the class numbered `index`.
+    ///
+    /// # Panics
+    /// If there's no class numbered `index`.
+    pub fn weight(&self, index: u64) -> u64 {
+        let parts = self.parts(index);
+        // Suits alike can be swapped without changing the hand.
+        let mut weight = 24;
+        let mut alike = 1;
+        for pair in parts.windows(2) {
+            if pair[0] == pair[1] {
+                alike += 1;
+                weight /= alike;
+            } else {
+                alike = 1;
+            }
+        }
+        weight
+    }
+
+    /// Returns the suits of the class numbered `index`, in order.
+    fn parts(&self, index: u64) -> Vec<Part> {
+        assert!(index < self.len(), "no class numbered {}", index);
+        let i = match self.offsets.binary_search(&index) {
+            Ok(i) => i,
+            Err(i) => i - 1,
+        };
+        let shape = &self.shapes[i];
+
+        let groups: Vec<(&Vec<usize>, usize)> = groups(shape).collect();
+        let mut index = index - self.offsets[i];
+        let mut parts = Vec::with_capacity(4);
+        for &(counts, suits) in groups.iter().rev() {
+            let len = multichoose(suit_size(counts), suits as u64);
+            let numbers = unrank_multiset(index % len, suits);
+            index /= len;
+            for number in numbers.into_iter().rev() {
+                parts.push((counts.clone(), number));
+            }
+        }
+        parts.reverse();
+        parts
+    }
+}
+
+/// Adds every shape of the cards left in `rounds` to `shapes`, with
+/// `counts` dealt so far.
+fn add_shapes(rounds: &[usize], counts: &mut Vec<Vec<usize>>, shapes: &mut BTreeSet<Shape>) {
+    let (&len, rounds) = match rounds.split_first() {
+        Some(split) => split,
+        None => {
+            let mut shape = counts.clone();
+            shape.sort_by(|a, b| b.cmp(a));
+            shapes.insert(shape);
+            return;
+        }
+    };
+
+    fn split(
+        suit: usize,
+        left: usize,
+        rounds: &[usize],
+        counts: &mut Vec<Vec<usize>>,
+        shapes: &mut BTreeSet<Shape>,
+    ) {
+        if suit == 4 {
+            if left == 0 {
+                add_shapes(rounds, counts, shapes);
+            }
+            return;
+        }
+        let room = 13 - counts[suit].iter().sum::<usize>();
+        for count in 0..=left.min(room) {
+            counts[suit].push(count);
+            split(suit + 1, left - count, rounds, counts, shapes);
+            counts[suit].pop();
+        }
+    }
+    split(0, len, rounds, counts, shapes);
+}
+
+/// Returns the runs of suits alike in `shape`, with the cards of one of
+/// them and how many there are.
+fn groups(shape: &[Vec<usize>]) -> impl Iterator<Item = (&Vec<usize>, usize)> {
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        let counts = shape.get(i)?;
+        let suits = shape[i..]
+            .iter()
+            .take_while(|&other| other == counts)
+            .count();
+        i += suits;
+        Some((counts, suits))
+    })
+}
+
+/// Returns the number of ways one suit can have `counts` cards in each
+/// round.
+fn suit_size(counts: &[usize]) -> u64 {
+    let mut left = 13;
+    let mut size = 1;
+    for &count in counts {
+        size *= choose(left, count as u64);
+        left -= count as u64;
+    }
+    size
+}
+
+/// Numbers the ranks of one suit, `masks` having those of each round.
+fn rank_suit(masks: &[u16]) -> Part {
+    let mut used = 0u16;
+    let mut counts = Vec::with_capacity(masks.len());
+    let mut index = 0;
+    for &mask in masks {
+        let count = mask.count_ones() as usize;
+        let mut set = 0;
+        let mut i = 0;
+        for bit in (0..13).filter(|bit| mask & 1 << bit != 0) {
+            // The rank's place among those not dealt in earlier rounds.
+            let place = bit - (used & ((1 << bit) - 1)).count_ones();
+            i += 1;
+            set += choose(u64::from(place), i);
+        }
+        index = index * choose(13 - u64::from(used.count_ones()), count as u64) + set;
+        counts.push(count);
+        used |= mask;
+    }
+    (counts, index)
+}
+
+/// Returns the ranks of each round of one suit, from the number given by
+/// `rank_suit`.
+fn unrank_suit(counts: &[usize], mut index: u64) -> Vec<u16> {
+    let mut left = 13;
+    let mut sets = Vec::with_capacity(counts.len());
+    for &count in counts {
+        sets.push((left, count));
+        left -= count as u64;
+    }
+    let mut places: Vec<Vec<u64>> = vec![Vec::new(); counts.len()];
+    for (round, &(left, count)) in sets.iter().enumerate().rev() {
+        let len = choose(left, count as u64);
+        places[round] = unrank_set(index % len, count, left);
+        index /= len;
+    }
+
+    let mut used = 0u16;
+    places
+        .into_iter()
+        .map(|places| {
+            let free: Vec<u16> = (0..13).filter(|bit| used & 1 << bit == 0).collect();
+            let mask = places
+                .into_iter()
+                .fold(0, |mask, place| mask | 1 << free[place as usize]);
+            used |= mask;
+            mask
+        })
+        .collect()
+}
+
+/// Numbers a sorted multiset, as a set in the colexicographic order.
+fn rank_multiset(values: &[u64]) -> u64 {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| choose(value + i as u64, i as u64 + 1))
+        .sum()
+}
+
+fn unrank_multiset(index: u64, len: usize) -> Vec<u64> {
+    let mut set = unrank_set(index, len, u64::MAX);
+    set.reverse();
+    set.into_iter()
+        .enumerate()
+        .map(|(i, value)| value - i as u64)
+        .collect()
+}
+
+/// Returns the set of `len` numbers below `limit` with the colexicographic
+/// number `index`, from the largest.
+fn unrank_set(mut index: u64, len: usize, limit: u64) -> Vec<u64> {
+    let mut set = Vec::with_capacity(len);
+    let mut limit = limit;
+    for k in (1..=len as u64).rev() {
+        // The largest value with `choose(value, k) <= index`.
+        let (mut low, mut high) = (k - 1, limit);
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if choose(mid, k) <= index {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        index -= choose(low, k);
+        set.push(low);
+        limit = low;
+    }
+    set
+}
+
+/// Returns the number of multisets of `k` of `n` things.
+fn multichoose(n: u64, k: u64) -> u64 {
+    if k == 0 {
+        1
+    } else {
+        choose(n + k - 1, k)
+    }
+}
+
+/// Returns the binomial coefficient, or `u64::MAX` if it's larger.
+fn choose(n: u64, k: u64) -> u64 {
+    if k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    let mut c = 1u128;
+    for i in 0..u128::from(k) {
+        c = c * (u128::from(n) - i) / (i + 1);
+        if c > u128::from(u64::MAX) {
+            return u64::MAX;
+        }
+    }
+    c as u64
+}
+
+#[test]
+fn classes() {
+    use super::class::Class;
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+    let preflop = Indexer::new(&[2]);
+    let mut seen = [false; 169];
+    for class in Class::all() {
+        let combos = class.combos();
+        let index = preflop.index(&combos[0]);
+        assert!(!seen[index as usize]);
+        seen[index as usize] = true;
+        assert!(combos.iter().all(|combo| preflop.index(combo) == index));
+        assert_eq!(preflop.weight(index), class.count() as u64);
+        let hand = preflop.unindex(index);
+        assert_eq!(Class::of(hand[0], hand[1]), class);
+    }
+
+    // Every flop, against the classes.
+    let flops = Indexer::new(&[3]);
+    let deck = super::equity::remaining(&[]);
+    let mut counts = vec![0; flops.len() as usize];
+    for a in 0..52 {
+        for b in a + 1..52 {
+            for c in b + 1..52 {
+                counts[flops.index(&[deck[a], deck[b], deck[c]]) as usize] += 1;
+            }
+        }
+    }
+    for (index, &count) in counts.iter().enumerate() {
+        assert_eq!(flops.weight(index as u64), count);
+        assert_eq!(flops.index(&flops.unindex(index as u64)), index as u64);
+    }
+
+    let sizes = [(3, 1_286_792), (4, 55_190_538), (5, 2_428_287_420)];
+    let mut rng = StdRng::seed_from_u64(5);
+    for &(board, len) in &sizes {
+        let indexer = Indexer::hand(board);
+        assert_eq!(indexer.len(), len);
+        for _ in 0..200 {
+            let hand: Vec<Card> = deck.choose_multiple(&mut rng, board + 2).cloned().collect();
+            let index = indexer.index(&hand);
+            let permutations = Suit::permutations();
+            let permutation = permutations.choose(&mut rng).unwrap();
+            let mut permuted: Vec<Card> =
+                hand.iter().map(|card| card.permute(permutation)).collect();
+            permuted[..2].reverse();
+            assert_eq!(indexer.index(&permuted), index);
+            assert_eq!(indexer.index(&indexer.unindex(index)), index);
+        }
+    }
+    assert_eq!(Suit::permutations().len(), 24);
+}
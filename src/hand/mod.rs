@@ -2,16 +2,24 @@
 pub mod rank;
 #[macro_use]
 pub mod macros;
+pub mod class;
+pub mod equity;
 pub mod error;
 pub mod extra;
+pub mod index;
+pub mod preflop;
 mod fmt;
 mod r#impl;
+pub mod value;
 
 extern crate rand;
 
 use crate::card::Card;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 pub use error::Error;
 pub use extra::*;
+pub use value::{Category, Value};
 
 /**
  * A hand consist of all cards "in hand or private cards" and
@@ -22,6 +30,7 @@ pub use extra::*;
  * Only the highest ranking cards are saved in it.
  */
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Hand {
     cards: Vec<Card>,
     rank: rank::Rank,
@@ -0,0 +1,176 @@
+//! Heads up all-in equities before the flop, between every two starting
+//! hand classes.
+//!
+//! The equities are exact: every deal of the two classes that doesn't share
+//! a card is played out on every board, which takes minutes, so the results
+//! are embedded in the crate as worked out by `tabulate`.
+use super::class::{Class, COUNT};
+use super::index::Indexer;
+use super::Value;
+use crate::card::Card;
+use std::io::{self, Write};
+
+/// For every two classes, the first numbered before the second, the points
+/// of the first over all deals and boards, two for a win and one for a
+/// split, as a `u32`, then the number of deals as a `u8`.
+static TABLE: &[u8] = include_bytes!("preflop.bin");
+
+/// The number of boards for two hands, 48 choose 5.
+const BOARDS: u64 = 1_712_304;
+
+/// Returns the equity of a hand of class `hero` all in against one of class
+/// `villain`, from 0 to 1, a split counting as half.
+///
+/// # Example
+/// ```
+/// # use aces_high::hand::preflop;
+/// let equity = |a: &str, b: &str| preflop::equity(a.parse().unwrap(), b.parse().unwrap());
+/// assert!((equity("AA", "KK") - 0.8195).abs() < 1e-4);
+/// assert!((equity("KK", "AA") - 0.1805).abs() < 1e-4);
+/// assert!((equity("AKs", "QQ") - 0.4605).abs() < 1e-4);
+/// assert_eq!(equity("T9s", "T9s"), 0.5);
+/// ```
+pub fn equity(hero: Class, villain: Class) -> f64 {
+    let (points, deals) = record(hero, villain);
+    points as f64 / (2 * BOARDS * deals) as f64
+}
+
+/// Returns the number of ways to deal a hand of class `hero` and one of
+/// class `villain` without sharing a card.
+pub fn deals(hero: Class, villain: Class) -> u32 {
+    record(hero, villain).1 as u32
+}
+
+/// Returns the equity of a hand of class `hero` all in against a random
+/// hand, every hand without a card of the hero's as likely.
+///
+/// # Example
+/// ```
+/// # use aces_high::hand::preflop;
+/// let versus_random = |class: &str| preflop::versus_random(class.parse().unwrap());
+/// assert!((versus_random("AA") - 0.8520).abs() < 1e-4);
+/// assert!((versus_random("72o") - 0.3458).abs() < 1e-4);
+/// ```
+pub fn versus_random(hero: Class) -> f64 {
+    let (points, deals) = Class::all()
+        .map(|villain| record(hero, villain))
+        .fold((0, 0), |(points, deals), record| {
+            (points + record.0, deals + record.1)
+        });
+    points as f64 / (2 * BOARDS * deals) as f64
+}
+
+/// Returns the points of `hero` against `villain`, and the number of
+/// deals.
+fn record(hero: Class, villain: Class) -> (u64, u64) {
+    let (a, b) = (hero.index(), villain.index());
+    let (points, deals) = entry(a.min(b), a.max(b));
+    if a <= b {
+        (points, deals)
+    } else {
+        (2 * BOARDS * deals - points, deals)
+    }
+}
+
+/// Returns the entry of the classes numbered `a` and `b`, `a` first.
+fn entry(a: usize, b: usize) -> (u64, u64) {
+    // Entries before row `a`, then into it.
+    let i = a * COUNT - a * (a + 1) / 2 + b;
+    let bytes = &TABLE[i * 5..i * 5 + 5];
+    let points = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    (u64::from(points), u64::from(bytes[4]))
+}
+
+/// Works out the table embedded in the crate, and writes it to `writer`.
+/// This plays out hundreds of billions of showdowns, and takes minutes.
+pub fn tabulate<W: Write>(writer: &mut W) -> io::Result<()> {
+    let hands: Vec<([Card; 2], usize, u64)> = Class::all()
+        .flat_map(|class| {
+            class.combos().into_iter().map(move |combo| {
+                let mask = combo
+                    .iter()
+                    .fold(0, |mask, &card| mask | 1 << u8::from(card));
+                (combo, class.index(), mask)
+            })
+        })
+        .collect();
+
+    let mut points = vec![0u64; COUNT * COUNT];
+    let mut deals = vec![0u64; COUNT * COUNT];
+    for (i, (_, a, x)) in hands.iter().enumerate() {
+        for (_, b, y) in &hands[i..] {
+            if x & y == 0 {
+                deals[a * COUNT + b] += 1;
+                deals[b * COUNT + a] += 1;
+            }
+        }
+    }
+
+    // Boards the same but for their suits play the same against classes.
+    let boards = Indexer::new(&[5]);
+    let mut values = Vec::with_capacity(hands.len());
+    let mut cards = [Card::default(); 7];
+    for index in 0..boards.len() {
+        let board = boards.unindex(index);
+        let weight = boards.weight(index);
+        let mask: u64 = board
+            .iter()
+            .fold(0, |mask, &card| mask | 1 << u8::from(card));
+        cards[2..].copy_from_slice(&board);
+
+        values.clear();
+        for (combo, class, x) in &hands {
+            if x & mask == 0 {
+                cards[..2].copy_from_slice(combo);
+                values.push((*class, *x, Value::new(&cards)));
+            }
+        }
+
+        for (i, &(a, x, first)) in values.iter().enumerate() {
+            for &(b, y, second) in &values[i + 1..] {
+                if x & y == 0 {
+                    let won = match first.cmp(&second) {
+                        std::cmp::Ordering::Greater => 2,
+                        std::cmp::Ordering::Equal => 1,
+                        std::cmp::Ordering::Less => 0,
+                    };
+                    points[a * COUNT + b] += weight * won;
+                    points[b * COUNT + a] += weight * (2 - won);
+                }
+            }
+        }
+    }
+
+    for a in 0..COUNT {
+        for b in a..COUNT {
+            let (points, deals) = (points[a * COUNT + b], deals[a * COUNT + b]);
+            writer.write_all(&(points as u32).to_le_bytes())?;
+            writer.write_all(&[deals as u8])?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn symmetric() {
+    assert_eq!(TABLE.len(), COUNT * (COUNT + 1) / 2 * 5);
+    for a in Class::all() {
+        let mut total = 0;
+        for b in Class::all() {
+            assert!((equity(a, b) + equity(b, a) - 1.0).abs() < 1e-12);
+            assert_eq!(deals(a, b), deals(b, a));
+            total += deals(a, b);
+        }
+        // Every hand without a card of the hero's, of 50 left.
+        assert_eq!(total as usize, a.count() * 1225);
+        assert!(versus_random(a) > 0.3 && versus_random(a) < 0.86);
+    }
+}
+
+#[test]
+#[ignore]
+fn tabulated() {
+    let mut table = Vec::new();
+    tabulate(&mut table).unwrap();
+    assert!(table == TABLE);
+}
@@ -1,6 +1,8 @@
 use super::{mediator, srank, Error};
 use crate::card::{Rank, Suit};
 use mimpl::mimpl;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use seq_macro::seq;
 use std::cmp::Ordering;
 
@@ -9,60 +11,70 @@ use std::cmp::Ordering;
 /* -------------------------------------------------------------------------- */
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct High {
     pub rank: Rank,
     pub suit: Suit,
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Pair {
     pub crank: Rank,
     pub suits: [Suit; 2],
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TwoPair {
     pub pair0: Pair,
     pub pair1: Pair,
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Trips {
     pub crank: Rank,
     pub suits: [Suit; 3],
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Straight {
     pub srank: srank::SRank,
     pub suits: [Suit; 5],
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Flush {
     pub ranks: [Rank; 5],
     pub csuit: Suit,
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct House {
     pub trips: Trips,
     pub pair: Pair,
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Quads {
     pub crank: Rank,
     pub suits: [Suit; 4],
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StraightFlush {
     pub srank: srank::SRank,
     pub csuit: Suit,
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Fives {
     pub crank: Rank,
     pub suits: [Suit; 5],
@@ -1,6 +1,7 @@
 use super::{inner, Error, Rank};
-use crate::card::Card;
+use crate::card::{self, Card};
 use mimpl::mimpl;
+use num_traits::FromPrimitive;
 use seq_macro::seq;
 use std::convert::{TryFrom, TryInto};
 
@@ -52,13 +53,14 @@ mimpl!(From; inner::Trips, Trips, |trips: inner::Trips|
     Self(seq!(n in 0..3{[#(Card{rank: trips.crank,  suit: trips.suits[n]},)*]}))
 );
 
+// The Ace follows the King at the top of a straight.
 mimpl!(From; inner::Straight, Straight, |straight: inner::Straight| {
-        let mut straight = straight.clone();
-        Self(seq!(n in 0..4{[
-            // Dont iterate on first
-            Card{rank: straight.srank.into(), suit: straight.suits[0]},
-            #(Card{rank: straight.srank.next().unwrap().into(), suit:  straight.suits[n]},)*
-        ]}))
+        let low = card::Rank::from(straight.srank) as u32;
+        let rank = |n: u32| card::Rank::from_u32((low + n) % 13).unwrap();
+        Self(seq!(n in 0..5{[#(Card{
+            rank: rank(n),
+            suit: straight.suits[n],
+        },)*]}))
     }
 );
 
@@ -75,11 +77,12 @@ mimpl!(From; inner::Quads, Quads, |quads: inner::Quads|
 );
 
 mimpl!(From; inner::StraightFlush, StraightFlush, |sf: inner::StraightFlush| {
-        let mut sf = sf.clone();
-        Self(seq!(n in 0..4{[
-            Card{rank: sf.srank.into(), suit: sf.csuit}, // Dont iterate on first
-            #(Card{rank: sf.srank.next().unwrap().into(), suit: sf.csuit},)*
-        ]}))
+        let low = card::Rank::from(sf.srank) as u32;
+        let rank = |n: u32| card::Rank::from_u32((low + n) % 13).unwrap();
+        Self(seq!(n in 0..5{[#(Card{
+            rank: rank(n),
+            suit: sf.csuit,
+        },)*]}))
     }
 );
 
@@ -2,6 +2,8 @@ mod fmt;
 mod r#impl;
 mod inner;
 pub mod mediator;
+#[cfg(feature = "serde")]
+mod serde;
 mod srank;
 
 pub use super::Error;
@@ -11,6 +13,9 @@ pub use super::Error;
 /// TODO: Document
 ///
 /// Only exposed interface. Implements try from
+///
+/// With the `serde` feature, it's serialized as its category tagged with the
+/// cards making it, such as `{"Pair":["As","Ad"]}`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Rank {
     High(inner::High),
@@ -0,0 +1,107 @@
+use super::Rank;
+use crate::card::Card;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// How a `Rank` looks serialized: its category and the cards making it.
+#[derive(Serialize, Deserialize)]
+enum Tagged {
+    High(Vec<Card>),
+    Pair(Vec<Card>),
+    TwoPair(Vec<Card>),
+    Trips(Vec<Card>),
+    Straight(Vec<Card>),
+    Flush(Vec<Card>),
+    House(Vec<Card>),
+    Quads(Vec<Card>),
+    StraightFlush(Vec<Card>),
+    Fives(Vec<Card>),
+}
+
+impl Serialize for Rank {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let cards = self.to_vec();
+        match self {
+            Rank::High(_) => Tagged::High(cards),
+            Rank::Pair(_) => Tagged::Pair(cards),
+            Rank::TwoPair(_) => Tagged::TwoPair(cards),
+            Rank::Trips(_) => Tagged::Trips(cards),
+            Rank::Straight(_) => Tagged::Straight(cards),
+            Rank::Flush(_) => Tagged::Flush(cards),
+            Rank::House(_) => Tagged::House(cards),
+            Rank::Quads(_) => Tagged::Quads(cards),
+            Rank::StraightFlush(_) => Tagged::StraightFlush(cards),
+            Rank::Fives(_) => Tagged::Fives(cards),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Returns `cards` if there are exactly `len` of them, since the
+/// constructors of ranks only look at the last cards of a slice.
+fn exactly<'de, D: Deserializer<'de>>(cards: &[Card], len: usize) -> Result<&[Card], D::Error> {
+    if cards.len() == len {
+        Ok(cards)
+    } else {
+        let expected = format!("{} cards", len);
+        Err(de::Error::invalid_length(cards.len(), &expected.as_str()))
+    }
+}
+
+impl<'de> Deserialize<'de> for Rank {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let tagged = Tagged::deserialize(deserializer)?;
+        let rank = match &tagged {
+            Tagged::High(cards) => Ok(Rank::high_from(&exactly::<D>(cards, 1)?[0])),
+            Tagged::Pair(cards) => Rank::pair_try_from(exactly::<D>(cards, 2)?),
+            Tagged::TwoPair(cards) => {
+                let cards = exactly::<D>(cards, 4)?;
+                Rank::two_pair_try_from(&cards[..2], &cards[2..])
+            }
+            Tagged::Trips(cards) => Rank::trips_try_from(exactly::<D>(cards, 3)?),
+            Tagged::Straight(cards) => Rank::straight_try_from(exactly::<D>(cards, 5)?),
+            Tagged::Flush(cards) => Rank::flush_try_from(exactly::<D>(cards, 5)?),
+            Tagged::House(cards) => {
+                let cards = exactly::<D>(cards, 5)?;
+                Rank::house_try_from(&cards[..3], &cards[3..])
+            }
+            Tagged::Quads(cards) => Rank::quads_try_from(exactly::<D>(cards, 4)?),
+            Tagged::StraightFlush(cards) => Rank::straight_flush_try_from(exactly::<D>(cards, 5)?),
+            Tagged::Fives(cards) => Rank::fives_try_from(exactly::<D>(cards, 5)?),
+        }
+        .map_err(|error| de::Error::custom(format!("{:?}", error)))?;
+
+        // Straights are built from their lowest card alone.
+        match (&tagged, rank) {
+            (Tagged::Straight(cards), rank) | (Tagged::StraightFlush(cards), rank)
+                if rank.to_vec() != *cards =>
+            {
+                Err(de::Error::custom(format!("{:?} isn't a straight", cards)))
+            }
+            _ => Ok(rank),
+        }
+    }
+}
+
+#[test]
+fn json() {
+    use crate::card::{Rank::*, Suit::*};
+
+    let pair = Rank::pair_try_from(&cards!(Ace, Spades; Ace, Diamonds)).unwrap();
+    let json = serde_json::to_string(&pair).unwrap();
+    assert_eq!(json, r#"{"Pair":["As","Ad"]}"#);
+    assert_eq!(serde_json::from_str::<Rank>(&json).unwrap(), pair);
+
+    let wheel = cards!(Ace, Hearts; Two, Clubs; Three, Clubs; Four, Spades; Five, Hearts);
+    let straight = Rank::straight_try_from(&wheel).unwrap();
+    let json = serde_json::to_string(&straight).unwrap();
+    assert_eq!(json, r#"{"Straight":["Ah","2c","3c","4s","5h"]}"#);
+    assert_eq!(serde_json::from_str::<Rank>(&json).unwrap(), straight);
+
+    let house = r#"{"House":["Kd","Kh","Ks","2c","2d"]}"#;
+    let rank = serde_json::from_str::<Rank>(house).unwrap();
+    assert_eq!(serde_json::to_string(&rank).unwrap(), house);
+
+    assert!(serde_json::from_str::<Rank>(r#"{"Pair":["As","Ad","Ac"]}"#).is_err());
+    assert!(serde_json::from_str::<Rank>(r#"{"Pair":["As","Kd"]}"#).is_err());
+    assert!(serde_json::from_str::<Rank>(r#"{"Straight":["Ah","2c","3c","4s","9h"]}"#).is_err());
+}
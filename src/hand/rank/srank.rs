@@ -3,6 +3,8 @@ use crate::card::{self, Card};
 use mimpl::mimpl;
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::convert::{From, TryFrom};
 use std::error;
 use variant_count::VariantCount;
@@ -13,6 +15,7 @@ use variant_count::VariantCount;
 ///         [Ace, Two, Three, Four, Five]
 ///         [Ten, Jack, Queen, King, Ace]     
 #[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, PartialOrd, Ord, VariantCount)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SRank {
     Ace,
     Two,
@@ -0,0 +1,272 @@
+use super::Hand;
+use crate::card::{self, Card};
+
+/// Category of a poker hand, from worst to best.
+/// Variants are named after those of `hand::rank::Rank`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    High,
+    Pair,
+    TwoPair,
+    Trips,
+    Straight,
+    Flush,
+    House,
+    Quads,
+    StraightFlush,
+    Fives,
+}
+
+/// Strength of the best five card hand that can be made from some cards.
+/// A greater `Value` beats a lesser one, and equal values split the pot.
+///
+/// # Example
+/// ```
+/// # use aces_high::{*, hand::value::*, card::face::*};
+/// let flush = Value::new(&cards!(
+///     Two, Hearts; Nine, Hearts; Jack, Hearts; Four, Hearts; King, Hearts; King, Spades
+/// ));
+/// let straight = Value::new(&cards!(
+///     Ace, Spades; Two, Clubs; Three, Hearts; Four, Diamonds; Five, Clubs
+/// ));
+///
+/// assert_eq!(flush.category(), Category::Flush);
+/// assert_eq!(straight.category(), Category::Straight);
+/// assert!(flush > straight);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Value(u32);
+
+/// Returns the strength of a card's rank, where Two is 0 and Ace is 12.
+pub fn strength(rank: card::Rank) -> u8 {
+    (rank as u8 + 12) % 13
+}
+
+const CATEGORIES: [Category; 10] = [
+    Category::High,
+    Category::Pair,
+    Category::TwoPair,
+    Category::Trips,
+    Category::Straight,
+    Category::Flush,
+    Category::House,
+    Category::Quads,
+    Category::StraightFlush,
+    Category::Fives,
+];
+
+const WHEEL: u16 = 0b1_0000_0000_1111;
+
+impl Value {
+    /// Evaluates the best five card hand among `cards`.
+    /// Any number of cards may be given. Fewer than five are valued as if
+    /// the missing cards were worthless.
+    pub fn new(cards: &[Card]) -> Value {
+        let mut counts = [0u8; 13];
+        let mut suits = [0u16; 4];
+
+        for card in cards {
+            let strength = strength(card.rank);
+            counts[strength as usize] += 1;
+            suits[card.suit as usize] |= 1 << strength;
+        }
+
+        let ranks = suits.iter().fold(0, |ranks, suit| ranks | suit);
+
+        // Ranks by how many there are of them, then by strength. Best first.
+        let mut groups = [(0u8, 0u8); 13];
+        for (strength, group) in groups.iter_mut().enumerate() {
+            *group = (counts[strength], strength as u8);
+        }
+        groups.sort_unstable_by(|a, b| b.cmp(a));
+
+        let without = |strengths: &[u8]| {
+            strengths
+                .iter()
+                .fold(ranks, |ranks, strength| ranks & !(1 << strength))
+        };
+
+        match groups {
+            [(count, fives), ..] if count >= 5 => {
+                return Value::pack(Category::Fives, Some(fives));
+            }
+            _ => (),
+        }
+
+        if let Some(high) = suits.iter().filter_map(|&suit| straight(suit)).max() {
+            return Value::pack(Category::StraightFlush, Some(high));
+        }
+
+        match groups {
+            [(4, quads), ..] => {
+                return Value::pack(
+                    Category::Quads,
+                    Some(quads).into_iter().chain(top(without(&[quads])).take(1)),
+                );
+            }
+            [(3, trips), (2..=3, pair), ..] => {
+                return Value::pack(Category::House, [trips, pair].iter().copied());
+            }
+            _ => (),
+        }
+
+        if let Some(flush) = suits
+            .iter()
+            .filter(|suit| suit.count_ones() >= 5)
+            .map(|&suit| Value::pack(Category::Flush, top(suit).take(5)))
+            .max()
+        {
+            return flush;
+        }
+
+        if let Some(high) = straight(ranks) {
+            return Value::pack(Category::Straight, Some(high));
+        }
+
+        match groups {
+            [(3, trips), ..] => Value::pack(
+                Category::Trips,
+                Some(trips).into_iter().chain(top(without(&[trips])).take(2)),
+            ),
+            [(2, high), (2, low), ..] => Value::pack(
+                Category::TwoPair,
+                [high, low]
+                    .iter()
+                    .copied()
+                    .chain(top(without(&[high, low])).take(1)),
+            ),
+            [(2, pair), ..] => Value::pack(
+                Category::Pair,
+                Some(pair).into_iter().chain(top(without(&[pair])).take(3)),
+            ),
+            _ => Value::pack(Category::High, top(ranks).take(5)),
+        }
+    }
+
+    /// Returns the category of the hand.
+    pub fn category(self) -> Category {
+        CATEGORIES[(self.0 >> 20) as usize]
+    }
+
+    /// Returns the encoded value. Comparing these compares the hands.
+    pub fn to_u32(self) -> u32 {
+        self.0
+    }
+
+    fn pack<I: IntoIterator<Item = u8>>(category: Category, strengths: I) -> Value {
+        let mut value = (category as u32) << 20;
+        for (i, strength) in strengths.into_iter().take(5).enumerate() {
+            value |= (strength as u32) << (16 - 4 * i);
+        }
+        Value(value)
+    }
+}
+
+/// Returns the strength of the highest card of the best straight in `ranks`.
+fn straight(ranks: u16) -> Option<u8> {
+    (4..13u8)
+        .rev()
+        .find(|&high| (ranks >> (high - 4)) & 0b1_1111 == 0b1_1111)
+        .or_else(|| if ranks & WHEEL == WHEEL { Some(3) } else { None })
+}
+
+/// Returns the strengths in `ranks`, best first.
+fn top(ranks: u16) -> impl Iterator<Item = u8> {
+    (0..13u8)
+        .rev()
+        .filter(move |&strength| ranks & (1 << strength) != 0)
+}
+
+impl Hand {
+    /// Evaluates the best five card hand among `cards` and `community`.
+    pub fn evaluate(cards: &[Card], community: &[Card]) -> Value {
+        let all: Vec<Card> = cards.iter().chain(community).copied().collect();
+        Value::new(&all)
+    }
+}
+
+#[test]
+fn categories() {
+    use crate::card::face::*;
+
+    let category = |cards: &[Card]| Value::new(cards).category();
+
+    assert_eq!(category(&cards!(Ace, Spades; King, Hearts)), Category::High);
+    assert_eq!(category(&cards!(Two, Spades; Two, Hearts; King, Hearts)), Category::Pair);
+    assert_eq!(
+        category(&cards!(Two, Spades; Two, Hearts; King, Hearts; King, Clubs; Three, Clubs)),
+        Category::TwoPair
+    );
+    assert_eq!(
+        category(&cards!(Two, Spades; Two, Hearts; Two, Clubs; King, Clubs)),
+        Category::Trips
+    );
+    assert_eq!(
+        category(&cards!(Ten, Spades; Jack, Hearts; Queen, Clubs; King, Clubs; Ace, Hearts)),
+        Category::Straight
+    );
+    assert_eq!(
+        category(&cards!(Two, Spades; Two, Hearts; Two, Clubs; King, Clubs; King, Hearts)),
+        Category::House
+    );
+    assert_eq!(
+        category(&cards!(Two, Spades; Two, Hearts; Two, Clubs; Two, Diamonds; King, Hearts)),
+        Category::Quads
+    );
+    assert_eq!(
+        category(&cards!(Ace, Clubs; Two, Clubs; Three, Clubs; Four, Clubs; Five, Clubs)),
+        Category::StraightFlush
+    );
+    assert_eq!(
+        category(&cards!(Ace, Clubs; Ace, Clubs; Ace, Hearts; Ace, Spades; Ace, Diamonds)),
+        Category::Fives
+    );
+}
+
+#[test]
+fn comparisons() {
+    use crate::card::face::*;
+
+    let value = |cards: &[Card]| Value::new(cards);
+
+    // Aces are high.
+    assert!(value(&cards!(Ace, Spades; Ace, Hearts)) > value(&cards!(King, Spades; King, Hearts)));
+    // Wheel is the lowest straight.
+    assert!(
+        value(&cards!(Ace, Spades; Two, Hearts; Three, Clubs; Four, Clubs; Five, Hearts))
+            < value(&cards!(Two, Hearts; Three, Clubs; Four, Clubs; Five, Hearts; Six, Spades))
+    );
+    // Kickers decide.
+    assert!(
+        value(&cards!(Nine, Spades; Nine, Hearts; Ace, Clubs; Four, Clubs; Three, Hearts))
+            > value(&cards!(Nine, Clubs; Nine, Diamonds; King, Clubs; Queen, Clubs; Jack, Hearts))
+    );
+    // Only five cards count.
+    assert_eq!(
+        value(&cards!(
+            Ace, Spades; King, Hearts; Nine, Clubs; Eight, Clubs; Six, Hearts; Four, Spades; Two, Spades
+        )),
+        value(&cards!(
+            Ace, Clubs; King, Spades; Nine, Hearts; Eight, Diamonds; Six, Spades; Three, Spades; Two, Hearts
+        ))
+    );
+    // A third pair can play as the kicker.
+    assert_eq!(
+        value(&cards!(
+            King, Spades; King, Hearts; Nine, Clubs; Nine, Hearts; Five, Hearts; Five, Spades; Two, Spades
+        )),
+        value(&cards!(King, Clubs; King, Diamonds; Nine, Spades; Nine, Diamonds; Five, Clubs))
+    );
+    // Two trips make the best house.
+    assert_eq!(
+        value(&cards!(
+            Four, Spades; Four, Hearts; Four, Clubs; Seven, Hearts; Seven, Spades; Seven, Clubs
+        ))
+        .category(),
+        Category::House
+    );
+    assert!(
+        value(&cards!(Two, Spades; Two, Hearts; Two, Clubs; Two, Diamonds; Ace, Hearts))
+            > value(&cards!(Ace, Spades; Ace, Hearts; Ace, Clubs; King, Clubs; King, Hearts))
+    );
+}
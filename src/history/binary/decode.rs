@@ -0,0 +1,268 @@
+use super::{Error, BIG_BLIND_ANTE, RETURNED, SEED, TOURNAMENT};
+use crate::card::Card;
+use crate::history::{Blind, Game, History, Post, Seat};
+use crate::player::{Action, Decision};
+use crate::table::{
+    pot::Pot,
+    structure::{Betting, FixedLimit, SpreadLimit},
+    Config, Street,
+};
+use std::convert::TryFrom;
+use std::io::{self, Read};
+
+/// Reads the parts of a hand from encoded data.
+#[derive(Debug)]
+pub(super) struct Input<R: Read>(pub(super) R);
+
+impl<R: Read> Input<R> {
+    /// Reads the next byte, or `None` at the end of the data.
+    pub(super) fn next(&mut self) -> Result<Option<u8>, Error> {
+        let mut byte = [0];
+        loop {
+            return match self.0.read(&mut byte) {
+                Ok(0) => Ok(None),
+                Ok(_) => Ok(Some(byte[0])),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => Err(error.into()),
+            };
+        }
+    }
+
+    pub(super) fn byte(&mut self) -> Result<u8, Error> {
+        self.next()?.ok_or(Error::Truncated)
+    }
+
+    fn varint(&mut self) -> Result<u64, Error> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            let bits = u64::from(byte & 0x7f);
+            if bits << shift >> shift != bits {
+                return Err(Error::Overflow);
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(Error::Overflow)
+    }
+
+    fn int<T: TryFrom<u64>>(&mut self) -> Result<T, Error> {
+        T::try_from(self.varint()?).map_err(|_| Error::Overflow)
+    }
+
+    fn seat(&mut self, seats: usize) -> Result<usize, Error> {
+        match self.int()? {
+            seat if seat < seats => Ok(seat),
+            seat => Err(Error::BadSeat(seat)),
+        }
+    }
+
+    fn seats(&mut self, seats: usize) -> Result<Vec<usize>, Error> {
+        (0..self.int::<usize>()?)
+            .map(|_| self.seat(seats))
+            .collect()
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        let len = self.varint()?;
+        let mut bytes = Vec::new();
+        (&mut self.0).take(len).read_to_end(&mut bytes)?;
+        if bytes.len() as u64 != len {
+            return Err(Error::Truncated);
+        }
+        String::from_utf8(bytes).map_err(|_| Error::BadName)
+    }
+
+    /// Reads `count` cards packed in 6 bits each, none of them twice.
+    pub(super) fn unpack(&mut self, count: usize) -> Result<Vec<Card>, Error> {
+        if count > 52 {
+            return Err(Error::Overflow);
+        }
+
+        let mut cards = Vec::with_capacity(count);
+        let mut seen = 0u64;
+        let mut bits: u32 = 0;
+        let mut len = 0;
+        while cards.len() < count {
+            if len < 6 {
+                bits = bits << 8 | u32::from(self.byte()?);
+                len += 8;
+            }
+            len -= 6;
+            let index = (bits >> len) as u8;
+            bits &= (1 << len) - 1;
+
+            let card = Card::try_from(index).map_err(|_| Error::BadCard(index))?;
+            if seen & 1 << index != 0 {
+                return Err(Error::BadCard(index));
+            }
+            seen |= 1 << index;
+            cards.push(card);
+        }
+        Ok(cards)
+    }
+
+    /// Reads the rest of a hand, after its `flags`.
+    pub(super) fn hand(&mut self, flags: u8) -> Result<History, Error> {
+        let id = self.varint()?;
+        let tournament = match flags & TOURNAMENT {
+            0 => None,
+            _ => Some(self.varint()?),
+        };
+        let table = self.string()?;
+        let game = match self.varint()? {
+            0 => Game::Holdem,
+            1 => Game::Omaha,
+            tag => return Err(Error::BadTag { what: "game", tag }),
+        };
+
+        let betting = match self.varint()? {
+            0 => Betting::NoLimit,
+            1 => Betting::PotLimit,
+            2 => Betting::FixedLimit(FixedLimit {
+                small_bet: self.int()?,
+                big_bet: self.int()?,
+                cap: self.int()?,
+            }),
+            3 => Betting::SpreadLimit(SpreadLimit {
+                min: self.int()?,
+                max: self.int()?,
+            }),
+            tag => {
+                return Err(Error::BadTag {
+                    what: "betting",
+                    tag,
+                })
+            }
+        };
+        let config = Config {
+            betting,
+            small_blind: self.int()?,
+            big_blind: self.int()?,
+            ante: self.int()?,
+            big_blind_ante: flags & BIG_BLIND_ANTE != 0,
+        };
+
+        let mut seats = Vec::new();
+        let mut holes = Vec::new();
+        for _ in 0..self.int::<usize>()? {
+            seats.push(Seat {
+                name: self.string()?,
+                stack: self.int()?,
+                hole: Vec::new(),
+            });
+            holes.push(self.int::<usize>()?);
+        }
+        let len = seats.len();
+        let button = self.seat(len)?;
+
+        let board = self.int::<usize>()?;
+        let dealt = holes
+            .iter()
+            .try_fold(board, |dealt, &hole| dealt.checked_add(hole))
+            .ok_or(Error::Overflow)?;
+        let mut cards = self.unpack(dealt)?.into_iter();
+        for (seat, &hole) in seats.iter_mut().zip(&holes) {
+            seat.hole = cards.by_ref().take(hole).collect();
+        }
+        let board = cards.collect();
+
+        let mut posts = Vec::new();
+        for _ in 0..self.int::<usize>()? {
+            let tag = self.varint()?;
+            let blind = match tag & 3 {
+                0 => Blind::Ante,
+                1 => Blind::Small,
+                2 => Blind::Big,
+                _ => return Err(Error::BadTag { what: "blind", tag }),
+            };
+            posts.push(Post {
+                seat: seat(tag >> 2, len)?,
+                blind,
+                amount: self.int()?,
+            });
+        }
+
+        let mut actions = Vec::new();
+        for _ in 0..self.int::<usize>()? {
+            let tag = self.varint()?;
+            let street = match tag >> 3 & 3 {
+                0 => Street::Preflop,
+                1 => Street::Flop,
+                2 => Street::Turn,
+                _ => Street::River,
+            };
+            let action = match tag & 7 {
+                0 => Action::Fold,
+                1 => Action::Check,
+                2 => Action::Call(self.int()?),
+                3 => Action::Bet(self.int()?),
+                4 => Action::Raise(self.int()?),
+                _ => {
+                    return Err(Error::BadTag {
+                        what: "action",
+                        tag,
+                    })
+                }
+            };
+            actions.push(Decision {
+                seat: seat(tag >> 5, len)?,
+                street,
+                action,
+            });
+        }
+
+        let shown = self.seats(len)?;
+        let returned = match flags & RETURNED {
+            0 => None,
+            _ => Some((self.seat(len)?, self.int()?)),
+        };
+        let mut pots = Vec::new();
+        for _ in 0..self.int::<usize>()? {
+            pots.push(Pot {
+                amount: self.int()?,
+                eligible: self.seats(len)?,
+            });
+        }
+        let won = (0..len).map(|_| self.int()).collect::<Result<_, _>>()?;
+        let rake = self.int()?;
+
+        let seed = match flags & SEED {
+            0 => None,
+            _ => {
+                let mut bytes = [0; 8];
+                self.0.read_exact(&mut bytes)?;
+                Some(u64::from_le_bytes(bytes))
+            }
+        };
+
+        Ok(History {
+            id,
+            tournament,
+            table,
+            game,
+            config,
+            seats,
+            button,
+            posts,
+            actions,
+            board,
+            shown,
+            returned,
+            pots,
+            won,
+            rake,
+            seed,
+        })
+    }
+}
+
+/// Checks a seat number read as part of a tag.
+fn seat(seat: u64, seats: usize) -> Result<usize, Error> {
+    match usize::try_from(seat) {
+        Ok(seat) if seat < seats => Ok(seat),
+        _ => Err(Error::BadSeat(seat as usize)),
+    }
+}
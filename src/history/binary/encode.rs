@@ -0,0 +1,165 @@
+use super::{BIG_BLIND_ANTE, RETURNED, SEED, TOURNAMENT};
+use crate::card::Card;
+use crate::history::{Blind, Game, History};
+use crate::player::Action;
+use crate::table::{structure::Betting, Street};
+
+/// Appends `hand` to `buf`.
+pub(super) fn hand(buf: &mut Vec<u8>, hand: &History) {
+    let mut flags = 0;
+    if hand.tournament.is_some() {
+        flags |= TOURNAMENT;
+    }
+    if hand.seed.is_some() {
+        flags |= SEED;
+    }
+    if hand.returned.is_some() {
+        flags |= RETURNED;
+    }
+    if hand.config.big_blind_ante {
+        flags |= BIG_BLIND_ANTE;
+    }
+    buf.push(flags);
+
+    varint(buf, hand.id);
+    if let Some(tournament) = hand.tournament {
+        varint(buf, tournament);
+    }
+    string(buf, &hand.table);
+    varint(
+        buf,
+        match hand.game {
+            Game::Holdem => 0,
+            Game::Omaha => 1,
+        },
+    );
+
+    let config = &hand.config;
+    match config.betting {
+        Betting::NoLimit => varint(buf, 0),
+        Betting::PotLimit => varint(buf, 1),
+        Betting::FixedLimit(limit) => {
+            varint(buf, 2);
+            amounts(buf, &[limit.small_bet, limit.big_bet, limit.cap]);
+        }
+        Betting::SpreadLimit(limit) => {
+            varint(buf, 3);
+            amounts(buf, &[limit.min, limit.max]);
+        }
+    }
+    amounts(buf, &[config.small_blind, config.big_blind, config.ante]);
+
+    varint(buf, hand.seats.len() as u64);
+    for seat in &hand.seats {
+        string(buf, &seat.name);
+        varint(buf, u64::from(seat.stack));
+        varint(buf, seat.hole.len() as u64);
+    }
+    varint(buf, hand.button as u64);
+    varint(buf, hand.board.len() as u64);
+    pack(
+        buf,
+        hand.seats
+            .iter()
+            .flat_map(|seat| &seat.hole)
+            .chain(&hand.board),
+    );
+
+    varint(buf, hand.posts.len() as u64);
+    for post in &hand.posts {
+        let blind = match post.blind {
+            Blind::Ante => 0,
+            Blind::Small => 1,
+            Blind::Big => 2,
+        };
+        varint(buf, (post.seat as u64) << 2 | blind);
+        varint(buf, u64::from(post.amount));
+    }
+
+    varint(buf, hand.actions.len() as u64);
+    for decision in &hand.actions {
+        let street = match decision.street {
+            Street::Preflop => 0,
+            Street::Flop => 1,
+            Street::Turn => 2,
+            Street::River => 3,
+        };
+        let (action, amount) = match decision.action {
+            Action::Fold => (0, None),
+            Action::Check => (1, None),
+            Action::Call(amount) => (2, Some(amount)),
+            Action::Bet(to) => (3, Some(to)),
+            Action::Raise(to) => (4, Some(to)),
+        };
+        varint(buf, (decision.seat as u64) << 5 | street << 3 | action);
+        if let Some(amount) = amount {
+            varint(buf, u64::from(amount));
+        }
+    }
+
+    seats(buf, &hand.shown);
+    if let Some((seat, amount)) = hand.returned {
+        varint(buf, seat as u64);
+        varint(buf, u64::from(amount));
+    }
+    varint(buf, hand.pots.len() as u64);
+    for pot in &hand.pots {
+        varint(buf, u64::from(pot.amount));
+        seats(buf, &pot.eligible);
+    }
+    amounts(buf, &hand.won);
+    varint(buf, u64::from(hand.rake));
+
+    // Seeds are random, so they don't shrink as varints.
+    if let Some(seed) = hand.seed {
+        buf.extend_from_slice(&seed.to_le_bytes());
+    }
+}
+
+/// Appends `value` 7 bits at a time, lowest first, with the high bit set on
+/// every byte but the last.
+pub(super) fn varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn amounts(buf: &mut Vec<u8>, amounts: &[u32]) {
+    for &amount in amounts {
+        varint(buf, u64::from(amount));
+    }
+}
+
+fn seats(buf: &mut Vec<u8>, seats: &[usize]) {
+    varint(buf, seats.len() as u64);
+    for &seat in seats {
+        varint(buf, seat as u64);
+    }
+}
+
+fn string(buf: &mut Vec<u8>, string: &str) {
+    varint(buf, string.len() as u64);
+    buf.extend_from_slice(string.as_bytes());
+}
+
+/// Appends `cards` in 6 bits each, the first in the highest bits, padding
+/// the last byte with zeros.
+pub(super) fn pack<'a, I: IntoIterator<Item = &'a Card>>(buf: &mut Vec<u8>, cards: I) {
+    let mut bits: u32 = 0;
+    let mut len = 0;
+
+    for &card in cards {
+        bits = bits << 6 | u32::from(u8::from(card));
+        len += 6;
+        if len >= 8 {
+            len -= 8;
+            buf.push((bits >> len) as u8);
+            bits &= (1 << len) - 1;
+        }
+    }
+    if len > 0 {
+        buf.push((bits << (8 - len)) as u8);
+    }
+}
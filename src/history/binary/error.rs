@@ -0,0 +1,51 @@
+use std::{fmt, io};
+
+/// Why encoded hands couldn't be read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The data doesn't start with the header of encoded hands.
+    NotHands,
+    /// Hands were encoded by a version of the format this one can't read.
+    Version(u8),
+    /// The data ends in the middle of a hand.
+    Truncated,
+    /// A number is too large for what it counts.
+    Overflow,
+    /// A card isn't numbered from 0 to 51, or is dealt twice.
+    BadCard(u8),
+    /// A seat number is beyond the seats of the hand.
+    BadSeat(usize),
+    /// A game, blind, betting structure, street or action is unknown.
+    BadTag { what: &'static str, tag: u64 },
+    /// A name isn't valid UTF-8.
+    BadName,
+    /// Reading failed for another reason.
+    Io(io::ErrorKind),
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::UnexpectedEof => Error::Truncated,
+            kind => Error::Io(kind),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotHands => write!(f, "not encoded hands"),
+            Error::Version(version) => write!(f, "can't read version {} of the format", version),
+            Error::Truncated => write!(f, "data ends in the middle of a hand"),
+            Error::Overflow => write!(f, "number out of range"),
+            Error::BadCard(card) => write!(f, "bad card {}", card),
+            Error::BadSeat(seat) => write!(f, "no seat {}", seat),
+            Error::BadTag { what, tag } => write!(f, "unknown {} {}", what, tag),
+            Error::BadName => write!(f, "name isn't UTF-8"),
+            Error::Io(kind) => write!(f, "can't read: {:?}", kind),
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! A dense binary format for storing hands by the billion.
+//!
+//! Encoded hands start with a header, the bytes `ACES` followed by the
+//! version of the format. Then come the hands, one after the other, without
+//! any separator:
+//!
+//! - Numbers, seat numbers and counts are varints, 7 bits to a byte.
+//! - Names are their length followed by their UTF-8.
+//! - Hole cards, then the board, are packed together in 6 bits a card, with
+//!   cards numbered as by `u8::from(card)`.
+//! - Posts and actions pack their seat, blind, street and kind of action in
+//!   a single varint, which is one byte for the first seats. Amounts follow.
+//! - Seeds are 8 bytes, little endian.
+//!
+//! That's a fifth or less of the same hand in the text of `stars::write`.
+mod decode;
+mod encode;
+pub mod error;
+
+pub use error::Error;
+
+use super::History;
+use decode::Input;
+use std::io::{self, Read, Write};
+
+/// The bytes every encoding starts with.
+const MAGIC: &[u8; 4] = b"ACES";
+
+/// Version of the format written, and the only one read.
+pub const VERSION: u8 = 1;
+
+// Flags at the start of each hand, for the parts it has.
+const TOURNAMENT: u8 = 1;
+const SEED: u8 = 2;
+const RETURNED: u8 = 4;
+const BIG_BLIND_ANTE: u8 = 8;
+
+/// Writes hands one at a time, after the header.
+///
+/// # Example
+/// ```
+/// # use aces_high::{bot::*, history::binary::*, player::Agent, table::*};
+/// let mut table = Table::new_seeded(Config::no_limit(1, 2), &[1000, 1000], 3);
+/// let mut agents: Vec<Box<dyn Agent>> = vec![Box::new(Station), Box::new(Maniac)];
+/// let mut encoder = Encoder::new(Vec::new())?;
+///
+/// for _ in 0..3 {
+///     table.play(&mut agents).unwrap();
+///     encoder.encode(table.last_hand().unwrap())?;
+/// }
+///
+/// let bytes = encoder.into_inner();
+/// let mut decoder = Decoder::new(&bytes[..]).unwrap();
+/// assert_eq!(decoder.decode().unwrap().unwrap().id, 1);
+/// assert_eq!(decoder.count(), 2);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Encoder<W: Write> {
+    writer: W,
+    buf: Vec<u8>,
+}
+
+impl<W: Write> Encoder<W> {
+    /// Constructs an `Encoder` writing to `writer`, and writes the header.
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        Ok(Encoder {
+            writer,
+            buf: Vec::new(),
+        })
+    }
+
+    pub fn encode(&mut self, hand: &History) -> io::Result<()> {
+        self.buf.clear();
+        encode::hand(&mut self.buf, hand);
+        self.writer.write_all(&self.buf)
+    }
+
+    /// Returns the writer, with everything encoded written to it.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Reads hands one at a time, after checking the header.
+///
+/// Hands are read a byte at a time, so readers such as files are best
+/// wrapped in a `BufReader`.
+#[derive(Debug)]
+pub struct Decoder<R: Read> {
+    input: Input<R>,
+}
+
+impl<R: Read> Decoder<R> {
+    /// Constructs a `Decoder` reading from `reader`, once the header shows
+    /// the hands can be read.
+    pub fn new(reader: R) -> Result<Self, Error> {
+        let mut input = Input(reader);
+
+        let mut magic = [0; 4];
+        for byte in magic.iter_mut() {
+            *byte = input.next()?.ok_or(Error::NotHands)?;
+        }
+        if &magic != MAGIC {
+            return Err(Error::NotHands);
+        }
+        match input.next()?.ok_or(Error::NotHands)? {
+            VERSION => Ok(Decoder { input }),
+            version => Err(Error::Version(version)),
+        }
+    }
+
+    /// Reads the next hand, or returns `None` once all have been read.
+    pub fn decode(&mut self) -> Result<Option<History>, Error> {
+        match self.input.next()? {
+            Some(flags) => self.input.hand(flags).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<R: Read> Iterator for Decoder<R> {
+    type Item = Result<History, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.decode().transpose()
+    }
+}
+
+/// Encodes `hands`, header included.
+pub fn encode(hands: &[History]) -> Vec<u8> {
+    let mut encoder = Encoder::new(Vec::new()).unwrap();
+    for hand in hands {
+        encoder.encode(hand).unwrap();
+    }
+    encoder.into_inner()
+}
+
+/// Decodes every hand in `bytes`, as encoded by `encode`.
+///
+/// # Example
+/// ```
+/// # use aces_high::{bot::*, history::binary, player::Agent, table::*};
+/// let mut table = Table::new_seeded(Config::no_limit(1, 2), &[100, 100], 3);
+/// let mut agents: Vec<Box<dyn Agent>> = vec![Box::new(Station), Box::new(Maniac)];
+/// table.play(&mut agents)?;
+///
+/// let hands = [table.last_hand().unwrap().clone()];
+/// let bytes = binary::encode(&hands);
+/// assert_eq!(bytes[..5], *b"ACES\x01");
+/// assert_eq!(binary::decode(&bytes).unwrap(), hands);
+/// # Ok::<(), Error>(())
+/// ```
+pub fn decode(bytes: &[u8]) -> Result<Vec<History>, Error> {
+    Decoder::new(bytes)?.collect()
+}
+
+#[cfg(test)]
+fn engine_hands() -> Vec<History> {
+    use crate::table::Config;
+
+    let mut config = Config::no_limit(5, 10);
+    config.ante = 1;
+    super::played(config, &[300, 200, 0, 400, 1000, 250], 8, 30)
+}
+
+#[test]
+fn round_trips() {
+    let hands = engine_hands();
+    let bytes = encode(&hands);
+    assert_eq!(decode(&bytes).unwrap(), hands);
+    assert!(bytes.len() * 5 < super::stars::write(&hands).unwrap().len());
+
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/data/stars");
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let text = std::fs::read_to_string(path).unwrap();
+        let hands = super::stars::parse(&text).unwrap();
+        let bytes = encode(&hands);
+        assert_eq!(decode(&bytes).unwrap(), hands);
+        assert!(bytes.len() * 5 < text.len());
+    }
+}
+
+#[test]
+fn packs_cards() {
+    use crate::card::{Card, Rank, Suit};
+    use std::convert::TryFrom;
+
+    let deck: Vec<Card> = (0..52).map(|i| Card::try_from(i).unwrap()).collect();
+    assert!(deck.windows(2).all(|cards| cards[0] < cards[1]));
+    assert_eq!(deck[51], Card::new(Rank::King, Suit::Spades));
+
+    let mut bytes = Vec::new();
+    encode::pack(&mut bytes, &deck);
+    assert_eq!(bytes.len(), 39);
+    assert_eq!(Input(&bytes[..]).unpack(52), Ok(deck));
+
+    let mut input = Input(&[0b0000_0000, 0b0000_0000][..]);
+    assert_eq!(input.unpack(2), Err(Error::BadCard(0)));
+    let mut input = Input(&[0b1101_0000][..]);
+    assert_eq!(input.unpack(1), Err(Error::BadCard(52)));
+}
+
+#[test]
+fn errors() {
+    let hands = engine_hands();
+    let bytes = encode(&hands[..1]);
+
+    assert_eq!(decode(b"ACE").unwrap_err(), Error::NotHands);
+    assert_eq!(decode(b"PK\x03\x04\x14").unwrap_err(), Error::NotHands);
+    assert_eq!(decode(b"ACES\x02").unwrap_err(), Error::Version(2));
+    assert_eq!(decode(b"ACES\x01"), Ok(Vec::new()));
+    assert_eq!(
+        decode(&bytes[..bytes.len() - 1]).unwrap_err(),
+        Error::Truncated
+    );
+
+    let mut overflow = b"ACES\x01\x00".to_vec();
+    overflow.extend_from_slice(&[0xff; 11]);
+    assert_eq!(decode(&overflow).unwrap_err(), Error::Overflow);
+
+    // The hand's id is 1, and the table name follows it.
+    let mut bad_name = bytes.clone();
+    assert_eq!(bad_name[6..8], [1, 0]);
+    bad_name[7] = 1;
+    bad_name.insert(8, 0xff);
+    assert_eq!(decode(&bad_name).unwrap_err(), Error::BadName);
+}
@@ -0,0 +1,121 @@
+use super::{Blind, History, Recorder};
+use crate::card::Card;
+use crate::player::Action;
+use crate::table::{Street, Summary, Table};
+
+impl History {
+    /// Returns the board cards dealt on `street`.
+    pub fn board_on(&self, street: Street) -> &[Card] {
+        let (from, to) = match street {
+            Street::Preflop => (0, 0),
+            Street::Flop => (0, 3),
+            Street::Turn => (3, 4),
+            Street::River => (4, 5),
+        };
+        let len = self.board.len();
+        &self.board[from.min(len)..to.min(len)]
+    }
+
+    /// Returns the chips each seat put in, posts included and the returned
+    /// uncalled bet not deducted. A bet or raise to no more than the seat
+    /// already has in on the street adds nothing.
+    pub fn put_in(&self) -> Vec<u32> {
+        let seats = self.seats.len();
+        let mut total = vec![0; seats];
+        let mut street = vec![0; seats];
+        let mut current = Street::Preflop;
+
+        for post in &self.posts {
+            total[post.seat] += post.amount;
+            if post.blind != Blind::Ante {
+                street[post.seat] += post.amount;
+            }
+        }
+
+        for decision in &self.actions {
+            if decision.street != current {
+                current = decision.street;
+                street.iter_mut().for_each(|bet| *bet = 0);
+            }
+
+            let seat = decision.seat;
+            let added = match decision.action {
+                Action::Fold | Action::Check => 0,
+                Action::Call(amount) => amount,
+                Action::Bet(to) | Action::Raise(to) => to.saturating_sub(street[seat]),
+            };
+            total[seat] += added;
+            street[seat] += added;
+        }
+
+        total
+    }
+
+    /// Returns how much each seat's stack changed.
+    pub fn net(&self) -> Vec<i64> {
+        let mut net: Vec<i64> = self
+            .won
+            .iter()
+            .zip(self.put_in())
+            .map(|(&won, put_in)| i64::from(won) - i64::from(put_in))
+            .collect();
+
+        if let Some((seat, amount)) = self.returned {
+            net[seat] += i64::from(amount);
+        }
+
+        net
+    }
+
+    /// Returns the summary the table gave when the hand was played,
+    /// for feeding the hand to anything that takes one.
+    pub fn summary(&self) -> Summary {
+        Summary {
+            button: self.button,
+            stacks: self.seats.iter().map(|seat| seat.stack).collect(),
+            history: self.actions.clone(),
+            board: self.board.clone(),
+            shown: self
+                .shown
+                .iter()
+                .map(|&seat| (seat, self.seats[seat].hole.clone()))
+                .collect(),
+            won: self.won.clone(),
+            net: self.net(),
+        }
+    }
+}
+
+impl Recorder {
+    /// Constructs an empty `Recorder` which names the players at a table by
+    /// their seat in `names`.
+    pub fn new<S: AsRef<str>>(names: &[S]) -> Self {
+        Recorder {
+            names: names.iter().map(|name| name.as_ref().to_string()).collect(),
+            hands: Vec::new(),
+        }
+    }
+
+    /// Records the last hand played at `table`, if there is one.
+    pub fn record(&mut self, table: &Table) {
+        if let Some(hand) = table.last_hand() {
+            self.add(hand.clone());
+        }
+    }
+
+    /// Records `hand`, renaming its seats if names were given for them.
+    pub fn add(&mut self, mut hand: History) {
+        for (seat, name) in hand.seats.iter_mut().zip(&self.names) {
+            seat.name = name.clone();
+        }
+        self.hands.push(hand);
+    }
+
+    pub fn hands(&self) -> &[History] {
+        &self.hands
+    }
+
+    pub fn into_hands(self) -> Vec<History> {
+        self.hands
+    }
+}
@@ -0,0 +1,194 @@
+//! Records of played hands, for storing, replaying and analysing them.
+pub mod binary;
+mod r#impl;
+pub mod replay;
+pub mod stars;
+
+use crate::card::Card;
+use crate::player::Decision;
+use crate::table::{pot::Pot, Config};
+use mimpl::mimpl;
+
+/// The poker variants a hand can be recorded for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Game {
+    /// Texas Hold'em, with two hole cards.
+    Holdem,
+    /// Omaha, with four hole cards of which exactly two must be played.
+    Omaha,
+}
+
+mimpl!(Default; Game, || Game::Holdem);
+
+/// Everything that happened in a hand, from the seating to the pots.
+///
+/// The `Table` records every hand it plays. Collect them with a `Recorder`.
+///
+/// # Example
+/// ```
+/// # use aces_high::{bot::*, history::*, player::Agent, table::*};
+/// let mut table = Table::new_seeded(Config::no_limit(1, 2), &[100, 100], 3);
+/// let mut agents: Vec<Box<dyn Agent>> = vec![Box::new(Station), Box::new(Maniac)];
+/// let summary = table.play(&mut agents)?;
+///
+/// let hand = table.last_hand().unwrap();
+/// assert_eq!(hand.seats[0].stack, 100);
+/// assert_eq!(hand.seats[0].hole.len(), 2);
+/// assert_eq!(hand.posts.len(), 2);
+/// assert_eq!(hand.summary(), summary);
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct History {
+    /// Identifies the hand, such as the number of hands dealt at its table.
+    pub id: u64,
+    /// Identifies the tournament, for tournament hands.
+    pub tournament: Option<u64>,
+    /// Name of the table, if it has one.
+    pub table: String,
+    pub game: Game,
+    pub config: Config,
+    pub seats: Vec<Seat>,
+    pub button: usize,
+    /// Antes and blinds, in the order they were posted.
+    pub posts: Vec<Post>,
+    /// Every action taken, in order.
+    pub actions: Vec<Decision>,
+    /// Flop, turn and river, as far as they were dealt.
+    pub board: Vec<Card>,
+    /// Seats that showed their cards at showdown, in the order they did.
+    pub shown: Vec<usize>,
+    /// An uncalled bet returned to the seat that made it.
+    pub returned: Option<(usize, u32)>,
+    /// Main pot followed by the side pots, after the rake.
+    pub pots: Vec<Pot>,
+    /// Chips each seat won from the pots.
+    pub won: Vec<u32>,
+    pub rake: u32,
+    /// What the deck was shuffled with, for hands dealt by this crate.
+    pub seed: Option<u64>,
+}
+
+/// A seat as it was when the hand started.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Seat {
+    pub name: String,
+    /// Chips at the start of the hand. Seats without chips weren't dealt in.
+    pub stack: u32,
+    /// Hole cards, or none if they were never seen.
+    pub hole: Vec<Card>,
+}
+
+/// Chips put in before any cards are dealt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Blind {
+    Ante,
+    Small,
+    Big,
+}
+
+/// A blind or ante posted by a seat.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Post {
+    pub seat: usize,
+    pub blind: Blind,
+    /// Chips actually posted, which is less than the blind when all-in.
+    pub amount: u32,
+}
+
+/// Collects the hands played at a table, under the names of the players.
+///
+/// # Example
+/// ```
+/// # use aces_high::{bot::*, history::*, player::Agent, table::*};
+/// let mut table = Table::new_seeded(Config::no_limit(1, 2), &[100, 100], 3);
+/// let mut agents: Vec<Box<dyn Agent>> = vec![Box::new(Station), Box::new(Tag::default())];
+/// let mut recorder = Recorder::new(&["station", "tag"]);
+///
+/// for _ in 0..5 {
+///     table.play(&mut agents)?;
+///     recorder.record(&table);
+/// }
+///
+/// assert_eq!(recorder.hands().len(), 5);
+/// assert_eq!(recorder.hands()[4].id, 5);
+/// assert_eq!(recorder.hands()[0].seats[1].name, "tag");
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Recorder {
+    names: Vec<String>,
+    hands: Vec<History>,
+}
+
+/// Plays up to `count` hands at a seeded table with `stacks`, between bots
+/// of every kind, while two seats have chips, and returns their histories.
+#[cfg(test)]
+fn played(config: Config, stacks: &[u32], seed: u64, count: usize) -> Vec<History> {
+    use crate::bot::{Equity, Maniac, Random, Station, Tag};
+    use crate::player::Agent;
+    use crate::table::Table;
+
+    let mut table = Table::new_seeded(config, stacks, seed);
+    let mut agents: Vec<Box<dyn Agent>> = (0..stacks.len())
+        .map(|seat| -> Box<dyn Agent> {
+            match seat % 5 {
+                0 => Box::new(Random::new_seeded(seed)),
+                1 => Box::new(Maniac),
+                2 => Box::new(Station),
+                3 => Box::new(Equity::new_seeded(20, seed)),
+                _ => Box::new(Tag::default()),
+            }
+        })
+        .collect();
+
+    let mut hands = Vec::new();
+    while hands.len() < count
+        && table
+            .players()
+            .iter()
+            .filter(|player| player.pot > 0)
+            .count()
+            > 1
+    {
+        table.play(&mut agents).unwrap();
+        hands.push(table.last_hand().unwrap().clone());
+    }
+    hands
+}
+
+#[test]
+fn engine_records() {
+    use crate::bot::{Equity, Maniac, Random, Station};
+    use crate::player::Agent;
+    use crate::table::Table;
+
+    let mut table = Table::new_seeded(Config::no_limit(5, 10), &[300, 200, 0, 400], 9);
+    table.players_mut()[3].pot = 400;
+    let mut agents: Vec<Box<dyn Agent>> = vec![
+        Box::new(Random::new_seeded(1)),
+        Box::new(Maniac),
+        Box::new(Station),
+        Box::new(Equity::new_seeded(20, 2)),
+    ];
+
+    for _ in 0..50 {
+        let stacks: Vec<u32> = table.players().iter().map(|player| player.pot).collect();
+        if stacks.iter().filter(|&&stack| stack > 0).count() < 2 {
+            break;
+        }
+
+        let summary = table.play(&mut agents).unwrap();
+        let hand = table.last_hand().unwrap();
+
+        assert_eq!(hand.summary(), summary);
+        assert_eq!(hand.seats[2].hole, Vec::new());
+        assert_eq!(
+            hand.pots.iter().map(|pot| pot.amount).sum::<u32>(),
+            hand.won.iter().sum::<u32>()
+        );
+        for (seat, (start, player)) in stacks.iter().zip(table.players()).enumerate() {
+            assert_eq!(i64::from(*start) + hand.net()[seat], i64::from(player.pot));
+        }
+    }
+}
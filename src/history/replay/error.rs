@@ -0,0 +1,69 @@
+use crate::card::Card;
+use crate::history::{Game, Post};
+use crate::table;
+use std::fmt;
+
+/// Where a recorded hand and its replay part ways.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The hand has no seed, so its cards can't be dealt again.
+    NoSeed,
+    /// Only hands of this game can be replayed.
+    Unsupported(Game),
+    /// The hand can't be dealt, such as with fewer than two seats with chips.
+    Table(table::Error),
+    /// The button is on a seat that wasn't dealt in.
+    Button(usize),
+    /// The antes and blinds recorded aren't the ones the replay posted.
+    Posts { replayed: Vec<Post> },
+    /// A seat was recorded with other hole cards than the seed deals it.
+    Hole { seat: usize, replayed: Vec<Card> },
+    /// The board recorded isn't the one the seed deals.
+    Board { replayed: Vec<Card> },
+    /// The action at `index` was recorded for a seat other than the one to
+    /// act, or after the betting was over.
+    OutOfTurn { index: usize, to_act: Option<usize> },
+    /// The action at `index` breaks the rules of the table.
+    Illegal { index: usize, error: table::Error },
+    /// The recorded actions ended with `seat` still to act.
+    Missing { seat: usize },
+    /// Other seats showed their cards than the ones left at showdown.
+    Shown { replayed: Vec<usize> },
+    /// The recorded uncalled bet isn't the one returned.
+    Returned { replayed: Option<(usize, u32)> },
+    /// The recorded winnings aren't what the replay awarded.
+    Won { replayed: Vec<u32> },
+}
+
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NoSeed => write!(f, "the hand has no seed to deal it again with"),
+            Error::Unsupported(game) => write!(f, "{:?} hands can't be replayed", game),
+            Error::Table(error) => write!(f, "{}", error),
+            Error::Button(seat) => write!(f, "button on seat {}, which wasn't dealt in", seat),
+            Error::Posts { replayed } => write!(f, "posts should be {:?}", replayed),
+            Error::Hole { seat, replayed } => {
+                write!(f, "seat {} should be dealt {:?}", seat, replayed)
+            }
+            Error::Board { replayed } => write!(f, "board should be {:?}", replayed),
+            Error::OutOfTurn {
+                index,
+                to_act: Some(seat),
+            } => write!(f, "action {} should be seat {}'s", index, seat),
+            Error::OutOfTurn {
+                index,
+                to_act: None,
+            } => write!(f, "action {} comes after the betting is over", index),
+            Error::Illegal { index, error } => write!(f, "action {}: {}", index, error),
+            Error::Missing { seat } => write!(f, "actions end with seat {} to act", seat),
+            Error::Shown { replayed } => write!(f, "seats {:?} should show", replayed),
+            Error::Returned { replayed } => {
+                write!(f, "uncalled bet returned should be {:?}", replayed)
+            }
+            Error::Won { replayed } => write!(f, "winnings should be {:?}", replayed),
+        }
+    }
+}
@@ -0,0 +1,318 @@
+//! Dealing recorded hands again, action by action, to audit them.
+pub mod error;
+
+pub use error::Error;
+
+use super::{Game, History};
+use crate::card::Card;
+use crate::deck::Deck;
+use crate::player::Decision;
+use crate::table::{self, pot::Pot, state::State, structure::Legal, Seat, Street, Summary};
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Steps through a hand dealt by this crate, from the seed it was shuffled
+/// with.
+///
+/// The cards are dealt again and every recorded action is taken in turn
+/// under the rules of the table, so the state of the table can be inspected
+/// at any point. Anything recorded which the replay doesn't agree with is an
+/// `Error`: cards that weren't dealt, actions out of turn or against the
+/// rules, and winnings other than what the hands evaluate to.
+///
+/// # Example
+/// ```
+/// # use aces_high::{bot::*, history::replay::*, player::Agent, table::*};
+/// let mut table = Table::new_seeded(Config::no_limit(1, 2), &[100, 100], 3);
+/// let mut agents: Vec<Box<dyn Agent>> = vec![Box::new(Station), Box::new(Maniac)];
+/// let summary = table.play(&mut agents)?;
+///
+/// let mut replay = Replay::new(table.last_hand().unwrap())?;
+/// assert_eq!(replay.pot(), 3);
+/// assert_eq!(replay.to_act(), Some(0));
+/// assert!(replay.legal().unwrap().call.is_some());
+///
+/// replay.step()?;
+/// assert_eq!(replay.history().len(), 1);
+/// assert_eq!(replay.finish()?, summary);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct Replay<'a> {
+    hand: &'a History,
+    state: State,
+    deck: Deck,
+    holes: Vec<Vec<Card>>,
+    /// Seat after which the next seat to act is looked for.
+    after: usize,
+    to_act: Option<usize>,
+}
+
+impl<'a> Replay<'a> {
+    /// Deals `hand` again, up to its first action.
+    pub fn new(hand: &'a History) -> Result<Self, Error> {
+        if hand.game != Game::Holdem {
+            return Err(Error::Unsupported(hand.game));
+        }
+        let seed = hand.seed.ok_or(Error::NoSeed)?;
+
+        let mut state = State::new(hand.seats.iter().map(|seat| seat.stack));
+        let dealt = state.dealt();
+        if dealt < 2 {
+            return Err(Error::Table(table::Error::NotEnoughPlayers { dealt }));
+        }
+
+        let button = hand.button;
+        if state.seats.get(button).map_or(true, |seat| !seat.dealt) {
+            return Err(Error::Button(button));
+        }
+
+        let (posts, big) = state.post_blinds(&hand.config, button);
+        if posts != hand.posts {
+            return Err(Error::Posts { replayed: posts });
+        }
+
+        let mut deck = Deck::new_sorted();
+        deck.shuffle_with(&mut StdRng::seed_from_u64(seed));
+
+        let holes = state.deal_holes(&mut deck, button);
+        for (seat, hole) in holes.iter().enumerate() {
+            if *hole != hand.seats[seat].hole {
+                return Err(Error::Hole {
+                    seat,
+                    replayed: hole.clone(),
+                });
+            }
+        }
+
+        let mut replay = Replay {
+            hand,
+            state,
+            deck,
+            holes,
+            after: big,
+            to_act: None,
+        };
+        replay.advance()?;
+
+        Ok(replay)
+    }
+
+    /// Deals the next streets until a seat has to act or the hand is over.
+    fn advance(&mut self) -> Result<(), Error> {
+        loop {
+            self.to_act = self.state.to_act(self.after);
+            if self.to_act.is_some() || self.state.deal_street(&mut self.deck).is_none() {
+                return Ok(());
+            }
+            self.after = self.hand.button;
+
+            if !self.hand.board.starts_with(&self.state.board) {
+                return Err(Error::Board {
+                    replayed: self.state.board.clone(),
+                });
+            }
+        }
+    }
+
+    /// Takes the next recorded action and returns it, or `None` once every
+    /// action has been taken.
+    pub fn step(&mut self) -> Result<Option<Decision>, Error> {
+        let index = self.state.history.len();
+        let decision = match self.hand.actions.get(index) {
+            Some(&decision) => decision,
+            None => {
+                return match self.to_act {
+                    Some(seat) => Err(Error::Missing { seat }),
+                    None => Ok(None),
+                }
+            }
+        };
+
+        if self.to_act != Some(decision.seat) || self.state.street != decision.street {
+            return Err(Error::OutOfTurn {
+                index,
+                to_act: self.to_act,
+            });
+        }
+
+        self.state
+            .act(&self.hand.config, decision.seat, decision.action)
+            .map_err(|error| Error::Illegal { index, error })?;
+        self.after = decision.seat;
+        self.advance()?;
+
+        Ok(Some(decision))
+    }
+
+    /// Takes the remaining actions, then checks the showdown and the pots
+    /// against the record. Returns the summary of the hand as replayed.
+    pub fn finish(mut self) -> Result<Summary, Error> {
+        while self.step()?.is_some() {}
+
+        if self.state.board != self.hand.board {
+            return Err(Error::Board {
+                replayed: self.state.board.clone(),
+            });
+        }
+
+        let showdown = self.state.showdown(&self.holes);
+        let seats: Vec<usize> = showdown.shown.iter().map(|&(seat, _)| seat).collect();
+        if seats != self.hand.shown {
+            return Err(Error::Shown { replayed: seats });
+        }
+
+        let award = self.state.award(self.hand.button, &showdown.values);
+        if award.returned != self.hand.returned {
+            return Err(Error::Returned {
+                replayed: award.returned,
+            });
+        }
+        if award.won != self.hand.won {
+            return Err(Error::Won {
+                replayed: award.won,
+            });
+        }
+
+        let stacks: Vec<u32> = self.hand.seats.iter().map(|seat| seat.stack).collect();
+        Ok(Summary {
+            button: self.hand.button,
+            net: self
+                .state
+                .seats
+                .iter()
+                .zip(&stacks)
+                .map(|(seat, &stack)| i64::from(seat.stack) - i64::from(stack))
+                .collect(),
+            stacks,
+            history: self.state.history,
+            board: self.state.board,
+            shown: showdown.shown,
+            won: award.won,
+        })
+    }
+
+    /// Returns the hand being replayed.
+    pub fn hand(&self) -> &History {
+        self.hand
+    }
+
+    pub fn street(&self) -> Street {
+        self.state.street
+    }
+
+    /// Returns the seats as they stand, with their stacks and bets.
+    pub fn seats(&self) -> &[Seat] {
+        &self.state.seats
+    }
+
+    /// Returns the board cards dealt so far.
+    pub fn board(&self) -> &[Card] {
+        &self.state.board
+    }
+
+    /// Returns the hole cards dealt to `seat`.
+    pub fn hole(&self, seat: usize) -> &[Card] {
+        &self.holes[seat]
+    }
+
+    /// Returns the chips put in so far, antes and blinds included.
+    pub fn pot(&self) -> u32 {
+        self.state.pot()
+    }
+
+    /// Returns the main pot followed by the side pots, as they stand.
+    pub fn pots(&self) -> Vec<Pot> {
+        self.state.pots().pots()
+    }
+
+    /// Returns the actions taken so far.
+    pub fn history(&self) -> &[Decision] {
+        &self.state.history
+    }
+
+    /// Returns the seat to act, or `None` once the betting is over.
+    pub fn to_act(&self) -> Option<usize> {
+        self.to_act
+    }
+
+    /// Returns what the seat to act may do.
+    pub fn legal(&self) -> Option<Legal> {
+        self.to_act
+            .map(|seat| self.state.legal(&self.hand.config, seat))
+    }
+}
+
+#[cfg(test)]
+fn hands() -> Vec<History> {
+    use crate::table::Config;
+
+    let mut config = Config::no_limit(5, 10);
+    config.ante = 2;
+    super::played(config, &[300, 150, 0, 400, 250], 11, 40)
+}
+
+#[test]
+fn replays_engine_hands() {
+    for hand in hands() {
+        let mut replay = Replay::new(&hand).unwrap();
+        let mut pot = replay.pot();
+
+        while let Some(seat) = replay.to_act() {
+            let decision = replay.step().unwrap().unwrap();
+            assert_eq!(decision.seat, seat);
+            assert!(replay.pot() >= pot);
+            pot = replay.pot();
+        }
+
+        assert_eq!(replay.finish(), Ok(hand.summary()));
+    }
+}
+
+#[test]
+fn catches_tampering() {
+    use crate::player::Action;
+
+    let hands = hands();
+    let hand = hands
+        .iter()
+        .find(|hand| hand.actions.len() > 3 && hand.won.iter().filter(|&&won| won > 0).count() == 1)
+        .unwrap();
+
+    let mut tampered = hand.clone();
+    tampered.seed = tampered.seed.map(|seed| seed + 1);
+    assert!(matches!(Replay::new(&tampered), Err(Error::Hole { .. })));
+
+    let mut tampered = hand.clone();
+    tampered.seed = None;
+    assert_eq!(Replay::new(&tampered).unwrap_err(), Error::NoSeed);
+
+    let mut tampered = hand.clone();
+    tampered.actions[0].action = Action::Raise(1);
+    assert!(matches!(
+        Replay::new(&tampered).unwrap().finish(),
+        Err(Error::Illegal { index: 0, .. })
+    ));
+
+    let mut tampered = hand.clone();
+    tampered.actions.swap(0, 1);
+    assert!(matches!(
+        Replay::new(&tampered).unwrap().finish(),
+        Err(Error::OutOfTurn { index: 0, .. })
+    ));
+
+    let mut tampered = hand.clone();
+    tampered.actions.pop();
+    assert!(matches!(
+        Replay::new(&tampered).unwrap().finish(),
+        Err(Error::Missing { .. })
+    ));
+
+    let mut tampered = hand.clone();
+    tampered.won.reverse();
+    assert_eq!(
+        Replay::new(&tampered).unwrap().finish(),
+        Err(Error::Won {
+            replayed: hand.won.clone()
+        })
+    );
+}
@@ -0,0 +1,71 @@
+use std::fmt;
+
+/// Why a hand history couldn't be read, and on which line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    /// Line number, starting from 1.
+    pub line: usize,
+    pub kind: Kind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Kind {
+    /// Expected the first line of a hand.
+    NotAHand,
+    /// A game, betting structure or feature this crate doesn't model.
+    Unsupported(String),
+    /// Expected the table line, naming the button.
+    NoTable,
+    BadSeat(String),
+    BadAmount(String),
+    BadCard(String),
+    /// Names someone who isn't seated.
+    UnknownPlayer(String),
+    UnknownAction(String),
+    /// Something happened out of order, such as an action before the cards
+    /// were dealt.
+    Unexpected(String),
+    /// The hand ended before its summary.
+    Incomplete,
+    /// What was collected and raked doesn't add up to what was put in.
+    Mismatch {
+        put_in: u32,
+        collected: u32,
+    },
+}
+
+impl Error {
+    pub(super) fn new(line: usize, kind: Kind) -> Self {
+        Error { line, kind }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Kind::NotAHand => write!(f, "expected the start of a hand"),
+            Kind::Unsupported(what) => write!(f, "{} isn't supported", what),
+            Kind::NoTable => write!(f, "expected the table and button"),
+            Kind::BadSeat(seat) => write!(f, "can't read seat '{}'", seat),
+            Kind::BadAmount(amount) => write!(f, "can't read amount '{}'", amount),
+            Kind::BadCard(card) => write!(f, "can't read card '{}'", card),
+            Kind::UnknownPlayer(name) => write!(f, "nobody named '{}' is seated", name),
+            Kind::UnknownAction(action) => write!(f, "unknown action '{}'", action),
+            Kind::Unexpected(line) => write!(f, "didn't expect '{}' here", line),
+            Kind::Incomplete => write!(f, "hand ends without a summary"),
+            Kind::Mismatch { put_in, collected } => write!(
+                f,
+                "{} was put in, but {} was collected and raked",
+                put_in, collected
+            ),
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use crate::card::Card;
+use crate::history::{Blind, Game, History};
+use crate::player::Action;
+use crate::table::{structure::Betting, Street};
+use std::fmt;
+
+/// Hands don't record when they were played, so they are all dated the same.
+const DATE: &str = "2020/01/01 0:00:00 ET";
+
+/// Name of the table for hands played at one without a name.
+const TABLE: &str = "Aces High";
+
+/// Formats a hand the way PokerStars writes it.
+pub(super) struct Text<'a>(pub &'a History);
+
+struct Cards<'a>(&'a [Card]);
+
+impl fmt::Display for Cards<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, card) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{:?}", card)?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl Text<'_> {
+    fn name(&self, seat: usize) -> String {
+        match &self.0.seats[seat].name {
+            name if name.is_empty() => format!("Seat {}", seat + 1),
+            name => name.clone(),
+        }
+    }
+
+    /// Writes the line dealing the board cards of `street`, or fails if the
+    /// board is too short to have dealt them.
+    fn deal(&self, f: &mut fmt::Formatter<'_>, street: Street) -> fmt::Result {
+        let board = &self.0.board;
+        let dealt = match street {
+            Street::Preflop => 0,
+            Street::Flop => 3,
+            Street::Turn => 4,
+            Street::River => 5,
+        };
+        if board.len() < dealt {
+            return Err(fmt::Error);
+        }
+
+        match street {
+            Street::Preflop => Ok(()),
+            Street::Flop => writeln!(f, "*** FLOP *** {}", Cards(&board[..3])),
+            Street::Turn => writeln!(
+                f,
+                "*** TURN *** {} {}",
+                Cards(&board[..3]),
+                Cards(&board[3..4])
+            ),
+            Street::River => writeln!(
+                f,
+                "*** RIVER *** {} {}",
+                Cards(&board[..4]),
+                Cards(&board[4..5])
+            ),
+        }
+    }
+
+    fn header(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hand = self.0;
+        let config = &hand.config;
+
+        write!(f, "PokerStars Hand #{}: ", hand.id)?;
+        if let Some(tournament) = hand.tournament {
+            write!(f, "Tournament #{}, ", tournament)?;
+        }
+
+        let game = match hand.game {
+            Game::Holdem => "Hold'em",
+            Game::Omaha => "Omaha",
+        };
+        let (betting, low, high) = match config.betting {
+            Betting::NoLimit => ("No Limit", config.small_blind, config.big_blind),
+            Betting::PotLimit => ("Pot Limit", config.small_blind, config.big_blind),
+            Betting::FixedLimit(limit) => ("Limit", limit.small_bet, limit.big_bet),
+            Betting::SpreadLimit(limit) => ("Spread Limit", limit.min, limit.max),
+        };
+        writeln!(f, "{} {} ({}/{}) - {}", game, betting, low, high, DATE)?;
+
+        let table = if hand.table.is_empty() {
+            TABLE
+        } else {
+            &hand.table
+        };
+        writeln!(
+            f,
+            "Table '{}' {}-max Seat #{} is the button",
+            table,
+            hand.seats.len(),
+            hand.button + 1
+        )?;
+
+        for (seat, info) in hand.seats.iter().enumerate() {
+            if info.stack > 0 {
+                writeln!(
+                    f,
+                    "Seat {}: {} ({} in chips)",
+                    seat + 1,
+                    self.name(seat),
+                    info.stack
+                )?;
+            } else if !info.name.is_empty() {
+                writeln!(
+                    f,
+                    "Seat {}: {} (0 in chips) is sitting out",
+                    seat + 1,
+                    info.name
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hand = self.0;
+
+        writeln!(f, "*** SUMMARY ***")?;
+        let pots: u32 = hand.pots.iter().map(|pot| pot.amount).sum();
+        write!(f, "Total pot {}", pots + hand.rake)?;
+        if hand.pots.len() > 1 {
+            write!(f, " Main pot {}.", hand.pots[0].amount)?;
+            for (i, pot) in hand.pots.iter().enumerate().skip(1) {
+                match hand.pots.len() {
+                    2 => write!(f, " Side pot {}.", pot.amount)?,
+                    _ => write!(f, " Side pot-{} {}.", i, pot.amount)?,
+                }
+            }
+        }
+        writeln!(f, " | Rake {}", hand.rake)?;
+
+        if !hand.board.is_empty() {
+            writeln!(f, "Board {}", Cards(&hand.board))?;
+        }
+
+        for (seat, info) in hand.seats.iter().enumerate() {
+            if info.stack == 0 {
+                continue;
+            }
+
+            write!(f, "Seat {}: {}", seat + 1, self.name(seat))?;
+            if seat == hand.button {
+                write!(f, " (button)")?;
+            }
+            for post in hand.posts.iter().filter(|post| post.seat == seat) {
+                match post.blind {
+                    Blind::Ante => (),
+                    Blind::Small => write!(f, " (small blind)")?,
+                    Blind::Big => write!(f, " (big blind)")?,
+                }
+            }
+
+            let folded = hand
+                .actions
+                .iter()
+                .find(|decision| decision.seat == seat && decision.action == Action::Fold);
+            let won = hand.won[seat];
+            match folded {
+                Some(decision) if decision.street == Street::Preflop => {
+                    writeln!(f, " folded before Flop")?
+                }
+                Some(decision) => writeln!(f, " folded on the {:?}", decision.street)?,
+                None if hand.shown.contains(&seat) && won > 0 => {
+                    writeln!(f, " showed {} and won ({})", Cards(&info.hole), won)?
+                }
+                None if hand.shown.contains(&seat) => {
+                    writeln!(f, " showed {} and lost", Cards(&info.hole))?
+                }
+                None if won > 0 => writeln!(f, " collected ({})", won)?,
+                None => writeln!(f, " mucked")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Text<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hand = self.0;
+        let mut stacks: Vec<u32> = hand.seats.iter().map(|seat| seat.stack).collect();
+        let mut bets = vec![0; hand.seats.len()];
+        let all_in = |stack: u32| if stack == 0 { " and is all-in" } else { "" };
+
+        self.header(f)?;
+
+        for post in &hand.posts {
+            let seat = post.seat;
+            stacks[seat] = stacks[seat].saturating_sub(post.amount);
+            let blind = match post.blind {
+                Blind::Ante => "the ante",
+                Blind::Small => "small blind",
+                Blind::Big => "big blind",
+            };
+            if post.blind != Blind::Ante {
+                bets[seat] += post.amount;
+            }
+            writeln!(
+                f,
+                "{}: posts {} {}{}",
+                self.name(seat),
+                blind,
+                post.amount,
+                all_in(stacks[seat])
+            )?;
+        }
+
+        writeln!(f, "*** HOLE CARDS ***")?;
+        for (seat, info) in hand.seats.iter().enumerate() {
+            if !info.hole.is_empty() {
+                writeln!(f, "Dealt to {} {}", self.name(seat), Cards(&info.hole))?;
+            }
+        }
+
+        let streets = [Street::Preflop, Street::Flop, Street::Turn, Street::River];
+        let mut street = 0;
+        for decision in &hand.actions {
+            while streets[street] < decision.street {
+                street += 1;
+                self.deal(f, streets[street])?;
+                bets.iter_mut().for_each(|bet| *bet = 0);
+            }
+
+            let seat = decision.seat;
+            let to_match = *bets.iter().max().unwrap_or(&0);
+            write!(f, "{}: ", self.name(seat))?;
+            match decision.action {
+                Action::Fold => writeln!(f, "folds")?,
+                Action::Check => writeln!(f, "checks")?,
+                Action::Call(amount) => {
+                    stacks[seat] = stacks[seat].saturating_sub(amount);
+                    bets[seat] += amount;
+                    writeln!(f, "calls {}{}", amount, all_in(stacks[seat]))?;
+                }
+                Action::Bet(to) | Action::Raise(to) => {
+                    stacks[seat] = stacks[seat].saturating_sub(to.saturating_sub(bets[seat]));
+                    bets[seat] = to;
+                    if to_match == 0 {
+                        write!(f, "bets {}", to)?;
+                    } else {
+                        write!(f, "raises {} to {}", to.saturating_sub(to_match), to)?;
+                    }
+                    writeln!(f, "{}", all_in(stacks[seat]))?;
+                }
+            }
+        }
+
+        if let Some((seat, amount)) = hand.returned {
+            writeln!(
+                f,
+                "Uncalled bet ({}) returned to {}",
+                amount,
+                self.name(seat)
+            )?;
+        }
+
+        // Run out the board of hands that were all-in.
+        let dealt = match hand.board.len() {
+            0..=2 => 0,
+            3 => 1,
+            4 => 2,
+            _ => 3,
+        };
+        for &street in &streets[street + 1..=dealt.max(street)] {
+            self.deal(f, street)?;
+        }
+
+        if !hand.shown.is_empty() {
+            writeln!(f, "*** SHOW DOWN ***")?;
+            for &seat in &hand.shown {
+                writeln!(
+                    f,
+                    "{}: shows {}",
+                    self.name(seat),
+                    Cards(&hand.seats[seat].hole)
+                )?;
+            }
+        }
+        for (seat, &won) in hand.won.iter().enumerate() {
+            if won > 0 {
+                writeln!(f, "{} collected {} from pot", self.name(seat), won)?;
+            }
+        }
+
+        self.summary(f)
+    }
+}
@@ -0,0 +1,593 @@
+use super::error::{Error, Kind};
+use crate::card::Card;
+use crate::history::{Blind, Game, History, Post, Seat};
+use crate::player::{Action, Decision};
+use crate::table::{
+    pot::Pots,
+    structure::{Betting, FixedLimit, SpreadLimit},
+    Config, Street,
+};
+
+/// Returns `true` if `line` is the first line of a hand.
+pub(super) fn is_start(line: &str) -> bool {
+    line.starts_with("PokerStars ") && (line.contains("Hand #") || line.contains("Game #"))
+}
+
+/// Reads `text` as an amount of chips, counting cents when `scale` is 100.
+fn amount(text: &str, scale: u32) -> Option<u32> {
+    let text = text
+        .trim()
+        .trim_start_matches(&['$', '€', '£'][..])
+        .replace(',', "");
+    let mut parts = text.splitn(2, '.');
+
+    let whole: u32 = parts.next()?.parse().ok()?;
+    let fraction = parts.next().unwrap_or("");
+    if fraction.len() > 2 || !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let cents: u32 = format!("{:0<2}", fraction).parse().ok()?;
+
+    match scale {
+        100 => whole.checked_mul(100)?.checked_add(cents),
+        _ if cents == 0 => whole.checked_mul(scale),
+        _ => None,
+    }
+}
+
+/// Reads the cards between the last pair of brackets in `text`.
+fn cards(text: &str) -> Result<Vec<Card>, Kind> {
+    let inner = match (text.rfind('['), text.rfind(']')) {
+        (Some(open), Some(close)) if open < close => &text[open + 1..close],
+        _ => return Err(Kind::BadCard(text.to_string())),
+    };
+
+    inner
+        .split_whitespace()
+        .map(|card| card.parse().map_err(|_| Kind::BadCard(card.to_string())))
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Section {
+    Seating,
+    Cards(Street),
+    Showdown,
+    Summary,
+}
+
+/// Reads one hand, line by line.
+struct Parser {
+    hand: History,
+    /// Games played for money are counted in cents.
+    scale: u32,
+    /// Number of seats at the table, if given.
+    max: usize,
+    sitting_out: Vec<bool>,
+    section: Section,
+    /// What the summary says was in the pot, and on which line.
+    total: Option<(usize, u32)>,
+}
+
+/// Reads a hand from its lines, each paired with its line number.
+///
+/// # Panics
+/// Panics if `lines` is empty.
+pub(super) fn hand(lines: &[(usize, &str)]) -> Result<History, Error> {
+    let (&(number, header), rest) = lines.split_first().unwrap();
+    let mut parser = Parser::header(number, header)?;
+
+    let (&(number, table), rest) = rest
+        .split_first()
+        .ok_or_else(|| Error::new(number, Kind::NoTable))?;
+    parser.table(number, table)?;
+
+    for &(number, line) in rest {
+        parser.line(number, line)?;
+    }
+
+    parser.finish(lines[lines.len() - 1].0)
+}
+
+impl Parser {
+    fn header(number: usize, line: &str) -> Result<Parser, Error> {
+        let error = |kind| Error::new(number, kind);
+
+        if !is_start(line) {
+            return Err(error(Kind::NotAHand));
+        }
+        let rest = &line[line.find('#').unwrap() + 1..];
+        let colon = rest.find(':').ok_or_else(|| error(Kind::NotAHand))?;
+        let id = rest[..colon]
+            .trim()
+            .parse()
+            .map_err(|_| error(Kind::NotAHand))?;
+        let rest = &rest[colon + 1..];
+
+        let tournament = match rest.find("Tournament #") {
+            Some(at) => {
+                let digits: String = rest[at + 12..]
+                    .chars()
+                    .take_while(char::is_ascii_digit)
+                    .collect();
+                Some(digits.parse().map_err(|_| error(Kind::NotAHand))?)
+            }
+            None => None,
+        };
+
+        let described = rest.split(" - ").next().unwrap_or(rest).trim();
+        if rest.contains("Hi/Lo") {
+            return Err(error(Kind::Unsupported(described.to_string())));
+        }
+        let game = if rest.contains("Hold'em") {
+            Game::Holdem
+        } else if rest.contains("Omaha") {
+            Game::Omaha
+        } else {
+            return Err(error(Kind::Unsupported(described.to_string())));
+        };
+
+        // Small and big blind, or small and big bet in fixed limit games.
+        let (scale, low, high) = rest
+            .match_indices('(')
+            .filter_map(|(open, _)| {
+                let inner = &rest[open + 1..open + rest[open..].find(')')?];
+                let (low, high) = inner.split_at(inner.find('/')?);
+                let high = high[1..].split_whitespace().next()?;
+                let scale = if inner.contains(&['$', '€', '£'][..]) {
+                    100
+                } else {
+                    1
+                };
+                Some((scale, amount(low, scale)?, amount(high, scale)?))
+            })
+            .next()
+            .ok_or_else(|| error(Kind::BadAmount(described.to_string())))?;
+
+        let config = if rest.contains("No Limit") {
+            Config::no_limit(low, high)
+        } else if rest.contains("Pot Limit") {
+            Config {
+                betting: Betting::PotLimit,
+                ..Config::no_limit(low, high)
+            }
+        } else if rest.contains("Spread Limit") {
+            Config {
+                betting: Betting::SpreadLimit(SpreadLimit {
+                    min: low,
+                    max: high,
+                }),
+                ..Config::no_limit(0, 0)
+            }
+        } else if rest.contains("Limit") {
+            Config {
+                betting: Betting::FixedLimit(FixedLimit {
+                    small_bet: low,
+                    big_bet: high,
+                    cap: 4,
+                }),
+                ..Config::no_limit(low / 2, low)
+            }
+        } else {
+            return Err(error(Kind::Unsupported(described.to_string())));
+        };
+
+        Ok(Parser {
+            hand: History {
+                id,
+                tournament,
+                game,
+                config,
+                ..History::default()
+            },
+            scale,
+            max: 0,
+            sitting_out: Vec::new(),
+            section: Section::Seating,
+            total: None,
+        })
+    }
+
+    /// Reads a line such as `Table 'Aludra IV' 6-max Seat #1 is the button`.
+    fn table(&mut self, number: usize, line: &str) -> Result<(), Error> {
+        let error = || Error::new(number, Kind::NoTable);
+
+        if !line.starts_with("Table '") {
+            return Err(error());
+        }
+        let rest = &line[7..];
+        let close = rest.rfind('\'').ok_or_else(error)?;
+        let after = &rest[close + 1..];
+
+        self.hand.table = rest[..close].to_string();
+        self.max = after
+            .split_whitespace()
+            .find(|word| word.ends_with("-max"))
+            .and_then(|word| word.trim_end_matches("-max").parse().ok())
+            .unwrap_or(0);
+
+        let button = after.find("Seat #").ok_or_else(error)?;
+        let button: String = after[button + 6..]
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect();
+        match button.parse::<usize>() {
+            Ok(button) if button > 0 => self.hand.button = button - 1,
+            _ => return Err(error()),
+        }
+
+        Ok(())
+    }
+
+    /// Reads a line such as `Seat 3: Mr. Fish ($7.25 in chips)`.
+    fn seat(&mut self, number: usize, line: &str) -> Result<(), Error> {
+        let bad = || Error::new(number, Kind::BadSeat(line.to_string()));
+
+        let rest = &line[5..];
+        let colon = rest.find(": ").ok_or_else(bad)?;
+        let seat = match rest[..colon].parse::<usize>() {
+            Ok(seat) if seat > 0 => seat - 1,
+            _ => return Err(bad()),
+        };
+        let rest = &rest[colon + 2..];
+
+        let chips = rest.rfind(" in chips").ok_or_else(bad)?;
+        let open = rest[..chips].rfind('(').ok_or_else(bad)?;
+        let name = rest[..open].trim_end();
+        if name.is_empty() {
+            return Err(bad());
+        }
+        let stack = &rest[open + 1..chips];
+        let stack = amount(stack, self.scale)
+            .ok_or_else(|| Error::new(number, Kind::BadAmount(stack.to_string())))?;
+
+        if self.hand.seats.len() <= seat {
+            self.hand.seats.resize(seat + 1, Seat::default());
+            self.hand.won.resize(seat + 1, 0);
+            self.sitting_out.resize(seat + 1, false);
+        }
+        self.hand.seats[seat] = Seat {
+            name: name.to_string(),
+            stack,
+            hole: Vec::new(),
+        };
+        self.sitting_out[seat] = rest[chips..].contains("sitting out");
+
+        Ok(())
+    }
+
+    /// Returns the seat of the player `line` starts with, and the rest of
+    /// the line. Picks the longest name, in case one name starts another.
+    fn player<'a>(&self, line: &'a str) -> Option<(usize, &'a str)> {
+        self.hand
+            .seats
+            .iter()
+            .enumerate()
+            .filter(|(_, seat)| !seat.name.is_empty() && line.starts_with(&seat.name))
+            .max_by_key(|(_, seat)| seat.name.len())
+            .map(|(seat, info)| (seat, &line[info.name.len()..]))
+    }
+
+    fn amount(&self, number: usize, text: &str) -> Result<u32, Error> {
+        let text = text.split_whitespace().next().unwrap_or("");
+        amount(text, self.scale)
+            .ok_or_else(|| Error::new(number, Kind::BadAmount(text.to_string())))
+    }
+
+    fn line(&mut self, number: usize, line: &str) -> Result<(), Error> {
+        let error = |kind| Error::new(number, kind);
+
+        if line.starts_with("*** ") {
+            return self.section(number, line);
+        }
+
+        match self.section {
+            Section::Summary => {
+                if line.starts_with("Total pot ") {
+                    let total = self.amount(number, &line[10..])?;
+                    self.total = Some((number, total));
+                    if let Some(rake) = line.find("| Rake ") {
+                        self.hand.rake = self.amount(number, &line[rake + 7..])?;
+                    }
+                }
+                return Ok(());
+            }
+            // Players may be named after their seat, as they are at tables of
+            // this crate, so a line naming one is read as theirs.
+            Section::Seating
+                if line.starts_with("Seat ")
+                    && !matches!(self.player(line), Some((_, rest)) if rest.starts_with(": ")) =>
+            {
+                return self.seat(number, line)
+            }
+            _ => (),
+        }
+
+        if line.starts_with("Dealt to ") {
+            let rest = &line[9..];
+            let (seat, rest) = self
+                .player(rest)
+                .ok_or_else(|| error(Kind::UnknownPlayer(rest.to_string())))?;
+            if rest.contains('[') {
+                self.hand.seats[seat].hole = cards(rest).map_err(error)?;
+            }
+            return Ok(());
+        }
+
+        if line.starts_with("Uncalled bet (") {
+            let close = line.find(')').unwrap_or(line.len());
+            let amount = self.amount(number, &line[14..close])?;
+            let name = line
+                .rfind(" returned to ")
+                .map_or("", |at| &line[at + 13..]);
+            let seat = match self.player(name) {
+                Some((seat, "")) => seat,
+                _ => return Err(error(Kind::UnknownPlayer(name.to_string()))),
+            };
+            self.hand.returned = Some((seat, amount));
+            return Ok(());
+        }
+
+        if let Some((seat, rest)) = self.player(line) {
+            if rest.starts_with(": ") {
+                return self.act(number, seat, &rest[2..]);
+            }
+            if rest.starts_with(" collected ") {
+                let amount = self.amount(number, &rest[11..])?;
+                self.hand.won[seat] += amount;
+                return Ok(());
+            }
+        }
+
+        // Chat, players coming and going, and such.
+        Ok(())
+    }
+
+    fn section(&mut self, number: usize, line: &str) -> Result<(), Error> {
+        let error = |kind| Error::new(number, kind);
+
+        let street = if line.starts_with("*** HOLE CARDS ***") {
+            self.seated(number)?;
+            Street::Preflop
+        } else if line.starts_with("*** FLOP ***") {
+            Street::Flop
+        } else if line.starts_with("*** TURN ***") {
+            Street::Turn
+        } else if line.starts_with("*** RIVER ***") {
+            Street::River
+        } else if line.starts_with("*** SHOW DOWN ***") {
+            if !matches!(self.section, Section::Cards(_)) {
+                return Err(error(Kind::Unexpected(line.to_string())));
+            }
+            self.section = Section::Showdown;
+            return Ok(());
+        } else if line.starts_with("*** SUMMARY ***") {
+            if self.section == Section::Seating {
+                return Err(error(Kind::Unexpected(line.to_string())));
+            }
+            self.section = Section::Summary;
+            return Ok(());
+        } else if line.contains("FIRST") || line.contains("SECOND") {
+            return Err(error(Kind::Unsupported("running it twice".to_string())));
+        } else {
+            return Err(error(Kind::Unexpected(line.to_string())));
+        };
+
+        let expected = match self.section {
+            Section::Seating => Street::Preflop,
+            Section::Cards(Street::Preflop) => Street::Flop,
+            Section::Cards(Street::Flop) => Street::Turn,
+            Section::Cards(Street::Turn) => Street::River,
+            _ => return Err(error(Kind::Unexpected(line.to_string()))),
+        };
+        if street != expected {
+            return Err(error(Kind::Unexpected(line.to_string())));
+        }
+
+        if street != Street::Preflop {
+            let dealt = cards(line).map_err(error)?;
+            self.hand.board.extend(dealt);
+        }
+
+        self.section = Section::Cards(street);
+        Ok(())
+    }
+
+    /// Sizes everything kept per seat once the seating is known.
+    fn seated(&mut self, number: usize) -> Result<(), Error> {
+        let seats = self.hand.seats.len();
+        if seats < 2 {
+            return Err(Error::new(
+                number,
+                Kind::Unsupported("fewer than two seats".to_string()),
+            ));
+        }
+
+        let seats = seats.max(self.max).max(self.hand.button + 1);
+        self.hand.seats.resize(seats, Seat::default());
+        self.hand.won.resize(seats, 0);
+        self.sitting_out.resize(seats, false);
+        Ok(())
+    }
+
+    /// Reads what a player did, from the part after `name: `.
+    fn act(&mut self, number: usize, seat: usize, text: &str) -> Result<(), Error> {
+        let error = |kind| Error::new(number, kind);
+        let unexpected = || error(Kind::Unexpected(text.to_string()));
+
+        let text = text.trim_end_matches(" and is all-in");
+        let (verb, arg) = match text.find(' ') {
+            Some(space) => (&text[..space], &text[space + 1..]),
+            None => (text, ""),
+        };
+
+        let action = match verb {
+            "posts" => {
+                if self.section != Section::Seating {
+                    return Err(unexpected());
+                }
+                let (blind, amount) = if arg.starts_with("small blind ") {
+                    (Blind::Small, &arg[12..])
+                } else if arg.starts_with("big blind ") {
+                    (Blind::Big, &arg[10..])
+                } else if arg.starts_with("the ante ") {
+                    (Blind::Ante, &arg[9..])
+                } else if arg.starts_with("small & big blinds") {
+                    return Err(error(Kind::Unsupported("dead blinds".to_string())));
+                } else {
+                    return Err(error(Kind::UnknownAction(text.to_string())));
+                };
+
+                let amount = self.amount(number, amount)?;
+                self.hand.posts.push(Post {
+                    seat,
+                    blind,
+                    amount,
+                });
+                return Ok(());
+            }
+            "shows" => {
+                self.hand.seats[seat].hole = cards(arg).map_err(error)?;
+                if !self.hand.shown.contains(&seat) {
+                    self.hand.shown.push(seat);
+                }
+                return Ok(());
+            }
+            "folds" => Action::Fold,
+            "checks" => Action::Check,
+            "calls" => Action::Call(self.amount(number, arg)?),
+            "bets" => Action::Bet(self.amount(number, arg)?),
+            "raises" => {
+                let to = arg
+                    .find(" to ")
+                    .ok_or_else(|| error(Kind::UnknownAction(text.to_string())))?;
+                Action::Raise(self.amount(number, &arg[to + 4..])?)
+            }
+            "mucks" | "doesn't" | "is" | "has" | "sits" | "said," | "leaves" | "joins" => {
+                return Ok(());
+            }
+            _ => return Err(error(Kind::UnknownAction(text.to_string()))),
+        };
+
+        let street = match self.section {
+            Section::Cards(street) => street,
+            _ => return Err(unexpected()),
+        };
+        if let Action::Bet(to) | Action::Raise(to) = action {
+            if to <= self.street_bet(seat, street) {
+                return Err(error(Kind::BadAmount(arg.to_string())));
+            }
+        }
+        self.hand.actions.push(Decision {
+            seat,
+            street,
+            action,
+        });
+
+        Ok(())
+    }
+
+    /// Returns what `seat` has put in on `street` so far, blinds included.
+    fn street_bet(&self, seat: usize, street: Street) -> u32 {
+        let posted = match street {
+            Street::Preflop => self
+                .hand
+                .posts
+                .iter()
+                .filter(|post| post.seat == seat && post.blind != Blind::Ante)
+                .map(|post| post.amount)
+                .sum(),
+            _ => 0,
+        };
+
+        self.hand
+            .actions
+            .iter()
+            .filter(|decision| decision.seat == seat && decision.street == street)
+            .fold(posted, |bet, decision| match decision.action {
+                Action::Fold | Action::Check => bet,
+                Action::Call(amount) => bet + amount,
+                Action::Bet(to) | Action::Raise(to) => to,
+            })
+    }
+
+    fn finish(mut self, last: usize) -> Result<History, Error> {
+        if self.section != Section::Summary {
+            return Err(Error::new(last, Kind::Incomplete));
+        }
+
+        let hand = &mut self.hand;
+
+        // Players sitting out who weren't dealt in play no part in the hand.
+        for (seat, &sitting_out) in self.sitting_out.iter().enumerate() {
+            let took_part = hand.posts.iter().any(|post| post.seat == seat)
+                || hand.actions.iter().any(|decision| decision.seat == seat)
+                || !hand.seats[seat].hole.is_empty();
+            if sitting_out && !took_part {
+                hand.seats[seat].stack = 0;
+            }
+        }
+
+        let antes: Vec<&Post> = hand
+            .posts
+            .iter()
+            .filter(|post| post.blind == Blind::Ante)
+            .collect();
+        hand.config.ante = antes.iter().map(|post| post.amount).max().unwrap_or(0);
+        // A lone ante from the big blind is posted for the whole table.
+        hand.config.big_blind_ante = match antes[..] {
+            [ante] => hand
+                .posts
+                .iter()
+                .any(|post| post.blind == Blind::Big && post.seat == ante.seat),
+            _ => false,
+        };
+
+        // Spread limit headers give the limits of bets, not the blinds.
+        if let Betting::SpreadLimit(_) = hand.config.betting {
+            let posted = |blind| {
+                hand.posts
+                    .iter()
+                    .filter(|post| post.blind == blind)
+                    .map(|post| post.amount)
+                    .max()
+                    .unwrap_or(0)
+            };
+            let (small_blind, big_blind) = (posted(Blind::Small), posted(Blind::Big));
+            hand.config.small_blind = small_blind;
+            hand.config.big_blind = big_blind;
+        }
+
+        let mut bets = hand.put_in();
+        if let Some((seat, amount)) = hand.returned {
+            bets[seat] = bets[seat].saturating_sub(amount);
+        }
+
+        let mut pots = Pots::new(hand.seats.len());
+        for (seat, &bet) in bets.iter().enumerate() {
+            pots.add(seat, bet);
+        }
+        for decision in &hand.actions {
+            if decision.action == Action::Fold {
+                pots.fold(decision.seat);
+            }
+        }
+
+        let put_in = pots.total();
+        let collected = hand.won.iter().sum::<u32>() + hand.rake;
+        if put_in != collected {
+            let line = self.total.map_or(last, |(line, _)| line);
+            return Err(Error::new(line, Kind::Mismatch { put_in, collected }));
+        }
+
+        let mut rake = hand.rake;
+        hand.pots = pots.pots();
+        for pot in hand.pots.iter_mut() {
+            let taken = rake.min(pot.amount);
+            pot.amount -= taken;
+            rake -= taken;
+        }
+
+        Ok(self.hand)
+    }
+}
@@ -0,0 +1,283 @@
+//! Hand histories in the text format of PokerStars.
+//!
+//! Hold'em and Omaha are read, at no limit, pot limit, fixed limit and
+//! spread limit, for cash games and tournaments alike. Amounts are counted
+//! in cents for games played for money, and in chips otherwise.
+//!
+//! Hands are written back in the same layout, with amounts in chips, so that
+//! hands played by bots can be opened in tools made for PokerStars.
+pub mod error;
+mod export;
+mod import;
+
+pub use error::{Error, Kind};
+
+use super::History;
+use std::fmt::{self, Write};
+
+/// Reads every hand in `text`, as exported by the PokerStars client.
+///
+/// # Example
+/// ```
+/// # use aces_high::{history::{*, stars}, player::Action};
+/// let text = "\
+/// PokerStars Hand #1: Hold'em No Limit ($0.05/$0.10 USD) - 2020/01/01 0:00:00 ET
+/// Table 'Alpha' 6-max Seat #1 is the button
+/// Seat 1: ann ($10 in chips)
+/// Seat 2: bob ($10 in chips)
+/// ann: posts small blind $0.05
+/// bob: posts big blind $0.10
+/// *** HOLE CARDS ***
+/// ann: raises $0.20 to $0.30
+/// bob: folds
+/// Uncalled bet ($0.20) returned to ann
+/// ann collected $0.20 from pot
+/// *** SUMMARY ***
+/// Total pot $0.20 | Rake $0
+/// ";
+///
+/// let hands = stars::parse(text)?;
+/// assert_eq!(hands[0].table, "Alpha");
+/// assert_eq!(hands[0].seats[1].stack, 1000);
+/// assert_eq!(hands[0].actions[0].action, Action::Raise(30));
+/// assert_eq!(hands[0].net()[..2], [10, -10]);
+/// # Ok::<(), stars::Error>(())
+/// ```
+pub fn parse(text: &str) -> Result<Vec<History>, Error> {
+    let mut hands: Vec<Vec<(usize, &str)>> = Vec::new();
+
+    let lines = text.trim_start_matches('\u{feff}').lines();
+    for (number, line) in lines.enumerate().map(|(i, line)| (i + 1, line.trim())) {
+        if line.is_empty() {
+            continue;
+        }
+        if import::is_start(line) {
+            hands.push(Vec::new());
+        }
+        match hands.last_mut() {
+            Some(hand) => hand.push((number, line)),
+            None => return Err(Error::new(number, Kind::NotAHand)),
+        }
+    }
+
+    hands.iter().map(|lines| import::hand(lines)).collect()
+}
+
+/// Writes `hands` in the text format of PokerStars, which `parse` reads
+/// back.
+///
+/// Every hole card known is written as dealt. Amounts are written in chips,
+/// and since hands don't record when they were played, they are all dated
+/// the same.
+///
+/// Returns an error if a hand has actions on a street whose cards aren't
+/// on its board.
+///
+/// # Example
+/// ```
+/// # use aces_high::{bot::*, history::stars, player::Agent, table::*};
+/// let mut table = Table::new_seeded(Config::no_limit(1, 2), &[100, 100], 3);
+/// let mut agents: Vec<Box<dyn Agent>> = vec![Box::new(Station), Box::new(Maniac)];
+/// table.play(&mut agents)?;
+///
+/// let hand = table.last_hand().unwrap();
+/// let text = stars::write(&[hand.clone()]).unwrap();
+/// assert!(text.starts_with("PokerStars Hand #1: Hold'em No Limit (1/2)"));
+/// assert_eq!(stars::parse(&text).unwrap()[0].actions, hand.actions);
+/// # Ok::<(), Error>(())
+/// ```
+pub fn write(hands: &[History]) -> Result<String, fmt::Error> {
+    let mut text = String::new();
+    for (i, hand) in hands.iter().enumerate() {
+        if i > 0 {
+            text.push_str("\n\n");
+        }
+        write!(text, "{}", export::Text(hand))?;
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+fn corpus(file: &str) -> Vec<History> {
+    let path = format!("{}/data/stars/{}", env!("CARGO_MANIFEST_DIR"), file);
+    let text = std::fs::read_to_string(&path).unwrap();
+    parse(&text).unwrap()
+}
+
+#[test]
+fn corpus_adds_up() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/data/stars");
+    let mut files = 0;
+
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let name = entry.unwrap().file_name().into_string().unwrap();
+        for hand in corpus(&name) {
+            let net: i64 = hand.net().iter().sum();
+            let pots: u32 = hand.pots.iter().map(|pot| pot.amount).sum();
+            assert_eq!(net + i64::from(hand.rake), 0, "{} #{}", name, hand.id);
+            assert_eq!(pots, hand.won.iter().sum::<u32>(), "{} #{}", name, hand.id);
+            assert_eq!(hand.seats[hand.button].name.is_empty(), false);
+        }
+        files += 1;
+    }
+
+    assert_eq!(files, 4);
+}
+
+#[test]
+fn cash() {
+    use super::Game;
+    use crate::table::{structure::Betting, Config};
+
+    let hands = corpus("cash_holdem.txt");
+    assert_eq!(hands.len(), 3);
+    assert!(hands.iter().all(|hand| hand.game == Game::Holdem));
+    assert!(hands
+        .iter()
+        .all(|hand| hand.config == Config::no_limit(5, 10)));
+    assert!(hands.iter().all(|hand| hand.tournament.is_none()));
+    assert!(hands
+        .iter()
+        .any(|hand| hand.seats.iter().any(|seat| seat.name == "reg: the second")));
+    assert!(hands.iter().any(|hand| hand.returned.is_some()));
+    assert!(hands.iter().any(|hand| hand.pots.len() == 2));
+
+    let hands = corpus("omaha_pot_limit.txt");
+    assert_eq!(hands.len(), 2);
+    assert!(hands.iter().all(|hand| hand.game == Game::Omaha));
+    assert!(hands
+        .iter()
+        .all(|hand| hand.config.betting == Betting::PotLimit));
+    assert!(hands
+        .iter()
+        .flat_map(|hand| &hand.seats)
+        .all(|seat| [0, 4].contains(&seat.hole.len())));
+}
+
+#[test]
+fn limit_and_tournament() {
+    use crate::table::structure::{Betting, FixedLimit};
+
+    let hand = &corpus("limit_holdem.txt")[0];
+    assert_eq!(
+        hand.config.betting,
+        Betting::FixedLimit(FixedLimit::new(50))
+    );
+    assert_eq!((hand.config.small_blind, hand.config.big_blind), (25, 50));
+    assert_eq!(hand.rake, 60);
+    assert_eq!(hand.board.len(), 5);
+
+    let hands = corpus("tournament_holdem.txt");
+    assert_eq!(hands.len(), 2);
+    for hand in &hands {
+        assert!(hand.tournament.is_some());
+        assert_eq!((hand.config.small_blind, hand.config.big_blind), (50, 100));
+        assert!(hand.config.ante > 0);
+        assert_eq!(hand.rake, 0);
+    }
+}
+
+#[test]
+fn errors() {
+    let header = "PokerStars Hand #7: Hold'em No Limit (5/10) - 2020/01/01 0:00:00 ET";
+    let seats = "Table 'T' 2-max Seat #1 is the button\n\
+                 Seat 1: a (100 in chips)\nSeat 2: b (100 in chips)";
+    let error = |text: &str| parse(text).unwrap_err();
+
+    assert_eq!(error("hello\n"), Error::new(1, Kind::NotAHand));
+    assert_eq!(error(header), Error::new(1, Kind::NoTable));
+    assert_eq!(
+        error(&format!("{}\n{}\n\na: folds\n", header, seats)),
+        Error::new(6, Kind::Unexpected("folds".to_string()))
+    );
+    assert_eq!(
+        error(&format!(
+            "{}\n{}\n*** HOLE CARDS ***\na: dances\n",
+            header, seats
+        )),
+        Error::new(6, Kind::UnknownAction("dances".to_string()))
+    );
+    assert_eq!(
+        error(&format!(
+            "{}\n{}\n*** HOLE CARDS ***\nDealt to a [Zz 2c]\n",
+            header, seats
+        )),
+        Error::new(6, Kind::BadCard("Zz".to_string()))
+    );
+    assert_eq!(
+        error(&format!("{}\n{}\n*** HOLE CARDS ***\n", header, seats)),
+        Error::new(5, Kind::Incomplete)
+    );
+    assert_eq!(
+        error(&format!(
+            "{}\n{}\na: posts small blind 5\nb: posts big blind 10\n*** HOLE CARDS ***\n\
+             a: raises 10 to 5\n",
+            header, seats
+        )),
+        Error::new(8, Kind::BadAmount("10 to 5".to_string()))
+    );
+    // Lines of the wrong section, before anything was sized for them.
+    assert_eq!(
+        error(&format!("{}\n{}\nb collected 20 from pot\n", header, seats)),
+        Error::new(5, Kind::Incomplete)
+    );
+    assert_eq!(
+        error(&format!("{}\n{}\n*** SHOW DOWN ***\n", header, seats)),
+        Error::new(5, Kind::Unexpected("*** SHOW DOWN ***".to_string()))
+    );
+    assert_eq!(
+        error(&format!(
+            "{}\n{}\na: posts small blind 5\nb: posts big blind 10\n*** HOLE CARDS ***\n\
+             a: folds\nb collected 20 from pot\n*** SUMMARY ***\nTotal pot 15 | Rake 0\n",
+            header, seats
+        )),
+        Error::new(
+            11,
+            Kind::Mismatch {
+                put_in: 15,
+                collected: 20
+            }
+        )
+    );
+}
+
+#[test]
+fn round_trips() {
+    use crate::table::{
+        structure::{Betting, SpreadLimit},
+        Config, Street,
+    };
+
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/data/stars");
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let hands = corpus(&entry.unwrap().file_name().into_string().unwrap());
+        assert_eq!(parse(&write(&hands).unwrap()).unwrap(), hands);
+    }
+
+    let mut config = Config::no_limit(5, 10);
+    config.ante = 1;
+    let mut hands = super::played(config, &[300, 200, 250, 400], 4, 30);
+    let spread = Config {
+        betting: Betting::SpreadLimit(SpreadLimit { min: 2, max: 6 }),
+        ..Config::no_limit(1, 2)
+    };
+    hands.extend(super::played(spread, &[500, 500, 500], 6, 10));
+    for hand in hands.iter_mut() {
+        hand.table = "Aces High".to_string();
+        hand.seed = None;
+    }
+
+    assert_eq!(parse(&write(&hands).unwrap()).unwrap(), hands);
+
+    // Actions on the river can't be written without its card.
+    let mut short = hands
+        .into_iter()
+        .find(|hand| {
+            hand.actions
+                .iter()
+                .any(|decision| decision.street == Street::River)
+        })
+        .unwrap();
+    short.board.truncate(4);
+    assert_eq!(write(&[short]), Err(fmt::Error));
+}
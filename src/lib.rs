@@ -27,14 +27,21 @@ extern crate strum;
 extern crate strum_macros;
 extern crate variant_count;
 
-//pub mod bot; // Included bots
 //pub mod betting // Chance and gameplay statistics
-//pub mod score // Player statistics
 #[macro_use]
 pub mod card; // Structure
 pub mod deck; // Structure
 #[macro_use]
 pub mod hand; // Poker logic
+// Declared after the modules whose macros it uses.
+pub mod bot; // Included bots
+pub mod arena; // Bot matches
+pub mod gym; // Reinforcement learning
+pub mod acpc; // Computer poker competition protocol
+pub mod history; // Hand records
 pub mod player; // Player interface
 pub mod prelude;
+pub mod score; // Player statistics
+pub mod solver; // Equilibrium strategies
 pub mod table; // Gameplay logic
+pub mod tournament; // Blinds, eliminations and prizes
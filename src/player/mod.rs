@@ -1,70 +1,115 @@
-/*
+mod view;
+
 use crate::card::Card;
-use crate::hand::Hand;
+use crate::table::{Street, Summary};
+pub use view::View;
 
+/// A seat at the table and the chips belonging to it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Player {
+    /// Chips behind, not yet put into the middle.
     pub pot: u32,
+    /// Chips put into the middle during the current hand.
     pub tot_bet: u32,
 
-    hand: Option<Hand>,
+    cards: Vec<Card>,
 }
 
-pub struct Action(ActionInner);
-
-enum ActionInner {
-    Fold(Vec<Card>),
-    /// Call is multifunctional. It works as both check, call and raise.
-    ///
-    /// It will automatically match any bid if 0 is provided and
-    /// automatically raise value if not 0.
-    ///
-    /// Mind that the table might ignore small raises.
+/// A decision made by a seat when it's their turn to act.
+///
+/// `Bet` and `Raise` hold what the seat's total bet on the street becomes,
+/// not how much is added to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    Fold,
+    Check,
+    /// Matches the current bet by adding the given amount.
+    /// It's less than what is owed only when calling all-in.
     Call(u32),
+    /// Opens the betting on a street.
+    Bet(u32),
+    /// Raises the current bet on a street.
+    Raise(u32),
 }
 
-impl Action {
-    pub fn folding(player: &mut Player) -> Action {
-        Action(ActionInner::Fold(player.discard()))
-    }
-
-    pub fn calling(player: &mut Player, mut bet: u32) -> Action {
-        bet = std::cmp::min(bet, player.pot);
-        player.pot -= bet;
-        Action(ActionInner::Call(bet))
-    }
+/// An `Action` taken by a seat.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Decision {
+    pub seat: usize,
+    pub street: Street,
+    pub action: Action,
 }
 
 impl Player {
-    pub fn take(&mut self, cards: Vec<Card>) -> Result<(), Error> {
-        match &mut self.hand {
-            None => {
-                self.hand = Some(Hand::new(cards)?);
-                Ok(())
-            }
-            _ => panic!(), // TODO
+    /// Constructs a new `Player` sitting down with `pot` chips.
+    ///
+    /// # Example
+    /// ```
+    /// # use aces_high::player::Player;
+    /// let player = Player::new(1_000);
+    /// assert_eq!(player.pot, 1_000);
+    /// assert!(player.hand_is_empty());
+    /// ```
+    pub fn new(pot: u32) -> Self {
+        Player {
+            pot,
+            tot_bet: 0,
+            cards: Vec::new(),
         }
     }
 
+    pub fn take(&mut self, mut cards: Vec<Card>) {
+        self.cards.append(&mut cards);
+    }
+
     pub fn discard(&mut self) -> Vec<Card> {
-        match self.hand.take() {
-            Some(hand) => hand.discard(),
-            None => Vec::new(),
-        }
+        std::mem::take(&mut self.cards)
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
     }
 
     pub fn hand_len(&self) -> usize {
-        match &self.hand {
-            Some(hand) => hand.len(),
-            None => 0,
-        }
+        self.cards.len()
+    }
+
+    pub fn hand_is_empty(&self) -> bool {
+        self.cards.is_empty()
     }
 
-    pub fn hand_is_none(&self) -> bool {
-        self.hand.is_none()
+    /// Moves up to `amount` chips from `pot` to `tot_bet`.
+    /// Returns how many chips were actually moved, which is less than
+    /// `amount` if the player can't cover it.
+    pub fn bet(&mut self, amount: u32) -> u32 {
+        let amount = std::cmp::min(amount, self.pot);
+        self.pot -= amount;
+        self.tot_bet += amount;
+        amount
+    }
+
+    /// Returns `true` if the player has put chips in and has none left.
+    pub fn is_all_in(&self) -> bool {
+        self.pot == 0 && self.tot_bet > 0
     }
 }
 
-trait Inter {
-    fn decide(&self) -> Action;
+/// Plays a seat at a `Table`. This is what bots are written against.
+///
+/// Only `decide` is required, the other methods let an agent keep track of
+/// what happens and default to doing nothing.
+pub trait Agent {
+    /// Picks an action for the seat. It must be one of `view.legal`,
+    /// otherwise the table rejects it.
+    fn decide(&mut self, view: &View) -> Action;
+
+    /// Called once every seat has been dealt its cards.
+    fn hand_start(&mut self, _view: &View) {}
+
+    /// Called when more than one seat is left after the river, with the cards
+    /// each of them shows.
+    fn showdown(&mut self, _view: &View, _shown: &[(usize, Vec<Card>)]) {}
+
+    /// Called once the pots have been awarded.
+    fn hand_end(&mut self, _view: &View, _summary: &Summary) {}
 }
-*/
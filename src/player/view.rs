@@ -0,0 +1,76 @@
+use super::{Action, Decision};
+use crate::card::Card;
+use crate::table::{structure::Legal, Seat, Street};
+use std::cmp;
+
+/// What a seat is allowed to know about a hand in progress.
+#[derive(Clone, Copy, Debug)]
+pub struct View<'a> {
+    /// The seat this view belongs to.
+    pub seat: usize,
+    pub button: usize,
+    pub street: Street,
+    pub big_blind: u32,
+    /// The seat's own cards.
+    pub hole: &'a [Card],
+    pub board: &'a [Card],
+    /// All chips in the middle, including bets on the current street.
+    pub pot: u32,
+    /// What everyone can see about every seat, the own seat included.
+    pub seats: &'a [Seat],
+    /// Every action taken so far this hand, blinds and antes excluded.
+    pub history: &'a [Decision],
+    /// Legal actions of the seat. Only meaningful when asked to decide.
+    pub legal: Legal,
+}
+
+impl<'a> View<'a> {
+    /// Returns the chips the seat has behind.
+    pub fn stack(&self) -> u32 {
+        self.seats[self.seat].stack
+    }
+
+    /// Returns what the seat owes to call, or 0 if it can check.
+    pub fn to_call(&self) -> u32 {
+        self.legal.call.unwrap_or(0)
+    }
+
+    /// Returns the largest bet on the current street.
+    pub fn to_match(&self) -> u32 {
+        self.seats.iter().map(|seat| seat.bet).max().unwrap_or(0)
+    }
+
+    /// Returns checking if it's legal, otherwise calling.
+    pub fn check_or_call(&self) -> Action {
+        match self.legal.call {
+            Some(amount) => Action::Call(amount),
+            None => Action::Check,
+        }
+    }
+
+    /// Returns a bet or raise to `to`, moved within the legal limits,
+    /// or `None` if the seat may not raise.
+    pub fn raise_to(&self, to: u32) -> Option<Action> {
+        self.legal.raise.map(|limits| {
+            let to = cmp::max(limits.min, cmp::min(to, limits.max));
+            if self.to_match() == 0 {
+                Action::Bet(to)
+            } else {
+                Action::Raise(to)
+            }
+        })
+    }
+
+    /// Returns the actions taken on the current street.
+    pub fn street_history(&self) -> impl Iterator<Item = &'a Decision> {
+        let street = self.street;
+        self.history
+            .iter()
+            .filter(move |decision| decision.street == street)
+    }
+
+    /// Returns the number of seats still in the hand.
+    pub fn in_hand(&self) -> usize {
+        self.seats.iter().filter(|seat| seat.in_hand()).count()
+    }
+}
@@ -0,0 +1,97 @@
+use crate::card::Card;
+use crate::hand::{equity::remaining, Value};
+use crate::table::{pot::Pots, Street, Summary};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+/// Runouts sampled when all-in before the flop, where there are too many to
+/// go through them all.
+const SAMPLES: u32 = 2_000;
+
+/// Returns what every seat was expected to win when the last action was
+/// taken, if the hand went to showdown with board cards still to come.
+///
+/// Every runout is dealt to the seats at showdown and the pots are awarded
+/// as usual, side pots included. Runouts of one or two cards are gone
+/// through exhaustively, longer ones are sampled with a fixed seed so that
+/// the result is the same every time.
+pub(super) fn all_in(summary: &Summary) -> Option<Vec<f64>> {
+    if summary.shown.len() < 2 {
+        return None;
+    }
+
+    let known = match summary.history.last().map(|decision| decision.street) {
+        None | Some(Street::Preflop) => 0,
+        Some(Street::Flop) => 3,
+        Some(Street::Turn) => 4,
+        Some(Street::River) => 5,
+    };
+    if summary.board.len() <= known {
+        return None;
+    }
+
+    let seats = summary.stacks.len();
+    let board = &summary.board[..known];
+    let bets: Vec<u32> = summary
+        .won
+        .iter()
+        .zip(&summary.net)
+        .map(|(&won, &net)| (i64::from(won) - net) as u32)
+        .collect();
+
+    let mut pots = Pots::new(seats);
+    for (seat, &bet) in bets.iter().enumerate() {
+        pots.add(seat, bet);
+        if summary.shown.iter().all(|&(shown, _)| shown != seat) {
+            pots.fold(seat);
+        }
+    }
+
+    let mut hands: Vec<&[Card]> = summary.shown.iter().map(|(_, cards)| &cards[..]).collect();
+    hands.push(board);
+    let mut deck = remaining(&hands);
+    let missing = 5 - known;
+
+    let mut won = vec![0.0; seats];
+    let mut runouts = 0;
+    let mut cards = Vec::with_capacity(7);
+    let mut values = vec![None; seats];
+
+    let mut award = |runout: &[Card]| {
+        for (seat, hole) in &summary.shown {
+            cards.clear();
+            cards.extend_from_slice(hole);
+            cards.extend_from_slice(board);
+            cards.extend_from_slice(runout);
+            values[*seat] = Some(Value::new(&cards));
+        }
+        for (won, amount) in won.iter_mut().zip(pots.award(summary.button, &values)) {
+            *won += f64::from(amount);
+        }
+        runouts += 1;
+    };
+
+    match missing {
+        1 => deck.iter().for_each(|&card| award(&[card])),
+        2 => {
+            for (i, &first) in deck.iter().enumerate() {
+                for &second in &deck[i + 1..] {
+                    award(&[first, second]);
+                }
+            }
+        }
+        _ => {
+            let mut rng = StdRng::seed_from_u64(0);
+            for _ in 0..SAMPLES {
+                let (runout, _) = deck.partial_shuffle(&mut rng, missing);
+                award(runout);
+            }
+        }
+    }
+
+    Some(
+        won.iter()
+            .zip(&bets)
+            .map(|(won, &bet)| won / f64::from(runouts) - f64::from(bet))
+            .collect(),
+    )
+}
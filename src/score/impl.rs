@@ -0,0 +1,249 @@
+use super::{ev, Position, Stats, Tracker};
+use crate::player::Action;
+use crate::table::{Street, Summary};
+use std::collections::HashMap;
+
+/// Returns `part / whole`, or `None` if `whole` is zero.
+fn ratio(part: u64, whole: u64) -> Option<f64> {
+    if whole == 0 {
+        None
+    } else {
+        Some(part as f64 / whole as f64)
+    }
+}
+
+impl Position {
+    /// Returns the position of `seat`, or `None` if it wasn't dealt in.
+    pub fn new(seat: usize, button: usize, dealt: &[bool]) -> Option<Position> {
+        let seats = dealt.len();
+        let order: Vec<usize> = (0..seats)
+            .map(|i| (button + i) % seats)
+            .filter(|&seat| dealt[seat])
+            .collect();
+        let index = order.iter().position(|&other| other == seat)?;
+
+        Some(match (order.len(), index) {
+            (_, 0) => Position::Button,
+            (2, _) => Position::BigBlind,
+            (_, 1) => Position::SmallBlind,
+            (_, 2) => Position::BigBlind,
+            (count, index) => {
+                let (between, index) = (count - 3, index - 3);
+                if index + 1 == between {
+                    Position::Cutoff
+                } else if index < between / 2 {
+                    Position::Early
+                } else {
+                    Position::Middle
+                }
+            }
+        })
+    }
+}
+
+impl Stats {
+    /// Share of hands where chips were put in preflop voluntarily.
+    pub fn vpip(&self) -> Option<f64> {
+        ratio(self.vpip, self.hands)
+    }
+
+    /// Share of hands raised preflop.
+    pub fn pfr(&self) -> Option<f64> {
+        ratio(self.pfr, self.hands)
+    }
+
+    /// Share of single raises faced preflop which were reraised.
+    pub fn three_bet(&self) -> Option<f64> {
+        ratio(self.three_bets, self.three_bet_chances)
+    }
+
+    /// Share of chances taken to continue betting on the flop after raising
+    /// preflop.
+    pub fn cbet(&self) -> Option<f64> {
+        ratio(self.cbets, self.cbet_chances)
+    }
+
+    pub fn fold_to_cbet(&self) -> Option<f64> {
+        ratio(self.folded_to_cbets, self.faced_cbets)
+    }
+
+    /// Bets and raises per call after the flop.
+    pub fn aggression(&self) -> Option<f64> {
+        ratio(self.aggressive, self.calls)
+    }
+
+    /// Share of flops seen that went to showdown.
+    pub fn wtsd(&self) -> Option<f64> {
+        ratio(self.showdowns, self.saw_flop)
+    }
+
+    /// Share of showdowns where chips were won.
+    pub fn wsd(&self) -> Option<f64> {
+        ratio(self.won_showdowns, self.showdowns)
+    }
+
+    /// Adds the counters of `other` to these.
+    pub fn merge(&mut self, other: &Stats) {
+        self.hands += other.hands;
+        self.vpip += other.vpip;
+        self.pfr += other.pfr;
+        self.three_bet_chances += other.three_bet_chances;
+        self.three_bets += other.three_bets;
+        self.cbet_chances += other.cbet_chances;
+        self.cbets += other.cbets;
+        self.faced_cbets += other.faced_cbets;
+        self.folded_to_cbets += other.folded_to_cbets;
+        self.aggressive += other.aggressive;
+        self.calls += other.calls;
+        self.saw_flop += other.saw_flop;
+        self.showdowns += other.showdowns;
+        self.won_showdowns += other.won_showdowns;
+        self.net += other.net;
+        self.ev += other.ev;
+    }
+
+    /// Counts what every seat did in a hand.
+    fn from_summary(summary: &Summary) -> Vec<Stats> {
+        let seats = summary.stacks.len();
+        let mut stats = vec![Stats::default(); seats];
+        let ev = ev::all_in(summary);
+
+        for (seat, stats) in stats.iter_mut().enumerate() {
+            if summary.stacks[seat] > 0 {
+                stats.hands = 1;
+                stats.net = summary.net[seat];
+                stats.ev = ev.as_ref().map_or(summary.net[seat] as f64, |ev| ev[seat]);
+            }
+        }
+
+        let mut raises = 0;
+        let mut aggressor = None;
+        // Whoever folds preflop doesn't see the flop.
+        let mut folded = vec![false; seats];
+        let mut cbet = false;
+        let mut raised_cbet = false;
+
+        for decision in &summary.history {
+            let (seat, action) = (decision.seat, decision.action);
+            let aggressive = matches!(action, Action::Bet(_) | Action::Raise(_));
+
+            match decision.street {
+                Street::Preflop => {
+                    let stats = &mut stats[seat];
+                    if raises == 1 && stats.three_bet_chances == 0 {
+                        stats.three_bet_chances = 1;
+                        stats.three_bets = aggressive as u64;
+                    }
+                    if action != Action::Fold && action != Action::Check {
+                        stats.vpip = 1;
+                    }
+                    if aggressive {
+                        stats.pfr = 1;
+                        raises += 1;
+                        aggressor = Some(seat);
+                    }
+                    if action == Action::Fold {
+                        folded[seat] = true;
+                    }
+                }
+                street => {
+                    if street == Street::Flop && !raised_cbet {
+                        if cbet && stats[seat].faced_cbets == 0 {
+                            stats[seat].faced_cbets = 1;
+                            stats[seat].folded_to_cbets = (action == Action::Fold) as u64;
+                            raised_cbet = aggressive;
+                        } else if !cbet && aggressor == Some(seat) {
+                            stats[seat].cbet_chances = 1;
+                            stats[seat].cbets = aggressive as u64;
+                            cbet = aggressive;
+                        } else if aggressive {
+                            // Someone else bet first.
+                            raised_cbet = true;
+                        }
+                    }
+
+                    if aggressive {
+                        stats[seat].aggressive += 1;
+                    } else if let Action::Call(_) = action {
+                        stats[seat].calls += 1;
+                    }
+                }
+            }
+        }
+
+        if summary.board.len() >= 3 {
+            for (seat, stats) in stats.iter_mut().enumerate() {
+                stats.saw_flop = (summary.stacks[seat] > 0 && !folded[seat]) as u64;
+            }
+        }
+
+        for (seat, _) in &summary.shown {
+            stats[*seat].showdowns = 1;
+            stats[*seat].won_showdowns = (summary.won[*seat] > 0) as u64;
+        }
+
+        stats
+    }
+}
+
+impl Tracker {
+    pub fn new() -> Self {
+        Tracker {
+            stats: HashMap::new(),
+        }
+    }
+
+    /// Counts a hand, where `names[seat]` is the name of the player in each
+    /// seat.
+    ///
+    /// # Panics
+    /// Panics if there isn't exactly one name per seat.
+    pub fn add<S: AsRef<str>>(&mut self, names: &[S], summary: &Summary) {
+        assert_eq!(names.len(), summary.stacks.len(), "every seat needs a name");
+
+        let dealt: Vec<bool> = summary.stacks.iter().map(|&stack| stack > 0).collect();
+
+        for (seat, stats) in Stats::from_summary(summary).iter().enumerate() {
+            if let Some(position) = Position::new(seat, summary.button, &dealt) {
+                self.stats
+                    .entry((names[seat].as_ref().to_string(), position))
+                    .or_default()
+                    .merge(stats);
+            }
+        }
+    }
+
+    /// Adds every hand counted by `other`, such as those of another session.
+    pub fn merge(&mut self, other: &Tracker) {
+        for (key, stats) in &other.stats {
+            self.stats.entry(key.clone()).or_default().merge(stats);
+        }
+    }
+
+    /// Returns the names of all players, sorted.
+    pub fn players(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.stats.keys().map(|(name, _)| name.as_str()).collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Returns the statistics of `name` over all positions.
+    pub fn stats(&self, name: &str) -> Stats {
+        let mut total = Stats::default();
+        for ((other, _), stats) in &self.stats {
+            if other == name {
+                total.merge(stats);
+            }
+        }
+        total
+    }
+
+    /// Returns the statistics of `name` in `position`.
+    pub fn position(&self, name: &str, position: Position) -> Stats {
+        self.stats
+            .get(&(name.to_string(), position))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
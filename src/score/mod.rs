@@ -0,0 +1,295 @@
+//! Player statistics gathered from played hands.
+mod ev;
+mod r#impl;
+
+use std::collections::HashMap;
+
+/// Where a seat sits relative to the button, in the order of acting
+/// preflop.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Position {
+    /// The first half of the seats between the big blind and the cutoff.
+    Early,
+    /// The second half of the seats between the big blind and the cutoff.
+    Middle,
+    /// Right before the button.
+    Cutoff,
+    /// Acts last after the flop. Heads up, it also posts the small blind.
+    Button,
+    SmallBlind,
+    BigBlind,
+}
+
+/// Counters of what a player did, and the chances they had to do it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub hands: u64,
+    /// Hands where chips were put in preflop voluntarily, blinds excluded.
+    pub vpip: u64,
+    /// Hands raised preflop.
+    pub pfr: u64,
+    /// Hands where the player faced a single raise preflop.
+    pub three_bet_chances: u64,
+    pub three_bets: u64,
+    /// Hands where the preflop aggressor could bet first on the flop.
+    pub cbet_chances: u64,
+    pub cbets: u64,
+    pub faced_cbets: u64,
+    pub folded_to_cbets: u64,
+    /// Bets and raises after the flop.
+    pub aggressive: u64,
+    /// Calls after the flop.
+    pub calls: u64,
+    pub saw_flop: u64,
+    pub showdowns: u64,
+    /// Showdowns where any chips were won.
+    pub won_showdowns: u64,
+    /// Chips won, or lost if negative.
+    pub net: i64,
+    /// Chips expected to be won, with the luck of the cards dealt after
+    /// everyone was all-in taken out.
+    pub ev: f64,
+}
+
+/// Statistics of every player, kept per position.
+///
+/// # Example
+/// ```
+/// # use aces_high::{bot::*, player::Agent, score::*, table::*};
+/// let mut table = Table::new_seeded(Config::no_limit(1, 2), &[200, 200], 1);
+/// let mut agents: Vec<Box<dyn Agent>> = vec![Box::new(Maniac), Box::new(Station)];
+/// let mut tracker = Tracker::new();
+///
+/// for _ in 0..10 {
+///     let summary = table.play(&mut agents)?;
+///     tracker.add(&["maniac", "station"], &summary);
+///     for player in table.players_mut() {
+///         player.pot = 200;
+///     }
+/// }
+///
+/// let maniac = tracker.stats("maniac");
+/// assert_eq!(maniac.hands, 10);
+/// assert_eq!(maniac.pfr(), Some(1.0));
+/// assert_eq!(tracker.stats("station").pfr(), Some(0.0));
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Tracker {
+    stats: HashMap<(String, Position), Stats>,
+}
+
+#[cfg(test)]
+fn summary(button: usize, stacks: &[u32]) -> crate::table::Summary {
+    crate::table::Summary {
+        button,
+        stacks: stacks.to_vec(),
+        won: vec![0; stacks.len()],
+        net: vec![0; stacks.len()],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn positions() {
+    use Position::*;
+
+    let dealt = [true; 6];
+    let at = |seat| Position::new(seat, 0, &dealt);
+    assert_eq!(
+        (0..6).map(at).collect::<Vec<_>>(),
+        vec![
+            Some(Button),
+            Some(SmallBlind),
+            Some(BigBlind),
+            Some(Early),
+            Some(Middle),
+            Some(Cutoff)
+        ]
+    );
+
+    let dealt = [true, false, true];
+    assert_eq!(Position::new(2, 2, &dealt), Some(Button));
+    assert_eq!(Position::new(0, 2, &dealt), Some(BigBlind));
+    assert_eq!(Position::new(1, 2, &dealt), None);
+}
+
+#[test]
+fn preflop() {
+    use crate::player::{Action::*, Decision};
+    use crate::table::Street::*;
+
+    // Seat 3 opens, 4 folds, 5 three-bets, 0 calls, the blinds fold, 3 calls.
+    let mut hand = summary(0, &[100; 6]);
+    hand.history = [
+        (3, Raise(6)),
+        (4, Fold),
+        (5, Raise(18)),
+        (0, Call(18)),
+        (1, Fold),
+        (2, Fold),
+        (3, Call(12)),
+    ]
+    .iter()
+    .map(|&(seat, action)| Decision {
+        seat,
+        street: Preflop,
+        action,
+    })
+    .collect();
+
+    let mut tracker = Tracker::new();
+    tracker.add(&["a", "b", "c", "d", "e", "f"], &hand);
+
+    let stats = |name| tracker.stats(name);
+    assert_eq!(stats("d").vpip, 1);
+    assert_eq!(stats("d").pfr, 1);
+    assert_eq!(stats("d").three_bet_chances, 0);
+    assert_eq!(stats("e").vpip, 0);
+    assert_eq!(stats("f").three_bets, 1);
+    assert_eq!(stats("a").three_bet_chances, 0);
+    assert_eq!(stats("a").vpip, 1);
+    assert_eq!(stats("b").vpip, 0);
+    assert_eq!(stats("c").three_bet_chances, 0);
+    assert_eq!(tracker.position("f", Position::Cutoff).pfr, 1);
+}
+
+#[test]
+fn postflop() {
+    use crate::card::face::*;
+    use crate::player::{Action::*, Decision};
+    use crate::table::Street::*;
+
+    // Heads up, the button raises, bets the flop and gets called down.
+    let mut hand = summary(0, &[100; 2]);
+    hand.history = [
+        (0, Preflop, Raise(6)),
+        (1, Preflop, Call(4)),
+        (1, Flop, Check),
+        (0, Flop, Bet(6)),
+        (1, Flop, Call(6)),
+        (1, Turn, Check),
+        (0, Turn, Check),
+        (1, River, Bet(10)),
+        (0, River, Raise(30)),
+        (1, River, Call(20)),
+    ]
+    .iter()
+    .map(|&(seat, street, action)| Decision {
+        seat,
+        street,
+        action,
+    })
+    .collect();
+    hand.board =
+        cards!(Two, Clubs; Seven, Hearts; Nine, Spades; King, Diamonds; Four, Clubs).to_vec();
+    hand.shown = vec![
+        (0, cards!(Ace, Spades; Ace, Hearts).to_vec()),
+        (1, cards!(King, Spades; Queen, Hearts).to_vec()),
+    ];
+    hand.won = vec![84, 0];
+    hand.net = vec![42, -42];
+
+    let mut tracker = Tracker::new();
+    tracker.add(&["button", "blind"], &hand);
+    let (button, blind) = (tracker.stats("button"), tracker.stats("blind"));
+
+    assert_eq!((button.cbet_chances, button.cbets), (1, 1));
+    assert_eq!((blind.faced_cbets, blind.folded_to_cbets), (1, 0));
+    assert_eq!(button.aggression(), None);
+    assert_eq!(blind.aggression(), Some(0.5));
+    assert_eq!((button.wtsd(), button.wsd()), (Some(1.0), Some(1.0)));
+    assert_eq!((blind.wtsd(), blind.wsd()), (Some(1.0), Some(0.0)));
+    assert_eq!(button.ev, 42.0);
+    assert_eq!(tracker.position("blind", Position::BigBlind).net, -42);
+}
+
+#[test]
+fn all_in_ev() {
+    use crate::card::face::*;
+    use crate::player::{Action::*, Decision};
+    use crate::table::Street::*;
+
+    // All-in on the turn with a set against a flush draw, which hits.
+    let mut hand = summary(0, &[100; 2]);
+    hand.history = [
+        (0, Preflop, Call(1)),
+        (1, Preflop, Check),
+        (1, Flop, Check),
+        (0, Flop, Check),
+        (1, Turn, Bet(98)),
+        (0, Turn, Call(98)),
+    ]
+    .iter()
+    .map(|&(seat, street, action)| Decision {
+        seat,
+        street,
+        action,
+    })
+    .collect();
+    hand.board =
+        cards!(Nine, Spades; Nine, Hearts; Two, Spades; Four, Clubs; Seven, Spades).to_vec();
+    hand.shown = vec![
+        (0, cards!(Ace, Spades; Ten, Spades).to_vec()),
+        (1, cards!(Nine, Clubs; King, Hearts).to_vec()),
+    ];
+    hand.won = vec![200, 0];
+    hand.net = vec![100, -100];
+
+    let mut tracker = Tracker::new();
+    tracker.add(&["draw", "trips"], &hand);
+
+    // 9 spades of 44 cards, minus the two giving the set a full house.
+    let flush = 7.0 / 44.0;
+    let ev = |name| tracker.stats(name).ev;
+    assert!((ev("draw") - (200.0 * flush - 100.0)).abs() < 1e-9);
+    assert!((ev("trips") + ev("draw")).abs() < 1e-9);
+}
+
+#[test]
+fn folding_after_the_flop() {
+    use crate::card::face::*;
+    use crate::player::{Action::*, Decision};
+    use crate::table::Street::*;
+
+    // The button calls, the small blind folds, and the button folds the turn.
+    let mut hand = summary(0, &[100; 3]);
+    hand.history = [
+        (0, Preflop, Call(2)),
+        (1, Preflop, Fold),
+        (2, Preflop, Check),
+        (2, Flop, Check),
+        (0, Flop, Check),
+        (2, Turn, Bet(4)),
+        (0, Turn, Fold),
+    ]
+    .iter()
+    .map(|&(seat, street, action)| Decision {
+        seat,
+        street,
+        action,
+    })
+    .collect();
+    hand.board = cards!(Two, Clubs; Seven, Hearts; Nine, Spades; King, Diamonds).to_vec();
+
+    let mut tracker = Tracker::new();
+    tracker.add(&["button", "small", "big"], &hand);
+    let stats = |name| tracker.stats(name);
+    assert_eq!(stats("button").saw_flop, 1);
+    assert_eq!(stats("button").wtsd(), Some(0.0));
+    assert_eq!(stats("small").saw_flop, 0);
+    assert_eq!(stats("big").saw_flop, 1);
+}
+
+#[test]
+fn merging() {
+    let mut one = Tracker::new();
+    one.add(&["a", "b"], &summary(0, &[10, 10]));
+    let mut two = Tracker::new();
+    two.add(&["b", "c"], &summary(1, &[10, 10]));
+
+    one.merge(&two);
+    assert_eq!(one.players(), vec!["a", "b", "c"]);
+    assert_eq!(one.stats("b").hands, 2);
+    assert_eq!(one.position("b", Position::BigBlind).hands, 2);
+}
@@ -0,0 +1,258 @@
+//! Abstractions on disk, so that they're built only once.
+//!
+//! A file starts with the bytes `ABST` and the version of the format. Then
+//! come the config and the seed, the centroids and strengths of the flop,
+//! turn and river, and the buckets worked out so far. Numbers are little
+//! endian, counts `u32` and values `f64`.
+use super::{Abstraction, Config, Method};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+const MAGIC: &[u8; 4] = b"ABST";
+
+/// Version of the format written, and the only one read.
+const VERSION: u8 = 1;
+
+impl Abstraction {
+    /// Writes the abstraction to `path`, with every bucket worked out.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Reads an abstraction written by `save`.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Abstraction::read(&mut BufReader::new(File::open(path)?))
+    }
+
+    /// Reads the abstraction at `path` if it was built with `config` and
+    /// `seed`, or else builds it and writes it there.
+    pub fn load_or_build<P: AsRef<Path>>(path: P, config: Config, seed: u64) -> io::Result<Self> {
+        let path = path.as_ref();
+        if let Ok(abstraction) = Abstraction::load(path) {
+            if abstraction.config == config && abstraction.seed == seed {
+                return Ok(abstraction);
+            }
+        }
+        let abstraction = Abstraction::build(config, seed);
+        abstraction.save(path)?;
+        Ok(abstraction)
+    }
+
+    pub fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+
+        let config = &self.config;
+        let (method, bins) = match config.method {
+            Method::Strength => (0, 0),
+            Method::Distribution { bins } => (1, bins),
+            Method::Potential => (2, 0),
+        };
+        writer.write_all(&[method])?;
+        write_len(writer, bins)?;
+        for &buckets in &config.buckets {
+            write_len(writer, buckets)?;
+        }
+        write_len(writer, config.training)?;
+        writer.write_all(&config.rollouts.to_le_bytes())?;
+        write_len(writer, config.iterations)?;
+        writer.write_all(&self.seed.to_le_bytes())?;
+
+        for (centroids, strengths) in self.centroids.iter().zip(&self.strengths) {
+            write_len(writer, centroids.len())?;
+            for (centroid, strength) in centroids.iter().zip(strengths) {
+                writer.write_all(&strength.to_le_bytes())?;
+                write_len(writer, centroid.len())?;
+                for x in centroid {
+                    writer.write_all(&x.to_le_bytes())?;
+                }
+            }
+        }
+
+        let mut known: Vec<(Vec<u8>, u32)> = self.known().into_iter().collect();
+        known.sort();
+        write_len(writer, known.len())?;
+        for (key, bucket) in known {
+            writer.write_all(&[key.len() as u8])?;
+            writer.write_all(&key)?;
+            writer.write_all(&bucket.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Reads an abstraction written by `write`, failing with
+    /// `io::ErrorKind::InvalidData` if it isn't one.
+    pub fn read<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid("not an abstraction"));
+        }
+        if read_u8(reader)? != VERSION {
+            return Err(invalid("unknown version"));
+        }
+
+        let method = read_u8(reader)?;
+        let bins = read_len(reader)?;
+        let method = match method {
+            0 => Method::Strength,
+            1 if bins > 0 => Method::Distribution { bins },
+            2 => Method::Potential,
+            _ => return Err(invalid("unknown method")),
+        };
+        let mut buckets = [0; 3];
+        for buckets in buckets.iter_mut() {
+            *buckets = read_len(reader)?;
+        }
+        let config = Config {
+            method,
+            buckets,
+            training: read_len(reader)?,
+            rollouts: read_u32(reader)?,
+            iterations: read_len(reader)?,
+        };
+        let seed = read_u64(reader)?;
+
+        let mut centroids: [Vec<Vec<f64>>; 3] = Default::default();
+        let mut strengths: [Vec<f64>; 3] = Default::default();
+        for (centroids, strengths) in centroids.iter_mut().zip(strengths.iter_mut()) {
+            for _ in 0..read_len(reader)? {
+                strengths.push(read_f64(reader)?);
+                let centroid = (0..read_len(reader)?)
+                    .map(|_| read_f64(reader))
+                    .collect::<io::Result<_>>()?;
+                centroids.push(centroid);
+            }
+        }
+
+        // Every street needs buckets, described as the method describes
+        // hands, and on the river by their strength alone.
+        for street in 0..3 {
+            let len = match method {
+                Method::Distribution { bins } if street != 2 => bins,
+                Method::Potential if street != 2 => centroids[street + 1].len(),
+                _ => 1,
+            };
+            if centroids[street].is_empty()
+                || centroids[street]
+                    .iter()
+                    .any(|centroid| centroid.len() != len)
+            {
+                return Err(invalid("malformed centroids"));
+            }
+        }
+
+        let mut known = HashMap::new();
+        for _ in 0..read_len(reader)? {
+            let mut key = vec![0; usize::from(read_u8(reader)?)];
+            reader.read_exact(&mut key)?;
+            let street = key.len().wrapping_sub(5);
+            if street > 2 || key.iter().any(|&card| card >= 52) {
+                return Err(invalid("not a hand"));
+            }
+            let bucket = read_u32(reader)?;
+            if bucket as usize >= centroids[street].len() {
+                return Err(invalid("no such bucket"));
+            }
+            known.insert(key, bucket);
+        }
+
+        Ok(Abstraction::from_parts(
+            config, seed, centroids, strengths, known,
+        ))
+    }
+}
+
+fn invalid(reason: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason)
+}
+
+fn write_len<W: Write>(writer: &mut W, len: usize) -> io::Result<()> {
+    writer.write_all(&(len as u32).to_le_bytes())
+}
+
+fn read_u8<R: Read>(reader: &mut R) -> io::Result<u8> {
+    let mut bytes = [0; 1];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes[0])
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_len<R: Read>(reader: &mut R) -> io::Result<usize> {
+    read_u32(reader).map(|len| len as usize)
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_f64<R: Read>(reader: &mut R) -> io::Result<f64> {
+    read_u64(reader).map(f64::from_bits)
+}
+
+#[test]
+fn round_trip() {
+    use crate::card::face::*;
+
+    let config = Config {
+        method: Method::Distribution { bins: 4 },
+        buckets: [3, 3, 3],
+        training: 20,
+        rollouts: 6,
+        iterations: 4,
+    };
+    let path = std::env::temp_dir().join(format!("abstraction-{}.bin", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let built = Abstraction::load_or_build(&path, config, 5).unwrap();
+    let hole = cards!(King, Hearts; Ten, Hearts);
+    let board = cards!(Queen, Hearts; Jack, Clubs; Two, Hearts);
+    let bucket = built.bucket(&hole, &board);
+    built.save(&path).unwrap();
+
+    let loaded = Abstraction::load(&path).unwrap();
+    assert_eq!(loaded.config(), config);
+    assert_eq!(loaded.centroids, built.centroids);
+    assert_eq!(loaded.known(), built.known());
+    assert_eq!(loaded.bucket(&hole, &board), bucket);
+    let rebuilt = Abstraction::load_or_build(&path, config, 5).unwrap();
+    assert_eq!(rebuilt.known().len(), loaded.known().len());
+    std::fs::remove_file(&path).unwrap();
+
+    let mut bytes = Vec::new();
+    built.write(&mut bytes).unwrap();
+    bytes[0] = b'X';
+    let error = Abstraction::read(&mut &bytes[..]).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    // Centroids that don't describe hands as the method does.
+    let (mut short, mut none) = (built.centroids.clone(), built.centroids.clone());
+    short[0][1].pop();
+    none[2].clear();
+    let mut strengths = built.strengths.clone();
+    for centroids in &[short, none] {
+        strengths[2].truncate(centroids[2].len());
+        let corrupt = Abstraction::from_parts(
+            config,
+            5,
+            centroids.clone(),
+            strengths.clone(),
+            HashMap::new(),
+        );
+        let mut bytes = Vec::new();
+        corrupt.write(&mut bytes).unwrap();
+        let error = Abstraction::read(&mut &bytes[..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,307 @@
+use super::{kmeans, Abstraction, Config, Method};
+use crate::card::{Card, Suit};
+use crate::hand::{class::Class, equity};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::sync::Mutex;
+
+const FLOP: usize = 0;
+const RIVER: usize = 2;
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            method: Method::Potential,
+            buckets: [64, 64, 64],
+            training: 2000,
+            rollouts: 50,
+            iterations: 20,
+        }
+    }
+}
+
+impl Abstraction {
+    /// Builds an abstraction, clustering the river first, then the turn and
+    /// the flop. The same `config` and `seed` always build the same one.
+    pub fn build(config: Config, seed: u64) -> Self {
+        let mut abstraction = Abstraction::empty(config, seed);
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        for street in (FLOP..=RIVER).rev() {
+            let points: Vec<Vec<f64>> = (0..config.training)
+                .map(|_| {
+                    let mut deck = equity::remaining(&[]);
+                    deck.shuffle(&mut rng);
+                    let (hole, board) = deck[..street + 5].split_at(2);
+                    abstraction.features(hole, board)
+                })
+                .collect();
+
+            let distance = |a: &[f64], b: &[f64]| abstraction.distance(street, a, b);
+            let centroids = kmeans::cluster(
+                &points,
+                config.buckets[street],
+                config.iterations,
+                distance,
+                &mut rng,
+            );
+
+            let mut centroids: Vec<(f64, Vec<f64>)> = centroids
+                .into_iter()
+                .map(|centroid| (abstraction.strength(street, &centroid), centroid))
+                .collect();
+            centroids.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+            let (strengths, centroids) = centroids.into_iter().unzip();
+            abstraction.strengths[street] = strengths;
+            abstraction.centroids[street] = centroids;
+        }
+
+        abstraction
+    }
+
+    /// Constructs an abstraction from its clusters, as read from disk.
+    pub(super) fn from_parts(
+        config: Config,
+        seed: u64,
+        centroids: [Vec<Vec<f64>>; 3],
+        strengths: [Vec<f64>; 3],
+        known: HashMap<Vec<u8>, u32>,
+    ) -> Self {
+        Abstraction {
+            centroids,
+            strengths,
+            known: Mutex::new(known),
+            ..Abstraction::empty(config, seed)
+        }
+    }
+
+    fn empty(config: Config, seed: u64) -> Self {
+        Abstraction {
+            config,
+            seed,
+            centroids: Default::default(),
+            strengths: Default::default(),
+            known: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn config(&self) -> Config {
+        self.config
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the number of buckets with `board` cards out, which is the
+    /// number of starting hand classes before the flop.
+    ///
+    /// # Panics
+    /// If there can't be `board` cards on the board.
+    pub fn buckets(&self, board: usize) -> usize {
+        match board {
+            0 => crate::hand::class::COUNT,
+            3..=5 => self.centroids[board - 3].len(),
+            _ => panic!("no street has {} cards on the board", board),
+        }
+    }
+
+    /// Returns the strength of each bucket with `board` cards out, the
+    /// equity against a random hand of the hands in it.
+    pub fn strengths(&self, board: usize) -> &[f64] {
+        &self.strengths[board - 3]
+    }
+
+    /// Returns the bucket of `hole` with `board`, from 0 for the weakest
+    /// after the flop. Before it, it's the index of the starting hand class,
+    /// which isn't ordered by strength.
+    ///
+    /// # Panics
+    /// If there aren't two hole cards, or there can't be `board` cards on
+    /// the board.
+    pub fn bucket(&self, hole: &[Card], board: &[Card]) -> u32 {
+        assert_eq!(hole.len(), 2, "hole cards must be two");
+        if board.is_empty() {
+            return Class::of(hole[0], hole[1]).index() as u32;
+        }
+        assert!(
+            (3..=5).contains(&board.len()),
+            "no street has {} cards on the board",
+            board.len()
+        );
+
+        let key = canonical(hole, board);
+        if let Some(&bucket) = self.known.lock().unwrap().get(&key) {
+            return bucket;
+        }
+
+        let street = board.len() - 3;
+        let cards: Vec<Card> = key.iter().map(|&u| Card::try_from(u).unwrap()).collect();
+        let point = self.features(&cards[..2], &cards[2..]);
+        let (bucket, _) = kmeans::nearest(&self.centroids[street], &point, |a, b| {
+            self.distance(street, a, b)
+        });
+        let bucket = bucket as u32;
+
+        self.known.lock().unwrap().insert(key, bucket);
+        bucket
+    }
+
+    /// Returns the buckets worked out so far, by canonical form.
+    pub(super) fn known(&self) -> HashMap<Vec<u8>, u32> {
+        self.known.lock().unwrap().clone()
+    }
+
+    /// Describes a hand for clustering. The same hand is always described
+    /// the same, as its runouts are dealt from a seed of its own.
+    fn features(&self, hole: &[Card], board: &[Card]) -> Vec<f64> {
+        let mut rng = StdRng::seed_from_u64(self.seed ^ fnv(&canonical(hole, board)));
+        let rollouts = self.config.rollouts;
+        let street = board.len() - 3;
+
+        match self.config.method {
+            Method::Distribution { bins } if street != RIVER => {
+                let mut histogram = vec![0.0; bins];
+                let mut deck = equity::remaining(&[hole, board]);
+                let mut river = board.to_vec();
+                for _ in 0..rollouts {
+                    let (runout, _) = deck.partial_shuffle(&mut rng, 5 - board.len());
+                    river.truncate(board.len());
+                    river.extend_from_slice(runout);
+                    let strength = equity::equity(hole, &river, 1, rollouts, &mut rng);
+                    histogram[((strength * bins as f64) as usize).min(bins - 1)] += 1.0;
+                }
+                normalized(histogram)
+            }
+            Method::Potential if street != RIVER => {
+                let mut histogram = vec![0.0; self.centroids[street + 1].len()];
+                let deck = equity::remaining(&[hole, board]);
+                let mut next = board.to_vec();
+                for _ in 0..rollouts {
+                    next.truncate(board.len());
+                    next.push(*deck.choose(&mut rng).unwrap());
+                    histogram[self.bucket(hole, &next) as usize] += 1.0;
+                }
+                normalized(histogram)
+            }
+            _ => vec![equity::equity(hole, board, 1, rollouts, &mut rng)],
+        }
+    }
+
+    /// Returns how far apart two descriptions of hands on `street` are.
+    fn distance(&self, street: usize, a: &[f64], b: &[f64]) -> f64 {
+        match self.config.method {
+            Method::Distribution { bins } if street != RIVER => {
+                let positions: Vec<f64> =
+                    (0..bins).map(|i| (i as f64 + 0.5) / bins as f64).collect();
+                kmeans::emd(a, b, &positions)
+            }
+            Method::Potential if street != RIVER => kmeans::emd(a, b, &self.strengths[street + 1]),
+            _ => (a[0] - b[0]).abs(),
+        }
+    }
+
+    /// Returns the strength of a description of hands on `street`.
+    fn strength(&self, street: usize, point: &[f64]) -> f64 {
+        match self.config.method {
+            Method::Distribution { bins } if street != RIVER => point
+                .iter()
+                .enumerate()
+                .map(|(i, p)| p * (i as f64 + 0.5) / bins as f64)
+                .sum(),
+            Method::Potential if street != RIVER => point
+                .iter()
+                .zip(&self.strengths[street + 1])
+                .map(|(p, strength)| p * strength)
+                .sum(),
+            _ => point[0],
+        }
+    }
+}
+
+/// Returns the cards of a hand with its suits renamed, so that hands the
+/// same up to their suits come out the same: the hole cards then the board,
+/// each sorted, numbered as by `u8::from(card)`.
+pub(super) fn canonical(hole: &[Card], board: &[Card]) -> Vec<u8> {
+    let pattern = |suit: Suit| {
+        let mask = |cards: &[Card]| {
+            cards
+                .iter()
+                .filter(|card| card.suit == suit)
+                .fold(0u16, |mask, card| mask | 1 << card.rank as u8)
+        };
+        (mask(hole), mask(board))
+    };
+
+    let mut suits = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+    suits.sort_by_key(|&suit| std::cmp::Reverse(pattern(suit)));
+    let rename = |card: &Card| {
+        let suit = suits.iter().position(|&suit| suit == card.suit).unwrap();
+        card.rank as u8 * 4 + suit as u8
+    };
+
+    let mut hole: Vec<u8> = hole.iter().map(rename).collect();
+    let mut board: Vec<u8> = board.iter().map(rename).collect();
+    hole.sort_unstable();
+    board.sort_unstable();
+    hole.extend(board);
+    hole
+}
+
+/// Returns the FNV-1a hash of `bytes`, which unlike `std`'s won't change
+/// between releases.
+fn fnv(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100_0000_01b3)
+    })
+}
+
+fn normalized(mut histogram: Vec<f64>) -> Vec<f64> {
+    let total: f64 = histogram.iter().sum();
+    if total > 0.0 {
+        for p in histogram.iter_mut() {
+            *p /= total;
+        }
+    }
+    histogram
+}
+
+#[test]
+fn suits_dont_matter() {
+    use crate::card::face::*;
+
+    let config = Config {
+        method: Method::Potential,
+        buckets: [5, 5, 5],
+        training: 30,
+        rollouts: 8,
+        iterations: 5,
+    };
+    let abstraction = Abstraction::build(config, 7);
+
+    let hole = cards!(Queen, Hearts; Jack, Hearts);
+    let board = cards!(Ten, Hearts; Two, Clubs; Nine, Spades; Four, Diamonds);
+    let same = (
+        cards!(Jack, Diamonds; Queen, Diamonds),
+        cards!(Two, Hearts; Nine, Clubs; Ten, Diamonds; Four, Spades),
+    );
+    assert_eq!(canonical(&hole, &board), canonical(&same.0, &same.1));
+    assert_ne!(
+        canonical(&hole, &board),
+        canonical(&cards!(Queen, Hearts; Jack, Spades), &board)
+    );
+
+    for len in 3..=4 {
+        let bucket = abstraction.bucket(&hole, &board[..len]);
+        assert_eq!(bucket, abstraction.bucket(&same.0, &same.1[..len]));
+        assert!((bucket as usize) < abstraction.buckets(len));
+    }
+
+    let board = cards!(Ace, Clubs; King, Clubs; Seven, Diamonds; Two, Hearts; Three, Spades);
+    let nuts = abstraction.bucket(&cards!(Ace, Spades; Ace, Hearts), &board);
+    let trash = abstraction.bucket(&cards!(Nine, Spades; Eight, Hearts), &board);
+    assert!(nuts > trash);
+    let strengths = abstraction.strengths(5);
+    assert!(strengths.windows(2).all(|pair| pair[0] <= pair[1]));
+}
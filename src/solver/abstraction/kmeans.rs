@@ -0,0 +1,112 @@
+//! Clustering by k-means, with the distances of each `Method`.
+use rand::Rng;
+
+/// Clusters `points` into at most `k` clusters, and returns their
+/// centroids. `distance` compares two points.
+pub(super) fn cluster<R, F>(
+    points: &[Vec<f64>],
+    k: usize,
+    iterations: usize,
+    distance: F,
+    rng: &mut R,
+) -> Vec<Vec<f64>>
+where
+    R: Rng + ?Sized,
+    F: Fn(&[f64], &[f64]) -> f64,
+{
+    if points.is_empty() {
+        return Vec::new();
+    }
+
+    // k-means++: each centroid is drawn in proportion to the squared
+    // distance to the closest one drawn already.
+    let mut centroids = vec![points[rng.gen_range(0, points.len())].clone()];
+    while centroids.len() < k {
+        let weights: Vec<f64> = points
+            .iter()
+            .map(|point| nearest(&centroids, point, &distance).1.powi(2))
+            .collect();
+        let total: f64 = weights.iter().sum();
+        if total == 0.0 {
+            break;
+        }
+        let mut at = rng.gen::<f64>() * total;
+        let i = weights
+            .iter()
+            .position(|&weight| {
+                at -= weight;
+                at < 0.0
+            })
+            .unwrap_or(points.len() - 1);
+        centroids.push(points[i].clone());
+    }
+
+    for _ in 0..iterations {
+        let mut sums = vec![vec![0.0; points[0].len()]; centroids.len()];
+        let mut counts = vec![0; centroids.len()];
+        for point in points {
+            let (i, _) = nearest(&centroids, point, &distance);
+            counts[i] += 1;
+            for (sum, x) in sums[i].iter_mut().zip(point) {
+                *sum += x;
+            }
+        }
+        for ((centroid, sum), &count) in centroids.iter_mut().zip(sums).zip(&counts) {
+            // An empty cluster keeps its centroid.
+            if count > 0 {
+                *centroid = sum.iter().map(|sum| sum / f64::from(count)).collect();
+            }
+        }
+    }
+
+    centroids
+}
+
+/// Returns the index of the centroid closest to `point`, and how far it is.
+pub(super) fn nearest<F>(centroids: &[Vec<f64>], point: &[f64], distance: F) -> (usize, f64)
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+{
+    centroids
+        .iter()
+        .map(|centroid| distance(centroid, point))
+        .enumerate()
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+        .unwrap()
+}
+
+/// Returns the earth mover's distance between two histograms whose bins
+/// lie on a line at `positions`, in increasing order.
+pub(super) fn emd(a: &[f64], b: &[f64], positions: &[f64]) -> f64 {
+    let mut carried = 0.0;
+    let mut distance = 0.0;
+    for i in 0..a.len().saturating_sub(1) {
+        carried += a[i] - b[i];
+        distance += carried.abs() * (positions[i + 1] - positions[i]);
+    }
+    distance
+}
+
+#[test]
+fn clusters() {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(3);
+    let points: Vec<Vec<f64>> = (0..60)
+        .map(|i| vec![f64::from(i % 3) * 10.0 + rng.gen::<f64>()])
+        .collect();
+    let distance = |a: &[f64], b: &[f64]| (a[0] - b[0]).abs();
+    let mut centroids = cluster(&points, 3, 10, distance, &mut rng);
+    centroids.sort_by(|a, b| a[0].partial_cmp(&b[0]).unwrap());
+    for (i, centroid) in centroids.iter().enumerate() {
+        assert!(
+            (centroid[0] - i as f64 * 10.0 - 0.5).abs() < 0.2,
+            "{:?}",
+            centroids
+        );
+    }
+
+    let positions = [0.0, 1.0, 3.0];
+    assert_eq!(emd(&[1.0, 0.0, 0.0], &[0.0, 0.0, 1.0], &positions), 3.0);
+    assert_eq!(emd(&[0.5, 0.5, 0.0], &[0.5, 0.0, 0.5], &positions), 1.0);
+}
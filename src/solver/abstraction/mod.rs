@@ -0,0 +1,83 @@
+//! Card abstraction, which groups hands that play alike into buckets so
+//! that a solver can treat them as one.
+//!
+//! Before the flop every starting hand class is a bucket of its own. After
+//! it, a hand is described by a feature, and hands are clustered by k-means
+//! on a sample of them, street by street from the river back:
+//! - `Strength`: the expected hand strength, the equity against one random
+//!   hand with the board rolled out.
+//! - `Distribution`: a histogram of the hand strength on the river over the
+//!   runouts, compared by earth mover's distance.
+//! - `Potential`: a histogram of the buckets of the next street over the
+//!   cards to come, compared by earth mover's distance with the buckets
+//!   placed on a line by their strength.
+//!
+//! River hands are always bucketed by strength. After the flop, buckets are
+//! numbered from the weakest to the strongest, while before it they are
+//! numbered as the classes, from aces down the chart. Hands the same up to
+//! their suits share a bucket, and each one is worked out once and
+//! remembered, which `save` keeps on disk with the clusters.
+mod file;
+mod r#impl;
+mod kmeans;
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// How hands are described for clustering.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+    Strength,
+    /// Histograms of `bins` bins.
+    Distribution {
+        bins: usize,
+    },
+    Potential,
+}
+
+/// What an `Abstraction` is built with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Config {
+    pub method: Method,
+    /// Buckets on the flop, turn and river.
+    pub buckets: [usize; 3],
+    /// Hands dealt on each street to cluster.
+    pub training: usize,
+    /// Runouts dealt for each histogram, and hands dealt against for each
+    /// strength.
+    pub rollouts: u32,
+    /// Rounds of k-means.
+    pub iterations: usize,
+}
+
+/// Maps hole cards and a board to a bucket.
+///
+/// # Example
+/// ```
+/// # use aces_high::{*, card::face::*, solver::abstraction::*};
+/// let config = Config {
+///     method: Method::Distribution { bins: 5 },
+///     buckets: [4, 4, 4],
+///     training: 40,
+///     rollouts: 10,
+///     iterations: 5,
+/// };
+/// let abstraction = Abstraction::build(config, 1);
+///
+/// let board = cards!(Ace, Clubs; Seven, Diamonds; Two, Hearts);
+/// let aces = abstraction.bucket(&cards!(Ace, Spades; Ace, Hearts), &board);
+/// let trash = abstraction.bucket(&cards!(Nine, Spades; Four, Hearts), &board);
+/// assert!(aces < 4 && aces > trash);
+/// assert_eq!(abstraction.bucket(&cards!(Ace, Spades; King, Spades), &[]), 1);
+/// ```
+#[derive(Debug)]
+pub struct Abstraction {
+    config: Config,
+    seed: u64,
+    /// The centroid of each bucket of the flop, turn and river.
+    centroids: [Vec<Vec<f64>>; 3],
+    /// The strength of each bucket, which places it on a line.
+    strengths: [Vec<f64>; 3],
+    /// The bucket of every hand worked out so far, by canonical form.
+    known: Mutex<HashMap<Vec<u8>, u32>>,
+}
@@ -0,0 +1,123 @@
+//! Best responses, and how much a strategy loses to them.
+use super::{Game, Strategy, Turn};
+use std::collections::HashMap;
+
+/// Returns what player 0 wins on average when both players play
+/// `strategy`.
+pub fn value<G: Game>(game: &G, strategy: &Strategy<G::Key>) -> f64 {
+    expected(game, strategy, &game.root())
+}
+
+/// Returns what `player` wins on average by playing the best response to
+/// the other player playing `strategy`.
+pub fn best_response<G: Game>(game: &G, strategy: &Strategy<G::Key>, player: usize) -> f64 {
+    let mut response = Response {
+        game,
+        strategy,
+        player,
+        states: HashMap::new(),
+        best: HashMap::new(),
+    };
+    let root = game.root();
+    response.collect(&root, 1.0);
+    response.value(&root)
+}
+
+/// Returns how much a best response wins against `strategy`, averaged over
+/// both players. It's 0 for an equilibrium.
+pub fn exploitability<G: Game>(game: &G, strategy: &Strategy<G::Key>) -> f64 {
+    (best_response(game, strategy, 0) + best_response(game, strategy, 1)) / 2.0
+}
+
+fn expected<G: Game>(game: &G, strategy: &Strategy<G::Key>, state: &G::State) -> f64 {
+    let actions = game.actions(state);
+    let weights = match game.turn(state) {
+        Turn::Terminal => return game.utility(state, 0),
+        Turn::Chance => game.chances(state),
+        Turn::Player(_) => strategy.probabilities(&game.key(state), actions.len()),
+    };
+    actions
+        .iter()
+        .zip(weights)
+        .filter(|&(_, p)| p > 0.0)
+        .map(|(action, p)| p * expected(game, strategy, &game.play(state, action)))
+        .sum()
+}
+
+/// Finds the best response of `player` one information set at a time:
+/// the action that wins most over every state of the set, weighted by the
+/// chance of the other player and the cards getting there.
+struct Response<'a, G: Game> {
+    game: &'a G,
+    strategy: &'a Strategy<G::Key>,
+    player: usize,
+    /// The states of each information set of `player`, with their weights.
+    states: HashMap<G::Key, Vec<(G::State, f64)>>,
+    /// The action picked in each information set decided so far.
+    best: HashMap<G::Key, usize>,
+}
+
+impl<'a, G: Game> Response<'a, G> {
+    fn collect(&mut self, state: &G::State, weight: f64) {
+        let actions = self.game.actions(state);
+        let weights = match self.game.turn(state) {
+            Turn::Terminal => return,
+            Turn::Chance => self.game.chances(state),
+            Turn::Player(player) if player == self.player => {
+                let key = self.game.key(state);
+                let states = self.states.entry(key).or_default();
+                states.push((state.clone(), weight));
+                vec![1.0; actions.len()]
+            }
+            Turn::Player(_) => self
+                .strategy
+                .probabilities(&self.game.key(state), actions.len()),
+        };
+        for (action, p) in actions.iter().zip(weights) {
+            self.collect(&self.game.play(state, action), weight * p);
+        }
+    }
+
+    fn value(&mut self, state: &G::State) -> f64 {
+        let actions = self.game.actions(state);
+        let weights = match self.game.turn(state) {
+            Turn::Terminal => return self.game.utility(state, self.player),
+            Turn::Chance => self.game.chances(state),
+            Turn::Player(player) if player == self.player => {
+                let best = self.best(self.game.key(state), actions.len());
+                return self.value(&self.game.play(state, &actions[best]));
+            }
+            Turn::Player(_) => self
+                .strategy
+                .probabilities(&self.game.key(state), actions.len()),
+        };
+        let mut value = 0.0;
+        for (action, p) in actions.iter().zip(weights) {
+            if p > 0.0 {
+                value += p * self.value(&self.game.play(state, action));
+            }
+        }
+        value
+    }
+
+    fn best(&mut self, key: G::Key, actions: usize) -> usize {
+        if let Some(&best) = self.best.get(&key) {
+            return best;
+        }
+
+        let states = self.states[&key].clone();
+        let mut values = vec![0.0; actions];
+        for (state, weight) in &states {
+            let choices = self.game.actions(state);
+            for (value, action) in values.iter_mut().zip(&choices) {
+                *value += weight * self.value(&self.game.play(state, action));
+            }
+        }
+
+        let best = (0..actions)
+            .max_by(|&a, &b| values[a].partial_cmp(&values[b]).unwrap())
+            .unwrap();
+        self.best.insert(key, best);
+        best
+    }
+}
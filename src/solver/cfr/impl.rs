@@ -0,0 +1,256 @@
+use super::{Game, Node, Solver, Strategy, Turn, Variant};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+impl<G: Game> Solver<G> {
+    pub fn new(game: G, variant: Variant) -> Self {
+        Solver::with_rng(game, variant, StdRng::from_entropy())
+    }
+
+    /// Constructs a `Solver` which samples the same way every time it's
+    /// constructed with the same `seed`.
+    pub fn new_seeded(game: G, variant: Variant, seed: u64) -> Self {
+        Solver::with_rng(game, variant, StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(game: G, variant: Variant, rng: StdRng) -> Self {
+        Solver {
+            game,
+            variant,
+            nodes: HashMap::new(),
+            iterations: 0,
+            passes: 0,
+            rng,
+        }
+    }
+
+    pub fn game(&self) -> &G {
+        &self.game
+    }
+
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    /// Returns the number of iterations run.
+    pub fn iterations(&self) -> u64 {
+        self.iterations
+    }
+
+    /// Runs `iterations` more iterations.
+    pub fn iterate(&mut self, iterations: u64) {
+        let root = self.game.root();
+        for _ in 0..iterations {
+            self.iterations += 1;
+            match self.variant {
+                Variant::Vanilla => {
+                    self.passes += 1;
+                    self.cfr(&root, None, [1.0, 1.0], 1.0);
+                }
+                Variant::Plus => {
+                    for player in 0..2 {
+                        self.passes += 1;
+                        self.cfr(&root, Some(player), [1.0, 1.0], 1.0);
+                    }
+                }
+                Variant::External => {
+                    for player in 0..2 {
+                        self.passes += 1;
+                        self.sample(&root, player);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the average strategy, which is what converges.
+    pub fn strategy(&self) -> Strategy<G::Key> {
+        let probabilities = self
+            .nodes
+            .iter()
+            .map(|(key, node)| (key.clone(), normalized(&node.total)))
+            .collect();
+        Strategy { probabilities }
+    }
+
+    /// Returns the strategy regret matching plays next in an information
+    /// set, if it has been visited.
+    pub fn current(&self, key: &G::Key) -> Option<Vec<f64>> {
+        self.nodes.get(key).map(|node| matched(&node.regrets))
+    }
+
+    /// Walks the whole tree, updating the regrets of `traverser`, or of both
+    /// players if `None`, and returns the utilities of both players.
+    /// `reach` is the probability of each player playing to `state`, and
+    /// `chance` that of the cards dealt.
+    fn cfr(
+        &mut self,
+        state: &G::State,
+        traverser: Option<usize>,
+        reach: [f64; 2],
+        chance: f64,
+    ) -> [f64; 2] {
+        match self.game.turn(state) {
+            Turn::Terminal => [self.game.utility(state, 0), self.game.utility(state, 1)],
+            Turn::Chance => {
+                let actions = self.game.actions(state);
+                let chances = self.game.chances(state);
+                let mut utilities = [0.0; 2];
+                for (action, p) in actions.iter().zip(chances) {
+                    let child = self.game.play(state, action);
+                    let child = self.cfr(&child, traverser, reach, chance * p);
+                    utilities[0] += p * child[0];
+                    utilities[1] += p * child[1];
+                }
+                utilities
+            }
+            Turn::Player(player) => {
+                let actions = self.game.actions(state);
+                let key = self.game.key(state);
+                let strategy = self.playing(&key, actions.len());
+
+                let mut values = Vec::with_capacity(actions.len());
+                let mut utilities = [0.0; 2];
+                for (action, &p) in actions.iter().zip(&strategy) {
+                    let mut reach = reach;
+                    reach[player] *= p;
+                    let child = self.game.play(state, action);
+                    let child = self.cfr(&child, traverser, reach, chance);
+                    utilities[0] += p * child[0];
+                    utilities[1] += p * child[1];
+                    values.push(child[player]);
+                }
+
+                if traverser.map_or(true, |traverser| traverser == player) {
+                    let plus = self.variant == Variant::Plus;
+                    let weight = if plus { self.iterations as f64 } else { 1.0 };
+                    let counterfactual = reach[1 - player] * chance;
+                    let node = self.nodes.get_mut(&key).unwrap();
+                    for (i, value) in values.iter().enumerate() {
+                        node.regrets[i] += counterfactual * (value - utilities[player]);
+                        if plus {
+                            node.regrets[i] = node.regrets[i].max(0.0);
+                        }
+                        node.total[i] += weight * reach[player] * strategy[i];
+                    }
+                }
+                utilities
+            }
+        }
+    }
+
+    /// Walks the tree sampling chance and the other player, updating the
+    /// regrets of `traverser`, and returns what they win.
+    fn sample(&mut self, state: &G::State, traverser: usize) -> f64 {
+        match self.game.turn(state) {
+            Turn::Terminal => self.game.utility(state, traverser),
+            Turn::Chance => {
+                let actions = self.game.actions(state);
+                let i = pick(&self.game.chances(state), &mut self.rng);
+                let child = self.game.play(state, &actions[i]);
+                self.sample(&child, traverser)
+            }
+            Turn::Player(player) => {
+                let actions = self.game.actions(state);
+                let key = self.game.key(state);
+                let strategy = self.playing(&key, actions.len());
+
+                if player != traverser {
+                    let node = self.nodes.get_mut(&key).unwrap();
+                    for (total, p) in node.total.iter_mut().zip(&strategy) {
+                        *total += p;
+                    }
+                    let i = pick(&strategy, &mut self.rng);
+                    let child = self.game.play(state, &actions[i]);
+                    return self.sample(&child, traverser);
+                }
+
+                let values: Vec<f64> = actions
+                    .iter()
+                    .map(|action| {
+                        let child = self.game.play(state, action);
+                        self.sample(&child, traverser)
+                    })
+                    .collect();
+                let value: f64 = values.iter().zip(&strategy).map(|(v, p)| v * p).sum();
+
+                let node = self.nodes.get_mut(&key).unwrap();
+                for (regret, v) in node.regrets.iter_mut().zip(&values) {
+                    *regret += v - value;
+                }
+                value
+            }
+        }
+    }
+
+    /// Returns the strategy played in an information set during this pass.
+    fn playing(&mut self, key: &G::Key, actions: usize) -> Vec<f64> {
+        let node = self.nodes.entry(key.clone()).or_insert_with(|| Node {
+            regrets: vec![0.0; actions],
+            total: vec![0.0; actions],
+            current: Vec::new(),
+            pass: 0,
+        });
+        if node.pass != self.passes {
+            node.current = matched(&node.regrets);
+            node.pass = self.passes;
+        }
+        node.current.clone()
+    }
+}
+
+impl<K: Eq + Hash> Strategy<K> {
+    /// Returns the probability of each action in an information set, or
+    /// `None` if the strategy doesn't know it.
+    pub fn get(&self, key: &K) -> Option<&[f64]> {
+        self.probabilities.get(key).map(Vec::as_slice)
+    }
+
+    /// Returns the probability of each of `actions` actions, playing them
+    /// all equally often where the strategy doesn't know the information
+    /// set.
+    pub fn probabilities(&self, key: &K, actions: usize) -> Vec<f64> {
+        match self.get(key) {
+            Some(probabilities) => probabilities.to_vec(),
+            None => vec![1.0 / actions as f64; actions],
+        }
+    }
+
+    /// Returns the number of information sets known.
+    pub fn len(&self) -> usize {
+        self.probabilities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.probabilities.is_empty()
+    }
+}
+
+/// Plays in proportion to the positive regrets, or uniformly if there are
+/// none.
+fn matched(regrets: &[f64]) -> Vec<f64> {
+    let positive: Vec<f64> = regrets.iter().map(|regret| regret.max(0.0)).collect();
+    normalized(&positive)
+}
+
+fn normalized(weights: &[f64]) -> Vec<f64> {
+    let sum: f64 = weights.iter().sum();
+    if sum > 0.0 {
+        weights.iter().map(|weight| weight / sum).collect()
+    } else {
+        vec![1.0 / weights.len() as f64; weights.len()]
+    }
+}
+
+/// Picks an index with the probabilities of `weights`.
+fn pick<R: Rng + ?Sized>(weights: &[f64], rng: &mut R) -> usize {
+    let mut at = rng.gen::<f64>() * weights.iter().sum::<f64>();
+    for (i, weight) in weights.iter().enumerate() {
+        if at < *weight {
+            return i;
+        }
+        at -= weight;
+    }
+    weights.len() - 1
+}
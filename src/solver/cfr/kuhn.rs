@@ -0,0 +1,171 @@
+//! Kuhn poker, the smallest poker worth solving.
+//!
+//! Each player antes a chip and is dealt one of a jack, a queen and a king.
+//! Player 0 checks or bets a chip, then player 1 checks, bets, calls or
+//! folds, and if player 0 checked into a bet they call or fold. The higher
+//! card wins at showdown. Player 0 loses 1/18 of a chip a hand at
+//! equilibrium.
+use super::{Game, Turn};
+use crate::card::{Card, Rank, Suit};
+use crate::deck::Deck;
+use crate::hand::value::strength;
+
+/// The game of Kuhn poker.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Kuhn;
+
+/// A move in Kuhn poker.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// A card dealt to the next player.
+    Deal(Card),
+    /// Checks or folds.
+    Pass,
+    /// Bets or calls.
+    Bet,
+}
+
+/// A hand of Kuhn poker being played.
+#[derive(Clone, Debug)]
+pub struct State {
+    deck: Deck,
+    /// The cards of player 0 and 1, once dealt.
+    cards: Vec<Card>,
+    history: Vec<Action>,
+}
+
+impl State {
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// Returns the actions of the players so far.
+    pub fn history(&self) -> &[Action] {
+        &self.history
+    }
+}
+
+impl Game for Kuhn {
+    type State = State;
+    type Action = Action;
+    /// The card of the player to act followed by the actions so far, such as
+    /// `Kpb`.
+    type Key = String;
+
+    fn root(&self) -> State {
+        let cards = [Rank::Jack, Rank::Queen, Rank::King];
+        let cards: Vec<Card> = cards
+            .iter()
+            .map(|&rank| Card::new(rank, Suit::Spades))
+            .collect();
+        State {
+            deck: Deck::new_custom(&cards),
+            cards: Vec::with_capacity(2),
+            history: Vec::with_capacity(3),
+        }
+    }
+
+    fn turn(&self, state: &State) -> Turn {
+        use Action::*;
+        match state.history[..] {
+            _ if state.cards.len() < 2 => Turn::Chance,
+            [Pass, Pass] | [Bet, _] | [Pass, Bet, _] => Turn::Terminal,
+            _ => Turn::Player(state.history.len() % 2),
+        }
+    }
+
+    fn actions(&self, state: &State) -> Vec<Action> {
+        match self.turn(state) {
+            Turn::Chance => {
+                let cards = state.deck.peek(state.deck.len()).unwrap();
+                cards.into_iter().map(Action::Deal).collect()
+            }
+            _ => vec![Action::Pass, Action::Bet],
+        }
+    }
+
+    fn chances(&self, state: &State) -> Vec<f64> {
+        vec![1.0 / state.deck.len() as f64; state.deck.len()]
+    }
+
+    fn play(&self, state: &State, action: &Action) -> State {
+        let mut state = state.clone();
+        match *action {
+            Action::Deal(card) => {
+                state.deck.remove(&[card]).unwrap();
+                state.cards.push(card);
+            }
+            action => state.history.push(action),
+        }
+        state
+    }
+
+    fn utility(&self, state: &State, player: usize) -> f64 {
+        use Action::*;
+        let showdown = |stake: f64| {
+            let wins = strength(state.cards[player].rank) > strength(state.cards[1 - player].rank);
+            if wins {
+                stake
+            } else {
+                -stake
+            }
+        };
+        match state.history[..] {
+            [Pass, Pass] => showdown(1.0),
+            [Bet, Bet] | [Pass, Bet, Bet] => showdown(2.0),
+            // Whoever passed last folded.
+            [Bet, Pass] => [1.0, -1.0][player],
+            [Pass, Bet, Pass] => [-1.0, 1.0][player],
+            _ => panic!("the hand isn't over"),
+        }
+    }
+
+    fn key(&self, state: &State) -> String {
+        let player = state.history.len() % 2;
+        let mut key = char::from(state.cards[player].rank).to_string();
+        for action in &state.history {
+            key.push(if *action == Action::Bet { 'b' } else { 'p' });
+        }
+        key
+    }
+}
+
+#[test]
+fn equilibrium() {
+    use super::{exploitability, value, Solver, Variant};
+
+    for &(variant, iterations, tolerance) in &[
+        (Variant::Vanilla, 5_000, 0.005),
+        (Variant::Plus, 1_000, 0.002),
+        (Variant::External, 20_000, 0.02),
+    ] {
+        let mut solver = Solver::new_seeded(Kuhn, variant, 5);
+        solver.iterate(iterations);
+        let strategy = solver.strategy();
+        assert_eq!(strategy.len(), 12);
+
+        let value = value(&Kuhn, &strategy);
+        assert!(
+            (value + 1.0 / 18.0).abs() < tolerance,
+            "{:?} {}",
+            variant,
+            value
+        );
+        let exploitability = exploitability(&Kuhn, &strategy);
+        assert!(
+            exploitability < tolerance,
+            "{:?} {}",
+            variant,
+            exploitability
+        );
+
+        // Player 1 always calls with a king and folds a jack.
+        assert!(strategy.get(&"Kb".to_string()).unwrap()[1] > 0.99);
+        assert!(strategy.get(&"Jb".to_string()).unwrap()[0] > 0.99);
+    }
+
+    // Playing at random is easy to exploit.
+    let mut solver = Solver::new_seeded(Kuhn, Variant::Vanilla, 5);
+    solver.iterate(1);
+    assert!(exploitability(&Kuhn, &solver.strategy()) > 0.1);
+}
@@ -0,0 +1,116 @@
+//! Counterfactual regret minimization, for two player zero-sum games of
+//! imperfect information.
+//!
+//! A game is described by implementing `Game`. The `Solver` plays it
+//! against itself, and the average of the strategies it plays converges to
+//! a Nash equilibrium. How far a strategy is from one is measured by its
+//! `exploitability`.
+pub mod best;
+mod r#impl;
+pub mod kuhn;
+pub mod leduc;
+
+pub use best::{best_response, exploitability, value};
+
+use rand::rngs::StdRng;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Who acts in a state of a game.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Turn {
+    /// Cards are dealt, at random.
+    Chance,
+    /// Player 0 or 1 picks an action.
+    Player(usize),
+    /// The game is over.
+    Terminal,
+}
+
+/// A two player zero-sum game in extensive form.
+///
+/// Players must have perfect recall: nothing they knew or did may be
+/// forgotten by the `key` of a later state.
+pub trait Game {
+    type State: Clone;
+    type Action: Clone;
+    /// Identifies an information set, the states a player can't tell apart.
+    type Key: Clone + Eq + Hash;
+
+    fn root(&self) -> Self::State;
+
+    fn turn(&self, state: &Self::State) -> Turn;
+
+    /// Returns the actions of the player to act, or the outcomes of a chance
+    /// state. There must be at least one.
+    fn actions(&self, state: &Self::State) -> Vec<Self::Action>;
+
+    /// Returns the probability of each outcome of a chance state, in the
+    /// order of `actions`.
+    fn chances(&self, state: &Self::State) -> Vec<f64>;
+
+    fn play(&self, state: &Self::State, action: &Self::Action) -> Self::State;
+
+    /// Returns what `player` wins in a terminal state. The players' wins
+    /// add up to 0.
+    fn utility(&self, state: &Self::State, player: usize) -> f64;
+
+    /// Returns what the player to act knows.
+    fn key(&self, state: &Self::State) -> Self::Key;
+}
+
+/// The flavours of CFR.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Variant {
+    /// Updates both players over the whole tree every iteration.
+    Vanilla,
+    /// CFR+: regrets are kept from going negative, the players are updated
+    /// in turn, and later iterations weigh more in the average.
+    Plus,
+    /// Monte Carlo CFR with external sampling: the chance outcomes and the
+    /// actions of the other player are sampled, which makes an iteration
+    /// much cheaper but noisier.
+    External,
+}
+
+/// How often each action is played in each information set, in the order
+/// the game lists them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Strategy<K: Eq + Hash> {
+    probabilities: HashMap<K, Vec<f64>>,
+}
+
+/// Finds an equilibrium of a `Game` by playing it against itself.
+///
+/// # Example
+/// ```
+/// # use aces_high::solver::cfr::{*, kuhn::Kuhn};
+/// let mut solver = Solver::new(Kuhn, Variant::Plus);
+/// solver.iterate(500);
+///
+/// let strategy = solver.strategy();
+/// assert!((value(&Kuhn, &strategy) + 1.0 / 18.0).abs() < 0.01);
+/// assert!(exploitability(&Kuhn, &strategy) < 0.01);
+/// ```
+pub struct Solver<G: Game> {
+    game: G,
+    variant: Variant,
+    nodes: HashMap<G::Key, Node>,
+    iterations: u64,
+    /// Walks of the tree so far.
+    passes: u64,
+    rng: StdRng,
+}
+
+/// What the solver keeps for an information set.
+#[derive(Clone, Debug, PartialEq)]
+struct Node {
+    /// Regret of not having played each action.
+    regrets: Vec<f64>,
+    /// Weighted sum of the strategies played.
+    total: Vec<f64>,
+    /// The strategy played during the pass `pass`, which mustn't change as
+    /// the regrets are updated during it.
+    current: Vec<f64>,
+    pass: u64,
+}
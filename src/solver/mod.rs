@@ -0,0 +1,4 @@
+//! Solvers for game theory optimal play.
+pub mod abstraction;
+pub mod cfr;
+pub mod pushfold;
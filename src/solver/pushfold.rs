@@ -0,0 +1,525 @@
+//! Push/fold equilibria, for short stacks that either go all in or fold.
+//!
+//! The players act in turn, and each acts once: until someone goes all in
+//! the choice is to jam or fold, after that it's to call all in or fold.
+//! Every player picks a range for each way the action can come to them, by
+//! class of starting hand. The solver plays fictitious play: it computes a
+//! best response of every range to the average of the others, and averages
+//! them in, until the ranges settle.
+//!
+//! Showdowns between two hands use the equities of `Matchups`, usually the
+//! exact ones of `hand::preflop`, and those between more hands are dealt out
+//! by Monte Carlo. Apart from the hand each player holds, card removal
+//! between the players is not accounted for.
+use crate::card::Card;
+use crate::hand::class::{Class, Range, COUNT};
+use crate::hand::{equity::remaining, preflop, Value};
+use crate::table::pot::Pots;
+use crate::tournament::icm;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use std::collections::HashMap;
+
+/// Heads up preflop all-in equities between every two classes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Matchups {
+    /// The equity of the first class, by index of the first then second.
+    equity: Vec<f64>,
+    /// The number of ways to deal both classes without sharing a card.
+    deals: Vec<u32>,
+}
+
+/// What the players play for.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Payoff {
+    /// Chips won or lost.
+    Chips,
+    /// Prize money by ICM, with the prizes of each place, first place first.
+    Icm(Vec<u64>),
+}
+
+/// A hand where every player either goes all in or folds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spot {
+    /// Chips of each player before posting, in the order they act. The last
+    /// two post the small and big blind, which heads up means the small
+    /// blind acts first.
+    pub stacks: Vec<u32>,
+    pub small_blind: u32,
+    pub big_blind: u32,
+    /// Posted by every player.
+    pub ante: u32,
+    pub payoff: Payoff,
+    /// Chips of the players not in the hand, for ICM.
+    pub others: Vec<u32>,
+}
+
+/// Solves a `Spot` for jam and call ranges.
+///
+/// # Example
+/// ```
+/// # use aces_high::{hand::class::Class, solver::pushfold::*};
+/// let matchups = Matchups::exact();
+/// let spot = Spot {
+///     stacks: vec![1_000, 1_000],
+///     small_blind: 50,
+///     big_blind: 100,
+///     ante: 0,
+///     payoff: Payoff::Chips,
+///     others: Vec::new(),
+/// };
+///
+/// let mut solver = Solver::new(spot, &matchups);
+/// solver.solve(50);
+/// let jam = solver.range(0, &[]);
+/// let call = solver.range(1, &[0]);
+/// assert!(jam.contains("AA".parse().unwrap()) && jam.contains("K2s".parse().unwrap()));
+/// assert!(call.contains("A2o".parse().unwrap()) && !call.contains("72o".parse().unwrap()));
+/// assert!(jam.combos() > call.combos());
+/// assert!(jam.to_string().starts_with("AA  AKs AQs AJs ATs A9s A8s"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Solver<'a> {
+    spot: Spot,
+    matchups: &'a Matchups,
+    /// Hands dealt out for each showdown of more than two hands.
+    pub samples: u32,
+    /// Chips each player posts.
+    posted: Vec<u32>,
+    /// The number of ways to deal each class.
+    counts: Vec<f64>,
+    /// The average range of each player, for every set of players before
+    /// them that went all in, by `node`.
+    ranges: Vec<Range>,
+    iterations: u32,
+    rng: StdRng,
+    /// What each `outcome` is worth, as it's asked for over and over.
+    outcomes: HashMap<(usize, usize, usize), f64>,
+}
+
+impl Matchups {
+    /// Constructs the exact equities, from those embedded in the crate.
+    pub fn exact() -> Self {
+        let mut equity = vec![0.5; COUNT * COUNT];
+        let mut deals = vec![0; COUNT * COUNT];
+        for a in Class::all() {
+            for b in Class::all() {
+                equity[a.index() * COUNT + b.index()] = preflop::equity(a, b);
+                deals[a.index() * COUNT + b.index()] = preflop::deals(a, b);
+            }
+        }
+        Matchups { equity, deals }
+    }
+
+    /// Estimates the equities by dealing `samples` boards to every two
+    /// classes.
+    pub fn estimate<R: Rng + ?Sized>(samples: u32, rng: &mut R) -> Self {
+        let combos: Vec<Vec<[Card; 2]>> = Class::all().map(Class::combos).collect();
+        let mut equity = vec![0.5; COUNT * COUNT];
+        let mut deals = vec![0; COUNT * COUNT];
+        let overlap = |a: &[Card; 2], b: &[Card; 2]| a.iter().any(|card| b.contains(card));
+
+        for a in 0..COUNT {
+            for b in 0..COUNT {
+                deals[a * COUNT + b] = combos[a]
+                    .iter()
+                    .map(|x| combos[b].iter().filter(|y| !overlap(x, y)).count() as u32)
+                    .sum();
+            }
+
+            for b in a + 1..COUNT {
+                let mut won = 0.0;
+                for _ in 0..samples {
+                    let x = *combos[a].choose(rng).unwrap();
+                    let y = loop {
+                        let y = combos[b].choose(rng).unwrap();
+                        if !overlap(&x, y) {
+                            break *y;
+                        }
+                    };
+                    let mut deck = remaining(&[&x, &y]);
+                    let (board, _) = deck.partial_shuffle(rng, 5);
+                    let value = |hole: &[Card; 2]| {
+                        let mut cards = board.to_vec();
+                        cards.extend_from_slice(hole);
+                        Value::new(&cards)
+                    };
+                    won += match value(&x).cmp(&value(&y)) {
+                        std::cmp::Ordering::Greater => 1.0,
+                        std::cmp::Ordering::Equal => 0.5,
+                        std::cmp::Ordering::Less => 0.0,
+                    };
+                }
+                let share = won / f64::from(samples.max(1));
+                equity[a * COUNT + b] = share;
+                equity[b * COUNT + a] = 1.0 - share;
+            }
+        }
+
+        Matchups { equity, deals }
+    }
+
+    /// Returns the share of the pot `a` wins against `b`, ties split.
+    pub fn equity(&self, a: Class, b: Class) -> f64 {
+        self.equity[a.index() * COUNT + b.index()]
+    }
+
+    /// Returns the number of ways `a` and `b` can be dealt together.
+    pub fn deals(&self, a: Class, b: Class) -> u32 {
+        self.deals[a.index() * COUNT + b.index()]
+    }
+}
+
+impl<'a> Solver<'a> {
+    /// Constructs a solver with every range starting out full.
+    ///
+    /// # Panics
+    /// Panics unless there are two to twelve players, each with more chips
+    /// than they post.
+    pub fn new(spot: Spot, matchups: &'a Matchups) -> Self {
+        let players = spot.stacks.len();
+        assert!((2..=12).contains(&players), "two to twelve players");
+
+        let posted: Vec<u32> = (0..players)
+            .map(|i| {
+                let blind = match players - i {
+                    2 => spot.small_blind,
+                    1 => spot.big_blind,
+                    _ => 0,
+                };
+                spot.ante + blind
+            })
+            .collect();
+        assert!(
+            (0..players).all(|i| spot.stacks[i] > posted[i]),
+            "every player needs chips behind after posting"
+        );
+
+        Solver {
+            spot,
+            matchups,
+            samples: 200,
+            posted,
+            counts: Class::all().map(|class| class.count() as f64).collect(),
+            ranges: vec![Range::full(); (1 << players) - 1],
+            iterations: 0,
+            rng: StdRng::seed_from_u64(0),
+            outcomes: HashMap::new(),
+        }
+    }
+
+    pub fn spot(&self) -> &Spot {
+        &self.spot
+    }
+
+    /// Returns the range `player` plays when the players `all_in` before
+    /// them went all in, and the rest folded. The range of the big blind
+    /// when everyone folds is full, as it wins the blinds without acting.
+    pub fn range(&self, player: usize, all_in: &[usize]) -> &Range {
+        let mask = all_in.iter().fold(0, |mask, &i| {
+            assert!(i < player, "only players before act before");
+            mask | 1 << i
+        });
+        &self.ranges[node(player, mask)]
+    }
+
+    /// Runs `iterations` more rounds of best responses.
+    pub fn solve(&mut self, iterations: u32) {
+        let players = self.spot.stacks.len();
+        for _ in 0..iterations {
+            let mut responses = Vec::with_capacity(self.ranges.len());
+            for player in 0..players {
+                for mask in 0..1 << player {
+                    responses.push(self.best_response(player, mask));
+                }
+            }
+
+            self.iterations += 1;
+            let step = 1.0 / f64::from(self.iterations + 1);
+            for (range, response) in self.ranges.iter_mut().zip(responses) {
+                if let Some(response) = response {
+                    for class in Class::all() {
+                        let weight = range.weight(class);
+                        range.set(class, weight + (response.weight(class) - weight) * step);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns what going all in and folding are worth to `player` holding
+    /// `class`, with the action as `all_in` describes.
+    pub fn ev(&mut self, player: usize, all_in: &[usize], class: Class) -> (f64, f64) {
+        let mask = all_in.iter().fold(0, |mask, &i| mask | 1 << i);
+        (
+            self.walk(player, player + 1, mask | 1 << player, Some(class), 1.0),
+            self.fold_ev(player, mask),
+        )
+    }
+
+    /// Returns the best response of `player` at `mask`, or `None` if they
+    /// don't act there.
+    fn best_response(&mut self, player: usize, mask: usize) -> Option<Range> {
+        let players = self.spot.stacks.len();
+        if player == players - 1 && mask == 0 {
+            return None;
+        }
+
+        let fold = self.fold_ev(player, mask);
+        let mut response = Range::default();
+        for class in Class::all() {
+            let jam = self.walk(player, player + 1, mask | 1 << player, Some(class), 1.0);
+            if jam > fold {
+                response.set(class, 1.0);
+            }
+        }
+        Some(response)
+    }
+
+    fn fold_ev(&mut self, player: usize, mask: usize) -> f64 {
+        match self.spot.payoff {
+            Payoff::Chips => f64::from(self.spot.stacks[player] - self.posted[player]),
+            Payoff::Icm(_) => self.walk(player, player + 1, mask, None, 1.0),
+        }
+    }
+
+    /// Returns what the rest of the hand is worth to `hero`, from `next` on
+    /// with the players of `mask` all in, weighted by `p`. `class` is the
+    /// class of the hero, if they are in.
+    fn walk(&mut self, hero: usize, next: usize, mask: usize, class: Option<Class>, p: f64) -> f64 {
+        if p == 0.0 {
+            return 0.0;
+        }
+        let players = self.spot.stacks.len();
+        if next == players {
+            return p * self.showdown(hero, mask, class);
+        }
+
+        let prior = mask & ((1 << next) - 1);
+        let jam = if next == players - 1 && prior == 0 {
+            1.0
+        } else {
+            self.chance(&self.ranges[node(next, prior)], class)
+        };
+        self.walk(hero, next + 1, mask | 1 << next, class, p * jam)
+            + self.walk(hero, next + 1, mask, class, p * (1.0 - jam))
+    }
+
+    /// Returns the probability that a player with `range` plays, given that
+    /// the hero holds `class`.
+    fn chance(&self, range: &Range, class: Option<Class>) -> f64 {
+        let weights = range.weights();
+        let (mut played, mut dealt) = (0.0, 0.0);
+        for (other, weight) in weights.iter().enumerate() {
+            let deals = match class {
+                Some(class) => f64::from(self.matchups.deals[class.index() * COUNT + other]),
+                None => self.counts[other],
+            };
+            played += weight * deals;
+            dealt += deals;
+        }
+        played / dealt
+    }
+
+    /// Returns what the hand is worth to `hero` once the players of `mask`
+    /// are all in and the rest folded.
+    fn showdown(&mut self, hero: usize, mask: usize, class: Option<Class>) -> f64 {
+        let players = self.spot.stacks.len();
+        let all_in: Vec<usize> = (0..players).filter(|&i| mask & 1 << i != 0).collect();
+        let range = |i: usize| &self.ranges[node(i, mask & ((1 << i) - 1))];
+
+        match all_in[..] {
+            [winner] => self.outcome(hero, mask, winner),
+            [a, b] => {
+                // The weight of each class, which is the hero's own if in.
+                let weights = |i: usize| match class {
+                    Some(class) if i == hero => {
+                        let mut weights = vec![0.0; COUNT];
+                        weights[class.index()] = 1.0;
+                        weights
+                    }
+                    _ => range(i).weights().to_vec(),
+                };
+                let (wa, wb) = (weights(a), weights(b));
+
+                let (mut won, mut total) = (0.0, 0.0);
+                for x in (0..COUNT).filter(|&x| wa[x] > 0.0) {
+                    for y in (0..COUNT).filter(|&y| wb[y] > 0.0) {
+                        let weight = wa[x] * wb[y] * f64::from(self.matchups.deals[x * COUNT + y]);
+                        won += weight * self.matchups.equity[x * COUNT + y];
+                        total += weight;
+                    }
+                }
+                if total == 0.0 {
+                    return 0.0;
+                }
+                let share = won / total;
+                share * self.outcome(hero, mask, a) + (1.0 - share) * self.outcome(hero, mask, b)
+            }
+            _ => self.deal_out(hero, &all_in, mask, class),
+        }
+    }
+
+    /// Deals out hands and boards to the players `all_in` by Monte Carlo.
+    fn deal_out(
+        &mut self,
+        hero: usize,
+        all_in: &[usize],
+        mask: usize,
+        class: Option<Class>,
+    ) -> f64 {
+        let picks: Vec<Vec<(f64, Class)>> = all_in
+            .iter()
+            .map(|&i| match class {
+                Some(class) if i == hero => vec![(1.0, class)],
+                _ => {
+                    let range = &self.ranges[node(i, mask & ((1 << i) - 1))];
+                    let mut sum = 0.0;
+                    Class::all()
+                        .filter(|&other| range.weight(other) > 0.0)
+                        .map(|other| {
+                            sum += range.weight(other) * other.count() as f64;
+                            (sum, other)
+                        })
+                        .collect()
+                }
+            })
+            .collect();
+        if picks.iter().any(Vec::is_empty) {
+            return 0.0;
+        }
+
+        let (mut total, mut dealt) = (0.0, 0);
+        let mut values = vec![None; self.spot.stacks.len()];
+        'deal: for _ in 0..self.samples {
+            let mut holes: Vec<[Card; 2]> = Vec::with_capacity(all_in.len());
+            for pick in &picks {
+                let hole = (0..100).find_map(|_| {
+                    let at = self.rng.gen::<f64>() * pick.last().unwrap().0;
+                    let i = pick.iter().position(|&(sum, _)| at < sum).unwrap_or(0);
+                    let hole = *pick[i].1.combos().choose(&mut self.rng).unwrap();
+                    let free = holes
+                        .iter()
+                        .all(|other| !other.iter().any(|c| hole.contains(c)));
+                    Some(hole).filter(|_| free)
+                });
+                match hole {
+                    Some(hole) => holes.push(hole),
+                    None => continue 'deal,
+                }
+            }
+
+            let known: Vec<&[Card]> = holes.iter().map(|hole| &hole[..]).collect();
+            let mut deck = remaining(&known);
+            let (board, _) = deck.partial_shuffle(&mut self.rng, 5);
+            for (&i, hole) in all_in.iter().zip(&holes) {
+                let mut cards = board.to_vec();
+                cards.extend_from_slice(hole);
+                values[i] = Some(Value::new(&cards));
+            }
+
+            let stacks = self.award(mask, &values);
+            total += self.payoff(hero, &stacks);
+            dealt += 1;
+        }
+
+        if dealt == 0 {
+            return 0.0;
+        }
+        total / f64::from(dealt)
+    }
+
+    /// Returns what the hand is worth to `hero` after the players of `mask`
+    /// went all in and `winner` won.
+    fn outcome(&mut self, hero: usize, mask: usize, winner: usize) -> f64 {
+        if let Some(&payoff) = self.outcomes.get(&(hero, mask, winner)) {
+            return payoff;
+        }
+        let values: Vec<Option<bool>> = (0..self.spot.stacks.len())
+            .map(|i| Some(i == winner))
+            .collect();
+        let payoff = self.payoff(hero, &self.award(mask, &values));
+        self.outcomes.insert((hero, mask, winner), payoff);
+        payoff
+    }
+
+    fn award<V: Ord>(&self, mask: usize, values: &[Option<V>]) -> Vec<u32> {
+        let players = self.spot.stacks.len();
+        let mut pots = Pots::new(players);
+        for i in 0..players {
+            if mask & 1 << i != 0 {
+                pots.add(i, self.spot.stacks[i]);
+            } else {
+                pots.add(i, self.posted[i]);
+                pots.fold(i);
+            }
+        }
+        pots.return_uncalled();
+
+        let button = players.saturating_sub(3);
+        let won = pots.award(button, values);
+        (0..players)
+            .map(|i| self.spot.stacks[i] - pots.bet(i) + won[i])
+            .collect()
+    }
+
+    fn payoff(&self, hero: usize, stacks: &[u32]) -> f64 {
+        match &self.spot.payoff {
+            Payoff::Chips => f64::from(stacks[hero]),
+            Payoff::Icm(prizes) => {
+                let mut stacks = stacks.to_vec();
+                stacks.extend_from_slice(&self.spot.others);
+                icm::equities(&stacks, prizes)[hero]
+            }
+        }
+    }
+}
+
+/// Returns the index of the range of `player` when the players of `mask`
+/// before them went all in.
+fn node(player: usize, mask: usize) -> usize {
+    (1 << player) - 1 + mask
+}
+
+#[test]
+fn short_stacks() {
+    let matchups = Matchups::exact();
+    let aces: Class = "AA".parse().unwrap();
+    assert!(matchups.equity(aces, "KK".parse().unwrap()) > 0.6);
+    assert_eq!(matchups.deals(aces, aces), 6);
+    assert_eq!(matchups.deals(aces, "AKs".parse().unwrap()), 6 * 2);
+
+    // With a blind and a half, anything is worth calling with.
+    let spot = |stacks: Vec<u32>, payoff| Spot {
+        stacks,
+        small_blind: 50,
+        big_blind: 100,
+        ante: 10,
+        payoff,
+        others: Vec::new(),
+    };
+    let mut solver = Solver::new(spot(vec![160, 160], Payoff::Chips), &matchups);
+    solver.solve(20);
+    assert!(solver.range(1, &[0]).combos() > 1_200.0);
+
+    // Three handed, the button jams tighter into two players.
+    let mut solver = Solver::new(spot(vec![1_500, 1_500, 1_500], Payoff::Chips), &matchups);
+    solver.samples = 20;
+    solver.solve(8);
+    let button = solver.range(0, &[]).combos();
+    let small_blind = solver.range(1, &[]).combos();
+    assert!(button < small_blind, "{} {}", button, small_blind);
+    assert!(solver.range(2, &[0, 1]).combos() < solver.range(2, &[0]).combos());
+    assert!(solver.range(2, &[]).contains("72o".parse().unwrap()));
+
+    // On the bubble, calling needs a better hand than for chips.
+    let mut chips = Solver::new(spot(vec![2_000, 2_000], Payoff::Chips), &matchups);
+    let prizes = Payoff::Icm(vec![50, 30, 20]);
+    let mut spot = spot(vec![2_000, 2_000], prizes);
+    spot.others = vec![500];
+    let mut bubble = Solver::new(spot, &matchups);
+    chips.solve(20);
+    bubble.solve(20);
+    assert!(bubble.range(1, &[0]).combos() < chips.range(1, &[0]).combos());
+    let (jam, fold) = bubble.ev(0, &[], aces);
+    assert!(jam > fold);
+}
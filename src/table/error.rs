@@ -0,0 +1,77 @@
+use crate::player::Action;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Tried to check while facing a bet.
+    CannotCheck {
+        to_call: u32,
+    },
+    /// Tried to call while not facing a bet.
+    NothingToCall,
+    /// Called with something other than what is owed.
+    WrongCall {
+        expected: u32,
+        actual: u32,
+    },
+    /// Tried to bet while facing a bet, which requires a raise.
+    CannotBet {
+        to_match: u32,
+    },
+    /// Tried to raise while not facing a bet, which requires a bet.
+    NothingToRaise,
+    /// The structure doesn't allow another raise, the seat can't cover one,
+    /// or a short all-in didn't reopen the betting.
+    RaiseNotAllowed,
+    RaiseTooSmall {
+        min: u32,
+        actual: u32,
+    },
+    RaiseTooLarge {
+        max: u32,
+        actual: u32,
+    },
+    /// Fewer than two seats have chips to play a hand with.
+    NotEnoughPlayers {
+        dealt: usize,
+    },
+    /// Wraps any of the above together with the seat and the action it tried.
+    IllegalAction {
+        seat: usize,
+        action: Action,
+        reason: Box<Error>,
+    },
+}
+
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::CannotCheck { to_call } => write!(f, "can't check, {} to call", to_call),
+            Error::NothingToCall => write!(f, "nothing to call"),
+            Error::WrongCall { expected, actual } => {
+                write!(f, "call must be {}, not {}", expected, actual)
+            }
+            Error::CannotBet { to_match } => {
+                write!(f, "can't bet into a bet of {}, raise instead", to_match)
+            }
+            Error::NothingToRaise => write!(f, "nothing to raise, bet instead"),
+            Error::RaiseNotAllowed => write!(f, "raising is not allowed"),
+            Error::RaiseTooSmall { min, actual } => {
+                write!(f, "raise to {} is less than the minimum {}", actual, min)
+            }
+            Error::RaiseTooLarge { max, actual } => {
+                write!(f, "raise to {} is more than the maximum {}", actual, max)
+            }
+            Error::NotEnoughPlayers { dealt } => {
+                write!(f, "can't play a hand with {} seats dealt in", dealt)
+            }
+            Error::IllegalAction {
+                seat,
+                action,
+                reason,
+            } => write!(f, "seat {} can't {:?}: {}", seat, action, reason),
+        }
+    }
+}
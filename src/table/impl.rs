@@ -0,0 +1,144 @@
+use super::{state::State, structure::Betting, Config, Seat, Table, MAX_SEATS};
+use crate::deck::Deck;
+use crate::history::History;
+use crate::player::Player;
+use rand::{rngs::StdRng, SeedableRng};
+
+impl Config {
+    /// Constructs a no limit config without antes.
+    pub fn no_limit(small_blind: u32, big_blind: u32) -> Self {
+        Config {
+            betting: Betting::NoLimit,
+            small_blind,
+            big_blind,
+            ante: 0,
+            big_blind_ante: false,
+        }
+    }
+}
+
+impl Seat {
+    /// Returns `true` if the seat was dealt in and hasn't folded.
+    pub fn in_hand(&self) -> bool {
+        self.dealt && !self.folded
+    }
+
+    /// Returns `true` if the seat is in the hand without any chips behind.
+    pub fn is_all_in(&self) -> bool {
+        self.in_hand() && self.stack == 0
+    }
+}
+
+impl Table {
+    /// Constructs a new `Table` with one seat per stack in `stacks`.
+    /// The button starts on the first seat.
+    ///
+    /// # Panics
+    /// Panics if there are more than `MAX_SEATS` stacks.
+    pub fn new(config: Config, stacks: &[u32]) -> Self {
+        Table::with_rng(config, stacks, StdRng::from_entropy())
+    }
+
+    /// Constructs a new `Table` which deals the same cards every time it's
+    /// constructed with the same `seed`.
+    ///
+    /// # Panics
+    /// Panics if there are more than `MAX_SEATS` stacks.
+    pub fn new_seeded(config: Config, stacks: &[u32], seed: u64) -> Self {
+        Table::with_rng(config, stacks, StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(config: Config, stacks: &[u32], rng: StdRng) -> Self {
+        assert!(
+            stacks.len() <= MAX_SEATS,
+            "a table has at most {} seats, not {}",
+            MAX_SEATS,
+            stacks.len()
+        );
+        Table {
+            config,
+            deck: Deck::new_sorted(),
+            players: stacks.iter().map(|&stack| Player::new(stack)).collect(),
+            button: 0,
+            hands: 0,
+            rng,
+            last: None,
+        }
+    }
+
+    /// Reseeds the shuffling of the deck.
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Changes the stakes and rules from the next hand on, such as when the
+    /// blinds go up.
+    pub fn set_config(&mut self, config: Config) {
+        self.config = config;
+    }
+
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    /// Gives access to the players between hands, for adding chips and such.
+    pub fn players_mut(&mut self) -> &mut [Player] {
+        &mut self.players
+    }
+
+    /// Returns the seat of the button in the last hand played,
+    /// or of the first hand if none has been played yet.
+    pub fn button(&self) -> usize {
+        self.button
+    }
+
+    /// Returns the seat that will post the big blind in the next hand, or
+    /// `None` if fewer than two seats have chips.
+    pub fn next_big_blind(&self) -> Option<usize> {
+        let state = State::new(self.players.iter().map(|player| player.pot));
+        if state.dealt() < 2 {
+            return None;
+        }
+        Some(state.blinds(self.next_button(&state)).1)
+    }
+
+    /// Returns the number of hands played.
+    pub fn hands(&self) -> u64 {
+        self.hands
+    }
+
+    /// Returns the record of the last hand played, if any.
+    pub fn last_hand(&self) -> Option<&History> {
+        self.last.as_ref()
+    }
+
+    /// Returns the button of the next hand, which moves on except before
+    /// the first hand.
+    pub(super) fn next_button(&self, state: &State) -> usize {
+        if self.hands > 0 || !state.seats[self.button].dealt {
+            state.next_dealt(self.button)
+        } else {
+            self.button
+        }
+    }
+}
+
+#[test]
+fn seats_fit_in_a_deck() {
+    use crate::bot::Station;
+    use crate::player::Agent;
+
+    let config = Config::no_limit(1, 2);
+    let mut table = Table::new_seeded(config, &[100; MAX_SEATS], 1);
+    let mut agents: Vec<Box<dyn Agent>> = (0..MAX_SEATS)
+        .map(|_| -> Box<dyn Agent> { Box::new(Station) })
+        .collect();
+    assert_eq!(table.play(&mut agents).unwrap().board.len(), 5);
+
+    let more = std::panic::catch_unwind(|| Table::new(config, &[100; MAX_SEATS + 1]));
+    assert!(more.is_err());
+}
@@ -1,26 +1,109 @@
-/*
+pub mod error;
+mod r#impl;
+mod play;
+pub mod pot;
+pub(crate) mod state;
+pub mod structure;
+
+pub use error::Error;
+
+use crate::card::Card;
 use crate::deck::Deck;
-use crate::player::Player;
+use crate::history::History;
+use crate::player::{Decision, Player};
+use mimpl::mimpl;
+use rand::rngs::StdRng;
+use structure::Betting;
+
+/// The most seats a table deals to, since every hole card, burn and board
+/// card of a hand comes from one deck of 52.
+pub const MAX_SEATS: usize = 22;
+
+/// The betting rounds of a hand.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+}
+
+mimpl!(Default; Street, || Street::Preflop);
+
+/// Stakes and rules of a table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Config {
+    pub betting: Betting,
+    pub small_blind: u32,
+    pub big_blind: u32,
+    /// Posted by every seat dealt in, before the blinds.
+    pub ante: u32,
+    /// If set, the ante is posted once, by the big blind, for everyone.
+    pub big_blind_ante: bool,
+}
 
+/// A Texas Hold'em table, dealing hands to the `Agent`s playing its seats.
+///
+/// # Example
+/// ```
+/// # use aces_high::{player::*, table::*};
+/// struct Caller;
+///
+/// impl Agent for Caller {
+///     fn decide(&mut self, view: &View) -> Action {
+///         match view.legal.call {
+///             Some(amount) => Action::Call(amount),
+///             None => Action::Check,
+///         }
+///     }
+/// }
+///
+/// let mut table = Table::new_seeded(Config::no_limit(1, 2), &[200, 200, 200], 7);
+/// let mut agents: Vec<Box<dyn Agent>> = vec![Box::new(Caller), Box::new(Caller), Box::new(Caller)];
+///
+/// let summary = table.play(&mut agents)?;
+/// assert_eq!(summary.board.len(), 5);
+/// assert_eq!(summary.net.iter().sum::<i64>(), 0);
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Debug)]
 pub struct Table {
+    config: Config,
     deck: Deck,
     players: Vec<Player>,
+    button: usize,
+    hands: u64,
+    rng: StdRng,
+    last: Option<History>,
 }
 
-impl Table {
-    pub fn deal(&mut self, size: usize) {
-        for player in self.players.as_mut_slice() {
-            player.take(self.deck.deal(size).unwrap()).ok();
-        }
-    }
+/// What everyone at the table can see about a seat during a hand.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Seat {
+    /// Chips behind.
+    pub stack: u32,
+    /// Chips bet on the current street.
+    pub bet: u32,
+    /// Chips put in during the hand, antes and blinds included.
+    pub tot_bet: u32,
+    /// `true` if the seat was dealt into the hand.
+    pub dealt: bool,
+    pub folded: bool,
 }
 
-impl Default for Table {
-    fn default() -> Self {
-        Table {
-            deck: Deck::new_shuffled(),
-            players: Vec::new(),
-        }
-    }
+/// How a hand played out.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub button: usize,
+    /// Stacks at the start of the hand. Seats without chips weren't dealt in.
+    pub stacks: Vec<u32>,
+    /// Every action taken, blinds and antes excluded.
+    pub history: Vec<Decision>,
+    pub board: Vec<Card>,
+    /// Cards of every seat that went to showdown.
+    pub shown: Vec<(usize, Vec<Card>)>,
+    /// Chips each seat won from the pots.
+    pub won: Vec<u32>,
+    /// How much each seat's stack changed.
+    pub net: Vec<i64>,
 }
-*/
@@ -0,0 +1,176 @@
+use super::{state::State, structure::Legal, Error, Street, Summary, Table};
+use crate::card::Card;
+use crate::deck::Deck;
+use crate::history::{self, Game, History};
+use crate::player::{Agent, View};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+impl Table {
+    /// Plays one hand, with `agents[seat]` deciding for every seat.
+    ///
+    /// Seats without chips sit the hand out. The button moves to the next
+    /// seat dealt in, except on the first hand.
+    ///
+    /// Returns an error, and leaves the players as they were before the
+    /// hand, if fewer than two seats have chips or an agent picks an illegal
+    /// action.
+    ///
+    /// # Panics
+    /// Panics if there isn't exactly one agent per seat.
+    pub fn play(&mut self, agents: &mut [Box<dyn Agent>]) -> Result<Summary, Error> {
+        assert_eq!(
+            agents.len(),
+            self.players.len(),
+            "every seat needs exactly one agent"
+        );
+
+        let (players, button, hands) = (self.players.clone(), self.button, self.hands);
+        let summary = self.play_hand(agents);
+
+        if summary.is_err() {
+            self.players = players;
+            self.button = button;
+            self.hands = hands;
+        }
+
+        summary
+    }
+
+    fn play_hand(&mut self, agents: &mut [Box<dyn Agent>]) -> Result<Summary, Error> {
+        let seats = self.players.len();
+        let mut state = State::new(self.players.iter().map(|player| player.pot));
+
+        let dealt = state.dealt();
+        if dealt < 2 {
+            return Err(Error::NotEnoughPlayers { dealt });
+        }
+
+        self.button = self.next_button(&state);
+        self.hands += 1;
+
+        let button = self.button;
+        let (posts, big) = state.post_blinds(&self.config, button);
+
+        // Every hand gets a seed of its own, so it can be dealt again alone.
+        let seed = self.rng.gen();
+        self.deck = Deck::new_sorted();
+        self.deck.shuffle_with(&mut StdRng::seed_from_u64(seed));
+
+        for player in self.players.iter_mut() {
+            player.discard();
+        }
+        let holes = state.deal_holes(&mut self.deck, button);
+        for (player, hole) in self.players.iter_mut().zip(holes) {
+            player.take(hole);
+        }
+
+        for seat in (0..seats).filter(|&seat| state.seats[seat].dealt) {
+            agents[seat].hand_start(&self.view(&state, seat, Legal::default()));
+        }
+
+        self.betting_round(&mut state, agents, big)?;
+        while state.deal_street(&mut self.deck).is_some() {
+            self.betting_round(&mut state, agents, button)?;
+        }
+
+        let holes: Vec<&[Card]> = self.players.iter().map(|player| player.cards()).collect();
+        let showdown = state.showdown(&holes);
+        if !showdown.shown.is_empty() {
+            for seat in (0..seats).filter(|&seat| state.seats[seat].dealt) {
+                agents[seat].showdown(&self.view(&state, seat, Legal::default()), &showdown.shown);
+            }
+        }
+
+        let award = state.award(button, &showdown.values);
+
+        let summary = Summary {
+            button,
+            stacks: self.players.iter().map(|player| player.pot).collect(),
+            history: state.history.clone(),
+            board: state.board.clone(),
+            shown: showdown.shown,
+            net: state
+                .seats
+                .iter()
+                .zip(&self.players)
+                .map(|(info, player)| i64::from(info.stack) - i64::from(player.pot))
+                .collect(),
+            won: award.won,
+        };
+
+        self.last = Some(History {
+            id: self.hands,
+            tournament: None,
+            table: String::new(),
+            game: Game::Holdem,
+            config: self.config,
+            seats: self
+                .players
+                .iter()
+                .enumerate()
+                .map(|(seat, player)| history::Seat {
+                    name: format!("Seat {}", seat + 1),
+                    stack: player.pot,
+                    hole: player.cards().to_vec(),
+                })
+                .collect(),
+            button,
+            posts,
+            actions: state.history.clone(),
+            board: state.board.clone(),
+            shown: summary.shown.iter().map(|&(seat, _)| seat).collect(),
+            returned: award.returned,
+            pots: award.pots.pots(),
+            won: summary.won.clone(),
+            rake: 0,
+            seed: Some(seed),
+        });
+
+        for seat in (0..seats).filter(|&seat| state.seats[seat].dealt) {
+            agents[seat].hand_end(&self.view(&state, seat, Legal::default()), &summary);
+        }
+
+        for (player, info) in self.players.iter_mut().zip(&state.seats) {
+            player.pot = info.stack;
+            player.tot_bet = 0;
+            player.discard();
+        }
+
+        Ok(summary)
+    }
+
+    /// Asks every seat that has to act for a decision, until the street is
+    /// over. The first seat asked is the first one after `after`.
+    fn betting_round(
+        &self,
+        state: &mut State,
+        agents: &mut [Box<dyn Agent>],
+        after: usize,
+    ) -> Result<(), Error> {
+        let mut seat = after;
+
+        while let Some(next) = state.to_act(seat) {
+            seat = next;
+            let legal = state.legal(&self.config, seat);
+            let action = agents[seat].decide(&self.view(state, seat, legal));
+            state.act(&self.config, seat, action)?;
+        }
+
+        Ok(())
+    }
+
+    fn view<'a>(&'a self, state: &'a State, seat: usize, legal: Legal) -> View<'a> {
+        View {
+            seat,
+            button: self.button,
+            street: state.street,
+            big_blind: self.config.big_blind,
+            hole: self.players[seat].cards(),
+            board: &state.board,
+            pot: state.pot(),
+            seats: &state.seats,
+            history: &state.history,
+            legal,
+        }
+    }
+}
@@ -0,0 +1,496 @@
+use crate::player::Player;
+use std::cmp;
+
+/// A pot and the seats who can win it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pot {
+    pub amount: u32,
+    /// Seats eligible to win the pot, in seat order.
+    pub eligible: Vec<usize>,
+}
+
+/// Keeps track of how much every seat has put into the middle during a hand,
+/// and splits it into a main pot and side pots.
+///
+/// # Example
+/// ```
+/// # use aces_high::table::pot::{Pot, Pots};
+/// let mut pots = Pots::new(3);
+/// pots.add(0, 50); // All-in
+/// pots.add(1, 100);
+/// pots.add(2, 100);
+///
+/// assert_eq!(
+///     pots.pots(),
+///     vec![
+///         Pot { amount: 150, eligible: vec![0, 1, 2] },
+///         Pot { amount: 100, eligible: vec![1, 2] },
+///     ]
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Pots {
+    bets: Vec<u32>,
+    folded: Vec<bool>,
+}
+
+impl Pots {
+    /// Constructs an empty `Pots` for a table with `seats` seats.
+    pub fn new(seats: usize) -> Self {
+        Pots {
+            bets: vec![0; seats],
+            folded: vec![false; seats],
+        }
+    }
+
+    /// Constructs `Pots` from what every player has bet this hand.
+    /// Players without cards are treated as folded.
+    pub fn from_players(players: &[Player]) -> Self {
+        Pots {
+            bets: players.iter().map(|player| player.tot_bet).collect(),
+            folded: players.iter().map(Player::hand_is_empty).collect(),
+        }
+    }
+
+    pub fn seats(&self) -> usize {
+        self.bets.len()
+    }
+
+    /// Adds `amount` chips from `seat` to the middle.
+    pub fn add(&mut self, seat: usize, amount: u32) {
+        self.bets[seat] += amount;
+    }
+
+    /// Marks `seat` as folded. Its chips stay in the middle as dead money,
+    /// but it can no longer win any of it.
+    pub fn fold(&mut self, seat: usize) {
+        self.folded[seat] = true;
+    }
+
+    pub fn is_folded(&self, seat: usize) -> bool {
+        self.folded[seat]
+    }
+
+    /// Returns how much `seat` has put in.
+    pub fn bet(&self, seat: usize) -> u32 {
+        self.bets[seat]
+    }
+
+    /// Returns the sum of all chips in the middle.
+    pub fn total(&self) -> u32 {
+        self.bets.iter().sum()
+    }
+
+    /// Returns the seat and amount of a bet nobody has matched, if any.
+    ///
+    /// This is the part of the largest bet exceeding the second largest.
+    pub fn uncalled(&self) -> Option<(usize, u32)> {
+        let (seat, largest) = self
+            .bets
+            .iter()
+            .copied()
+            .enumerate()
+            .max_by_key(|&(_, bet)| bet)?;
+
+        let second = self
+            .bets
+            .iter()
+            .enumerate()
+            .filter(|&(other, _)| other != seat)
+            .map(|(_, &bet)| bet)
+            .max()
+            .unwrap_or(0);
+
+        if largest > second {
+            Some((seat, largest - second))
+        } else {
+            None
+        }
+    }
+
+    /// Takes back the uncalled part of a bet, if any, and returns it so it
+    /// can be given back to its owner.
+    pub fn return_uncalled(&mut self) -> Option<(usize, u32)> {
+        let uncalled = self.uncalled();
+
+        if let Some((seat, amount)) = uncalled {
+            self.bets[seat] -= amount;
+        }
+
+        uncalled
+    }
+
+    /// Splits everything in the middle into a main pot followed by side pots.
+    ///
+    /// A new side pot starts at every amount a seat still in the hand is
+    /// all-in for. Chips from folded seats are dead money and are spread over
+    /// the pots they reach, but folded seats are never eligible.
+    /// Uncalled bets should be returned with `return_uncalled` first,
+    /// otherwise they end up in a pot of their own.
+    pub fn pots(&self) -> Vec<Pot> {
+        let mut levels: Vec<u32> = self
+            .bets
+            .iter()
+            .zip(&self.folded)
+            .filter(|&(&bet, &folded)| !folded && bet > 0)
+            .map(|(&bet, _)| bet)
+            .collect();
+        levels.sort();
+        levels.dedup();
+
+        if levels.is_empty() {
+            let amount = self.total();
+            return if amount > 0 {
+                vec![Pot {
+                    amount,
+                    eligible: Vec::new(),
+                }]
+            } else {
+                Vec::new()
+            };
+        }
+
+        let mut pots = Vec::with_capacity(levels.len());
+        let mut floor = 0;
+
+        for (i, &level) in levels.iter().enumerate() {
+            // The last pot also collects dead money above the highest live bet.
+            let ceiling = if i + 1 == levels.len() {
+                u32::MAX
+            } else {
+                level
+            };
+
+            let amount = self
+                .bets
+                .iter()
+                .map(|&bet| cmp::min(bet, ceiling).saturating_sub(floor))
+                .sum();
+
+            let eligible = (0..self.seats())
+                .filter(|&seat| !self.folded[seat] && self.bets[seat] >= level)
+                .collect();
+
+            pots.push(Pot { amount, eligible });
+            floor = level;
+        }
+
+        pots
+    }
+
+    /// Awards every pot to the eligible seats holding the best value and
+    /// returns how many chips each seat won.
+    ///
+    /// `values` holds the showdown value of each seat, where a greater value
+    /// wins. Seats without a value have mucked and can't win, unless nobody
+    /// eligible for a pot has one, in which case it is split among everyone
+    /// eligible. Odd chips go as described in `split`.
+    ///
+    /// # Example
+    /// ```
+    /// # use aces_high::table::pot::Pots;
+    /// let mut pots = Pots::new(3);
+    /// pots.add(0, 50);
+    /// pots.add(1, 100);
+    /// pots.add(2, 100);
+    ///
+    /// // Seat 0 has the best hand but can only win the main pot.
+    /// assert_eq!(pots.award(2, &[Some(3), Some(2), Some(1)]), vec![150, 100, 0]);
+    /// ```
+    pub fn award<V: Ord>(&self, button: usize, values: &[Option<V>]) -> Vec<u32> {
+        let value = |seat: usize| values.get(seat).and_then(Option::as_ref);
+        let mut won = vec![0; self.seats()];
+
+        for pot in self.pots() {
+            let best = pot.eligible.iter().filter_map(|&seat| value(seat)).max();
+
+            let winners: Vec<usize> = match best {
+                Some(best) => pot
+                    .eligible
+                    .iter()
+                    .copied()
+                    .filter(|&seat| value(seat) == Some(best))
+                    .collect(),
+                None => pot.eligible.clone(),
+            };
+
+            for (seat, amount) in split(pot.amount, &winners, button, self.seats()) {
+                won[seat] += amount;
+            }
+        }
+
+        won
+    }
+}
+
+/// Splits `amount` chips evenly between `winners`.
+///
+/// Chips that can't be split evenly are handed out one at a time, going
+/// clockwise from the first seat left of the `button`.
+/// The shares are returned in that same order.
+///
+/// # Example
+/// ```
+/// # use aces_high::table::pot::split;
+/// // Seat 3 is first left of the button and gets the odd chip.
+/// assert_eq!(split(5, &[1, 3], 2, 4), vec![(3, 3), (1, 2)]);
+/// ```
+pub fn split(amount: u32, winners: &[usize], button: usize, seats: usize) -> Vec<(usize, u32)> {
+    if winners.is_empty() {
+        return Vec::new();
+    }
+
+    let mut winners = winners.to_vec();
+    winners.sort_by_key(|&seat| (seat + seats - button % seats - 1) % seats);
+
+    let share = amount / winners.len() as u32;
+    let odd = amount as usize % winners.len();
+
+    winners
+        .into_iter()
+        .enumerate()
+        .map(|(i, seat)| (seat, if i < odd { share + 1 } else { share }))
+        .collect()
+}
+
+#[cfg(test)]
+fn pots_from(bets: &[u32], folded: &[usize]) -> Pots {
+    let mut pots = Pots::new(bets.len());
+    for (seat, &bet) in bets.iter().enumerate() {
+        pots.add(seat, bet);
+    }
+    for &seat in folded {
+        pots.fold(seat);
+    }
+    pots
+}
+
+#[cfg(test)]
+fn pot(amount: u32, eligible: &[usize]) -> Pot {
+    Pot {
+        amount,
+        eligible: eligible.to_vec(),
+    }
+}
+
+#[test]
+fn single_pot_when_bets_are_equal() {
+    let pots = pots_from(&[100, 100, 100], &[]);
+    assert_eq!(pots.pots(), vec![pot(300, &[0, 1, 2])]);
+    assert_eq!(pots.uncalled(), None);
+}
+
+#[test]
+fn empty_pots() {
+    assert_eq!(Pots::new(4).pots(), vec![]);
+    assert_eq!(Pots::new(4).uncalled(), None);
+    assert_eq!(Pots::new(0).uncalled(), None);
+    assert_eq!(Pots::new(4).award(0, &[Some(1); 4]), vec![0; 4]);
+}
+
+#[test]
+fn side_pot_for_short_all_in() {
+    let pots = pots_from(&[50, 100, 100], &[]);
+    assert_eq!(pots.pots(), vec![pot(150, &[0, 1, 2]), pot(100, &[1, 2])]);
+}
+
+#[test]
+fn side_pot_for_every_all_in_level() {
+    let pots = pots_from(&[10, 40, 25, 100, 100], &[]);
+    assert_eq!(
+        pots.pots(),
+        vec![
+            pot(50, &[0, 1, 2, 3, 4]),
+            pot(60, &[1, 2, 3, 4]),
+            pot(45, &[1, 3, 4]),
+            pot(120, &[3, 4]),
+        ]
+    );
+}
+
+#[test]
+fn equal_all_ins_share_a_level() {
+    let pots = pots_from(&[30, 30, 80, 80], &[]);
+    assert_eq!(pots.pots(), vec![pot(120, &[0, 1, 2, 3]), pot(100, &[2, 3])]);
+}
+
+#[test]
+fn folded_chips_are_dead_money() {
+    // Seat 3 folded after putting in 20.
+    let pots = pots_from(&[30, 100, 100, 20], &[3]);
+    assert_eq!(pots.pots(), vec![pot(110, &[0, 1, 2]), pot(140, &[1, 2])]);
+
+    // Seat 3 folded after putting in more than the all-in.
+    let pots = pots_from(&[30, 100, 100, 60], &[3]);
+    assert_eq!(pots.pots(), vec![pot(120, &[0, 1, 2]), pot(170, &[1, 2])]);
+}
+
+#[test]
+fn folded_chips_above_every_live_bet_go_to_last_pot() {
+    let pots = pots_from(&[40, 40, 90], &[2]);
+    assert_eq!(pots.pots(), vec![pot(170, &[0, 1])]);
+}
+
+#[test]
+fn everyone_folded_leaves_unclaimed_pot() {
+    let pots = pots_from(&[10, 20], &[0, 1]);
+    assert_eq!(pots.pots(), vec![pot(30, &[])]);
+}
+
+#[test]
+fn uncalled_bet_is_returned() {
+    let mut pots = pots_from(&[100, 40], &[]);
+    assert_eq!(pots.uncalled(), Some((0, 60)));
+    assert_eq!(pots.return_uncalled(), Some((0, 60)));
+    assert_eq!(pots.bet(0), 40);
+    assert_eq!(pots.return_uncalled(), None);
+    assert_eq!(pots.pots(), vec![pot(80, &[0, 1])]);
+}
+
+#[test]
+fn uncalled_bet_when_everyone_folds() {
+    // Small blind folds to the big blind.
+    let mut pots = pots_from(&[1, 2], &[0]);
+    assert_eq!(pots.return_uncalled(), Some((1, 1)));
+    assert_eq!(pots.pots(), vec![pot(2, &[1])]);
+    assert_eq!(pots.award(0, &[None::<u8>, None]), vec![0, 2]);
+
+    // Raise folded around, with a caller who folded later.
+    let mut pots = pots_from(&[1, 2, 60, 20], &[0, 1, 3]);
+    assert_eq!(pots.return_uncalled(), Some((2, 40)));
+    assert_eq!(pots.total(), 43);
+    assert_eq!(pots.award(3, &[None::<u8>, None, None, None]), vec![0, 0, 43, 0]);
+}
+
+#[test]
+fn no_uncalled_bet_on_tie() {
+    let pots = pots_from(&[100, 100, 50], &[]);
+    assert_eq!(pots.uncalled(), None);
+}
+
+#[test]
+fn uncalled_counts_folded_bets() {
+    // The largest bet was called by someone who later folded.
+    let pots = pots_from(&[200, 200, 50], &[1]);
+    assert_eq!(pots.uncalled(), None);
+}
+
+#[test]
+fn from_players_uses_tot_bet_and_cards() {
+    use crate::card::face::*;
+
+    let mut players = vec![Player::new(100), Player::new(100), Player::new(100)];
+    for (player, bet) in players.iter_mut().zip(&[50, 100, 100]) {
+        player.bet(*bet);
+    }
+    players[0].take(vec![card!(Ace, Spades), card!(King, Spades)]);
+    players[1].take(vec![card!(Two, Clubs), card!(Seven, Hearts)]);
+
+    let pots = Pots::from_players(&players);
+    assert!(pots.is_folded(2));
+    assert_eq!(pots.pots(), vec![pot(150, &[0, 1]), pot(100, &[1])]);
+}
+
+#[test]
+fn award_main_and_side_pots() {
+    let pots = pots_from(&[50, 100, 100], &[]);
+
+    // Short stack wins main, seat 1 wins side.
+    assert_eq!(pots.award(0, &[Some(3), Some(2), Some(1)]), vec![150, 100, 0]);
+    // Short stack loses everything.
+    assert_eq!(pots.award(0, &[Some(1), Some(2), Some(3)]), vec![0, 0, 250]);
+    // Short stack wins main, the rest chop the side pot.
+    assert_eq!(pots.award(0, &[Some(3), Some(2), Some(2)]), vec![150, 50, 50]);
+}
+
+#[test]
+fn award_ignores_folded_seats() {
+    let pots = pots_from(&[30, 100, 100, 60], &[3]);
+    // The folded seat holds the "best" hand, but it has no say.
+    assert_eq!(
+        pots.award(0, &[Some(1), Some(3), Some(2), Some(9)]),
+        vec![0, 290, 0, 0]
+    );
+}
+
+#[test]
+fn award_to_mucked_hands_only_without_contest() {
+    let pots = pots_from(&[100, 100], &[]);
+    // Seat 0 mucked at showdown.
+    assert_eq!(pots.award(0, &[None, Some(1)]), vec![0, 200]);
+}
+
+#[test]
+fn award_odd_chip_left_of_button() {
+    let pots = pots_from(&[5, 5, 5], &[2]);
+
+    assert_eq!(pots.award(2, &[Some(1), Some(1), None]), vec![8, 7, 0]);
+    assert_eq!(pots.award(0, &[Some(1), Some(1), None]), vec![7, 8, 0]);
+    // Button is one of the winners, but still comes last.
+    assert_eq!(pots.award(1, &[Some(1), Some(1), None]), vec![8, 7, 0]);
+}
+
+#[test]
+fn award_odd_chips_per_pot() {
+    // Three-way chop of main, two-way chop of side.
+    let pots = pots_from(&[11, 16, 16], &[]);
+    assert_eq!(pots.award(0, &[Some(1), Some(1), Some(1)]), vec![11, 16, 16]);
+
+    let pots = pots_from(&[10, 10, 10, 1], &[3]);
+    // 31 chips, three winners, button on seat 1: 2 gets the odd chip.
+    assert_eq!(
+        pots.award(1, &[Some(1), Some(1), Some(1), None]),
+        vec![10, 10, 11, 0]
+    );
+}
+
+#[test]
+fn split_evenly() {
+    assert_eq!(split(9, &[0, 1, 2], 0, 3), vec![(1, 3), (2, 3), (0, 3)]);
+    assert_eq!(split(0, &[4], 0, 6), vec![(4, 0)]);
+    assert_eq!(split(10, &[], 0, 6), vec![]);
+}
+
+#[test]
+fn split_odd_chips_clockwise() {
+    assert_eq!(split(5, &[1, 3], 0, 4), vec![(1, 3), (3, 2)]);
+    assert_eq!(split(5, &[1, 3], 2, 4), vec![(3, 3), (1, 2)]);
+    assert_eq!(split(5, &[1, 3], 3, 4), vec![(1, 3), (3, 2)]);
+    assert_eq!(
+        split(11, &[0, 2, 4, 5], 4, 6),
+        vec![(5, 3), (0, 3), (2, 3), (4, 2)]
+    );
+}
+
+#[test]
+fn chips_are_never_lost() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(1);
+
+    for _ in 0..10_000 {
+        let seats = rng.gen_range(2, 10);
+        let bets: Vec<u32> = (0..seats).map(|_| rng.gen_range(0, 200)).collect();
+        let live = rng.gen_range(0, seats);
+        let folded: Vec<usize> = (0..seats)
+            .filter(|&seat| seat != live && rng.gen())
+            .collect();
+        let values: Vec<Option<u8>> = (0..seats).map(|_| Some(rng.gen_range(0, 4))).collect();
+        let button = rng.gen_range(0, seats);
+
+        let mut pots = pots_from(&bets, &folded);
+        let total = pots.total();
+        let returned = pots.return_uncalled().map_or(0, |(_, amount)| amount);
+
+        assert_eq!(pots.uncalled(), None);
+        assert_eq!(pots.pots().iter().map(|pot| pot.amount).sum::<u32>(), total - returned);
+
+        let won = pots.award(button, &values);
+        if pots.pots().iter().all(|pot| !pot.eligible.is_empty()) {
+            assert_eq!(won.iter().sum::<u32>() + returned, total);
+        }
+        for seat in folded {
+            assert_eq!(won[seat], 0);
+        }
+    }
+}
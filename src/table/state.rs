@@ -0,0 +1,381 @@
+use super::{
+    pot::Pots,
+    structure::{Legal, Situation, Structure},
+    Config, Error, Seat, Street,
+};
+use crate::card::Card;
+use crate::deck::Deck;
+use crate::hand::{Hand, Value};
+use crate::history::{Blind, Post};
+use crate::player::{Action, Decision};
+use std::cmp;
+
+/// Everything about the hand in progress which isn't kept by `Table`.
+///
+/// Shared by the table playing hands and the replay of recorded ones, so
+/// that both follow the same rules.
+#[derive(Debug)]
+pub(crate) struct State {
+    pub(crate) street: Street,
+    pub(crate) seats: Vec<Seat>,
+    pub(crate) board: Vec<Card>,
+    pub(crate) history: Vec<Decision>,
+    to_match: u32,
+    last_raise: u32,
+    raises: u32,
+    /// Whether each seat has acted since the last full raise.
+    acted: Vec<bool>,
+    /// Whether each seat may raise, see `Situation::reopened`.
+    reopened: Vec<bool>,
+}
+
+/// The hands of the seats left at showdown.
+pub(crate) struct Showdown {
+    /// Value of each seat's hand, if it was shown.
+    pub(crate) values: Vec<Option<Value>>,
+    pub(crate) shown: Vec<(usize, Vec<Card>)>,
+}
+
+/// How the pots were given out at the end of a hand.
+pub(crate) struct Award {
+    pub(crate) pots: Pots,
+    pub(crate) returned: Option<(usize, u32)>,
+    pub(crate) won: Vec<u32>,
+}
+
+impl State {
+    /// Constructs the state of a hand about to be dealt, with seats dealt
+    /// in if they have chips.
+    pub(crate) fn new<I: IntoIterator<Item = u32>>(stacks: I) -> Self {
+        let seats: Vec<Seat> = stacks
+            .into_iter()
+            .map(|stack| Seat {
+                stack,
+                dealt: stack > 0,
+                ..Seat::default()
+            })
+            .collect();
+        let len = seats.len();
+
+        State {
+            street: Street::Preflop,
+            seats,
+            board: Vec::with_capacity(5),
+            history: Vec::new(),
+            to_match: 0,
+            last_raise: 0,
+            raises: 0,
+            acted: vec![false; len],
+            reopened: vec![true; len],
+        }
+    }
+
+    pub(crate) fn pot(&self) -> u32 {
+        self.seats.iter().map(|seat| seat.tot_bet).sum()
+    }
+
+    pub(crate) fn dealt(&self) -> usize {
+        self.seats.iter().filter(|seat| seat.dealt).count()
+    }
+
+    pub(crate) fn in_hand(&self) -> usize {
+        self.seats.iter().filter(|seat| seat.in_hand()).count()
+    }
+
+    /// Returns the first seat going clockwise from `seat`, itself included,
+    /// for which `f` returns `true`.
+    fn next_from<F: Fn(usize) -> bool>(&self, seat: usize, f: F) -> Option<usize> {
+        let seats = self.seats.len();
+        (0..seats).map(|i| (seat + i) % seats).find(|&seat| f(seat))
+    }
+
+    /// Returns the first seat dealt in, clockwise after `seat`.
+    pub(crate) fn next_dealt(&self, seat: usize) -> usize {
+        self.next_from(seat + 1, |seat| self.seats[seat].dealt)
+            .unwrap()
+    }
+
+    /// Returns `true` if `seat` has to act before the street is over.
+    fn must_act(&self, seat: usize) -> bool {
+        let info = &self.seats[seat];
+        info.in_hand() && info.stack > 0 && (!self.acted[seat] || info.bet < self.to_match)
+    }
+
+    /// Returns `true` if nobody is left to bet against.
+    fn is_settled(&self) -> bool {
+        if self.in_hand() < 2 {
+            return true;
+        }
+
+        let mut with_chips = self
+            .seats
+            .iter()
+            .filter(|seat| seat.in_hand() && seat.stack > 0);
+
+        match (with_chips.next(), with_chips.next()) {
+            (None, _) => true,
+            (Some(seat), None) => seat.bet >= self.to_match,
+            _ => false,
+        }
+    }
+
+    /// Returns the next seat to act on this street, the first one after
+    /// `after` that has to, or `None` if the street is over.
+    pub(crate) fn to_act(&self, after: usize) -> Option<usize> {
+        if self.is_settled() {
+            return None;
+        }
+        self.next_from(after + 1, |seat| self.must_act(seat))
+    }
+
+    /// Moves up to `amount` chips from the stack of `seat` into the middle,
+    /// and returns how many were moved.
+    fn put(&mut self, seat: usize, amount: u32) -> u32 {
+        let seat = &mut self.seats[seat];
+        let amount = cmp::min(amount, seat.stack);
+        seat.stack -= amount;
+        seat.bet += amount;
+        seat.tot_bet += amount;
+        amount
+    }
+
+    /// Makes `to` the bet to match. A `full` raise reopens the betting for
+    /// everyone else, while a short all-in only makes them act again.
+    fn raise(&mut self, seat: usize, to: u32, full: bool) {
+        for other in (0..self.seats.len()).filter(|&other| other != seat) {
+            if full {
+                self.reopened[other] = true;
+            } else if self.acted[other] {
+                self.reopened[other] = false;
+            }
+            self.acted[other] = false;
+        }
+
+        if full {
+            self.last_raise = to - self.to_match;
+            self.raises += 1;
+        }
+        self.to_match = to;
+    }
+
+    /// Returns the seats of the small and big blinds with the button on
+    /// `button`. Heads up, the button posts the small blind.
+    pub(crate) fn blinds(&self, button: usize) -> (usize, usize) {
+        if self.dealt() == 2 {
+            (button, self.next_dealt(button))
+        } else {
+            let small = self.next_dealt(button);
+            (small, self.next_dealt(small))
+        }
+    }
+
+    /// Posts the antes and blinds of `config` for the hand with the button
+    /// on `button`, and returns them together with the seat of the big
+    /// blind.
+    pub(crate) fn post_blinds(&mut self, config: &Config, button: usize) -> (Vec<Post>, usize) {
+        let (small, big) = self.blinds(button);
+
+        let mut posts = Vec::new();
+        let mut post = |state: &mut State, seat, blind, amount| {
+            let amount = state.put(seat, amount);
+            if amount > 0 {
+                posts.push(Post {
+                    seat,
+                    blind,
+                    amount,
+                });
+            }
+        };
+
+        // Antes are dead money, and don't count towards matching a bet.
+        if config.big_blind_ante {
+            // The big blind comes first if it can't cover both.
+            let behind = self.seats[big].stack.saturating_sub(config.big_blind);
+            post(self, big, Blind::Ante, cmp::min(config.ante, behind));
+            self.seats[big].bet = 0;
+        } else {
+            for seat in 0..self.seats.len() {
+                if self.seats[seat].dealt {
+                    post(self, seat, Blind::Ante, config.ante);
+                    self.seats[seat].bet = 0;
+                }
+            }
+        }
+        post(self, small, Blind::Small, config.small_blind);
+        post(self, big, Blind::Big, config.big_blind);
+        self.to_match = config.big_blind;
+        self.last_raise = config.big_blind;
+        self.raises = 1;
+
+        (posts, big)
+    }
+
+    /// Deals two hole cards from `deck` to every seat dealt in, one at a
+    /// time from the seat after the `button`, and returns those of each
+    /// seat.
+    pub(crate) fn deal_holes(&self, deck: &mut Deck, button: usize) -> Vec<Vec<Card>> {
+        let mut holes = vec![Vec::with_capacity(2); self.seats.len()];
+        for _ in 0..2 {
+            let mut seat = button;
+            for _ in 0..self.dealt() {
+                seat = self.next_dealt(seat);
+                holes[seat].push(deck.draw().unwrap());
+            }
+        }
+        holes
+    }
+
+    /// Burns a card and deals the board of the next street from `deck`,
+    /// and returns the street, or `None` if the hand doesn't go on to one.
+    pub(crate) fn deal_street(&mut self, deck: &mut Deck) -> Option<Street> {
+        let street = match self.street {
+            _ if self.in_hand() < 2 => return None,
+            Street::Preflop => Street::Flop,
+            Street::Flop => Street::Turn,
+            Street::Turn => Street::River,
+            Street::River => return None,
+        };
+        let cards = if street == Street::Flop { 3 } else { 1 };
+
+        deck.discard();
+        self.board.extend(deck.deal(cards).unwrap());
+        self.new_street(street);
+        Some(street)
+    }
+
+    pub(crate) fn new_street(&mut self, street: Street) {
+        self.street = street;
+        self.to_match = 0;
+        self.last_raise = 0;
+        self.raises = 0;
+
+        for seat in self.seats.iter_mut() {
+            seat.bet = 0;
+        }
+        for acted in self.acted.iter_mut() {
+            *acted = false;
+        }
+        for reopened in self.reopened.iter_mut() {
+            *reopened = true;
+        }
+    }
+
+    pub(crate) fn situation(&self, config: &Config, seat: usize) -> Situation {
+        Situation {
+            street: self.street,
+            big_blind: config.big_blind,
+            to_match: self.to_match,
+            committed: self.seats[seat].bet,
+            stack: self.seats[seat].stack,
+            last_raise: self.last_raise,
+            raises: self.raises,
+            pot: self.pot(),
+            reopened: self.reopened[seat],
+        }
+    }
+
+    /// Returns what `seat` may do, if it's their turn.
+    pub(crate) fn legal(&self, config: &Config, seat: usize) -> Legal {
+        config.betting.legal(&self.situation(config, seat))
+    }
+
+    /// Takes `action` for `seat`, if it's legal.
+    pub(crate) fn act(
+        &mut self,
+        config: &Config,
+        seat: usize,
+        action: Action,
+    ) -> Result<(), Error> {
+        let situation = self.situation(config, seat);
+
+        config
+            .betting
+            .validate(&situation, action)
+            .map_err(|reason| Error::IllegalAction {
+                seat,
+                action,
+                reason: Box::new(reason),
+            })?;
+
+        match action {
+            Action::Fold => self.seats[seat].folded = true,
+            Action::Check => (),
+            Action::Call(amount) => {
+                self.put(seat, amount);
+            }
+            Action::Bet(to) | Action::Raise(to) => {
+                let full = config
+                    .betting
+                    .limits(&situation)
+                    .map_or(true, |limits| to >= limits.min);
+
+                self.put(seat, to - situation.committed);
+                self.raise(seat, to, full);
+            }
+        }
+
+        self.acted[seat] = true;
+        self.history.push(Decision {
+            seat,
+            street: self.street,
+            action,
+        });
+
+        Ok(())
+    }
+
+    /// Evaluates the hands of the seats at showdown, given every seat's
+    /// hole cards.
+    pub(crate) fn showdown<C: AsRef<[Card]>>(&self, holes: &[C]) -> Showdown {
+        let seats = self.seats.len();
+        let mut values: Vec<Option<Value>> = vec![None; seats];
+        let mut shown = Vec::new();
+
+        if self.in_hand() > 1 {
+            for seat in (0..seats).filter(|&seat| self.seats[seat].in_hand()) {
+                let cards = holes[seat].as_ref();
+                values[seat] = Some(Hand::evaluate(cards, &self.board));
+                shown.push((seat, cards.to_vec()));
+            }
+        }
+
+        Showdown { values, shown }
+    }
+
+    /// Returns the pots as they stand.
+    pub(crate) fn pots(&self) -> Pots {
+        let mut pots = Pots::new(self.seats.len());
+        for (seat, info) in self.seats.iter().enumerate() {
+            pots.add(seat, info.tot_bet);
+            if !info.in_hand() {
+                pots.fold(seat);
+            }
+        }
+        pots
+    }
+
+    /// Returns the uncalled bet and awards the pots to the best `values`,
+    /// adding what is won to the stacks.
+    pub(crate) fn award(&mut self, button: usize, values: &[Option<Value>]) -> Award {
+        let mut pots = self.pots();
+
+        let returned = pots.return_uncalled();
+        if let Some((seat, amount)) = returned {
+            let info = &mut self.seats[seat];
+            info.stack += amount;
+            info.bet -= cmp::min(amount, info.bet);
+            info.tot_bet -= amount;
+        }
+
+        let won = pots.award(button, values);
+        for (info, &amount) in self.seats.iter_mut().zip(&won) {
+            info.stack += amount;
+        }
+
+        Award {
+            pots,
+            returned,
+            won,
+        }
+    }
+}
@@ -0,0 +1,593 @@
+use super::{Error, Street};
+use crate::player::Action;
+use mimpl::mimpl;
+use std::cmp;
+
+/// Everything a betting structure needs to know about the seat to act.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Situation {
+    pub street: Street,
+    pub big_blind: u32,
+    /// The largest total bet on this street, which must be matched to call.
+    pub to_match: u32,
+    /// What the seat to act has bet on this street.
+    pub committed: u32,
+    /// Chips the seat to act has behind.
+    pub stack: u32,
+    /// Size of the last full bet or raise on this street.
+    /// Preflop, the big blind counts as the opening bet.
+    pub last_raise: u32,
+    /// Number of bets and raises made on this street.
+    /// Preflop, the big blind counts as the opening bet.
+    pub raises: u32,
+    /// All chips in the middle, including bets made on this street.
+    pub pot: u32,
+    /// `false` if the seat has already acted and the only raise since was a
+    /// short all-in, which doesn't reopen the betting.
+    pub reopened: bool,
+}
+
+impl Situation {
+    /// Returns what the seat to act owes to stay in the hand.
+    pub fn to_call(&self) -> u32 {
+        self.to_match.saturating_sub(self.committed)
+    }
+}
+
+/// Smallest and largest amount a bet or raise may go to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    pub min: u32,
+    pub max: u32,
+}
+
+/// The legal actions of the seat to act. Folding is always legal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Legal {
+    pub check: bool,
+    /// What it costs to call, if facing a bet.
+    pub call: Option<u32>,
+    /// What a bet or raise may go to, if allowed.
+    pub raise: Option<Limits>,
+    /// What a pot sized bet or raise goes to, capped by the stack.
+    pub pot: u32,
+    /// `true` if there is a bet on the street, the big blind included, so
+    /// that going further is a raise rather than a bet.
+    pub opened: bool,
+}
+
+impl Legal {
+    /// Returns `true` if `action` is among the legal actions.
+    pub fn allows(&self, action: Action) -> bool {
+        match action {
+            Action::Fold => true,
+            Action::Check => self.check,
+            Action::Call(amount) => self.call == Some(amount),
+            Action::Bet(_) if self.opened => false,
+            Action::Raise(_) if !self.opened => false,
+            Action::Bet(to) | Action::Raise(to) => self
+                .raise
+                .map_or(false, |limits| limits.min <= to && to <= limits.max),
+        }
+    }
+}
+
+/// A set of betting rules.
+///
+/// Implementors only decide how far a bet or raise may go.
+/// Checking, calling and going all-in for less are handled the same for all.
+pub trait Structure {
+    /// Returns the smallest and largest amount a bet or raise may go to,
+    /// ignoring the size of the seat's stack.
+    /// Returns `None` if no more raises are allowed on this street.
+    fn limits(&self, situation: &Situation) -> Option<Limits>;
+
+    /// Returns the legal actions in `situation`.
+    ///
+    /// # Example
+    /// ```
+    /// # use aces_high::table::{structure::*, Street};
+    /// let situation = Situation {
+    ///     street: Street::Flop,
+    ///     big_blind: 10,
+    ///     to_match: 30,
+    ///     committed: 0,
+    ///     stack: 500,
+    ///     last_raise: 30,
+    ///     raises: 1,
+    ///     pot: 90,
+    ///     reopened: true,
+    /// };
+    ///
+    /// let legal = NoLimit.legal(&situation);
+    /// assert!(!legal.check);
+    /// assert_eq!(legal.call, Some(30));
+    /// assert_eq!(legal.raise, Some(Limits { min: 60, max: 500 }));
+    /// assert_eq!(legal.pot, 150);
+    ///
+    /// assert_eq!(PotLimit.legal(&situation).raise, Some(Limits { min: 60, max: 150 }));
+    /// ```
+    fn legal(&self, situation: &Situation) -> Legal {
+        let to_call = situation.to_call();
+        let all_in = situation.committed + situation.stack;
+        let pot = situation.to_match + situation.pot + to_call;
+
+        let raise = if situation.reopened && all_in > situation.to_match {
+            self.limits(situation).map(|limits| {
+                if all_in < limits.min {
+                    // All-in for less than a full raise.
+                    Limits {
+                        min: all_in,
+                        max: all_in,
+                    }
+                } else {
+                    Limits {
+                        min: limits.min,
+                        max: cmp::min(limits.max, all_in),
+                    }
+                }
+            })
+        } else {
+            None
+        };
+
+        Legal {
+            check: to_call == 0,
+            call: if to_call > 0 {
+                Some(cmp::min(to_call, situation.stack))
+            } else {
+                None
+            },
+            raise,
+            pot: cmp::min(pot, all_in),
+            opened: situation.to_match > 0,
+        }
+    }
+
+    /// Checks that `action` is legal in `situation`,
+    /// and returns why if it isn't.
+    fn validate(&self, situation: &Situation, action: Action) -> Result<(), Error> {
+        let legal = self.legal(situation);
+
+        match action {
+            Action::Fold => Ok(()),
+            Action::Check if legal.check => Ok(()),
+            Action::Check => Err(Error::CannotCheck {
+                to_call: situation.to_call(),
+            }),
+            Action::Call(actual) => match legal.call {
+                Some(expected) if expected == actual => Ok(()),
+                Some(expected) => Err(Error::WrongCall { expected, actual }),
+                None => Err(Error::NothingToCall),
+            },
+            Action::Bet(_) if situation.to_match > 0 => Err(Error::CannotBet {
+                to_match: situation.to_match,
+            }),
+            Action::Raise(_) if situation.to_match == 0 => Err(Error::NothingToRaise),
+            Action::Bet(actual) | Action::Raise(actual) => match legal.raise {
+                None => Err(Error::RaiseNotAllowed),
+                Some(Limits { min, .. }) if actual < min => {
+                    Err(Error::RaiseTooSmall { min, actual })
+                }
+                Some(Limits { max, .. }) if actual > max => {
+                    Err(Error::RaiseTooLarge { max, actual })
+                }
+                Some(_) => Ok(()),
+            },
+        }
+    }
+}
+
+/// Returns the smallest amount a no limit or pot limit raise may go to:
+/// the current bet plus the last full raise, and never less than a big blind.
+fn min_raise(situation: &Situation) -> u32 {
+    situation.to_match + cmp::max(situation.last_raise, situation.big_blind)
+}
+
+/// Bets and raises are of a fixed size, with a cap on how many are allowed
+/// on each street.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FixedLimit {
+    /// Size of bets and raises preflop and on the flop.
+    pub small_bet: u32,
+    /// Size of bets and raises on the turn and river.
+    pub big_bet: u32,
+    /// Largest number of bets and raises allowed on each street.
+    pub cap: u32,
+}
+
+impl FixedLimit {
+    /// Constructs the usual structure, with a small bet of one big blind,
+    /// a big bet of two, and a cap of one bet and three raises.
+    pub fn new(big_blind: u32) -> Self {
+        FixedLimit {
+            small_bet: big_blind,
+            big_bet: 2 * big_blind,
+            cap: 4,
+        }
+    }
+}
+
+impl Structure for FixedLimit {
+    fn limits(&self, situation: &Situation) -> Option<Limits> {
+        if situation.raises >= self.cap {
+            return None;
+        }
+
+        let size = match situation.street {
+            Street::Preflop | Street::Flop => self.small_bet,
+            Street::Turn | Street::River => self.big_bet,
+        };
+
+        Some(Limits {
+            min: situation.to_match + size,
+            max: situation.to_match + size,
+        })
+    }
+}
+
+/// Bets and raises may be as large as the pot after calling.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PotLimit;
+
+impl Structure for PotLimit {
+    fn limits(&self, situation: &Situation) -> Option<Limits> {
+        let min = min_raise(situation);
+        let max = situation.to_match + situation.pot + situation.to_call();
+
+        Some(Limits {
+            min,
+            max: cmp::max(min, max),
+        })
+    }
+}
+
+/// Bets and raises may be as large as the stack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NoLimit;
+
+impl Structure for NoLimit {
+    fn limits(&self, situation: &Situation) -> Option<Limits> {
+        Some(Limits {
+            min: min_raise(situation),
+            max: u32::MAX,
+        })
+    }
+}
+
+/// Bets and raises may be of any size within a spread, but a raise must be at
+/// least as large as the last bet or raise on the street.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpreadLimit {
+    pub min: u32,
+    pub max: u32,
+}
+
+impl Structure for SpreadLimit {
+    fn limits(&self, situation: &Situation) -> Option<Limits> {
+        let min = cmp::min(cmp::max(self.min, situation.last_raise), self.max);
+
+        Some(Limits {
+            min: situation.to_match + min,
+            max: situation.to_match + self.max,
+        })
+    }
+}
+
+/// The built-in betting structures, for when one has to be stored or sent
+/// along with the rest of a table's configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Betting {
+    FixedLimit(FixedLimit),
+    PotLimit,
+    NoLimit,
+    SpreadLimit(SpreadLimit),
+}
+
+mimpl!(Default; Betting, || Betting::NoLimit);
+
+impl Structure for Betting {
+    fn limits(&self, situation: &Situation) -> Option<Limits> {
+        match self {
+            Betting::FixedLimit(structure) => structure.limits(situation),
+            Betting::PotLimit => PotLimit.limits(situation),
+            Betting::NoLimit => NoLimit.limits(situation),
+            Betting::SpreadLimit(structure) => structure.limits(situation),
+        }
+    }
+}
+
+#[cfg(test)]
+fn facing(to_match: u32, committed: u32, stack: u32, pot: u32) -> Situation {
+    Situation {
+        street: Street::Flop,
+        big_blind: 10,
+        to_match,
+        committed,
+        stack,
+        last_raise: to_match,
+        raises: if to_match > 0 { 1 } else { 0 },
+        pot,
+        reopened: true,
+    }
+}
+
+#[test]
+fn check_and_bet_when_unopened() {
+    let situation = facing(0, 0, 1_000, 100);
+    let legal = NoLimit.legal(&situation);
+
+    assert!(legal.check);
+    assert_eq!(legal.call, None);
+    assert_eq!(
+        legal.raise,
+        Some(Limits {
+            min: 10,
+            max: 1_000
+        })
+    );
+    assert_eq!(legal.pot, 100);
+
+    assert_eq!(NoLimit.validate(&situation, Action::Check), Ok(()));
+    assert_eq!(NoLimit.validate(&situation, Action::Bet(10)), Ok(()));
+    assert_eq!(
+        NoLimit.validate(&situation, Action::Call(0)),
+        Err(Error::NothingToCall)
+    );
+    assert_eq!(
+        NoLimit.validate(&situation, Action::Raise(50)),
+        Err(Error::NothingToRaise)
+    );
+    assert_eq!(
+        NoLimit.validate(&situation, Action::Bet(5)),
+        Err(Error::RaiseTooSmall { min: 10, actual: 5 })
+    );
+}
+
+#[test]
+fn preflop_big_blind_counts_as_bet() {
+    let situation = Situation {
+        street: Street::Preflop,
+        big_blind: 10,
+        to_match: 10,
+        committed: 5,
+        stack: 995,
+        last_raise: 10,
+        raises: 1,
+        pot: 15,
+        reopened: true,
+    };
+    let legal = NoLimit.legal(&situation);
+
+    assert_eq!(legal.call, Some(5));
+    assert_eq!(
+        legal.raise,
+        Some(Limits {
+            min: 20,
+            max: 1_000
+        })
+    );
+    // Call 5 and then raise the 20 in the middle.
+    assert_eq!(
+        PotLimit.legal(&situation).raise,
+        Some(Limits { min: 20, max: 30 })
+    );
+    assert_eq!(
+        NoLimit.validate(&situation, Action::Bet(20)),
+        Err(Error::CannotBet { to_match: 10 })
+    );
+    assert_eq!(
+        NoLimit.validate(&situation, Action::Check),
+        Err(Error::CannotCheck { to_call: 5 })
+    );
+}
+
+#[test]
+fn wrong_call_amount_is_rejected() {
+    let situation = facing(50, 0, 1_000, 50);
+
+    assert_eq!(NoLimit.validate(&situation, Action::Call(50)), Ok(()));
+    assert_eq!(
+        NoLimit.validate(&situation, Action::Call(40)),
+        Err(Error::WrongCall {
+            expected: 50,
+            actual: 40
+        })
+    );
+}
+
+#[test]
+fn short_stack_calls_all_in() {
+    let situation = facing(50, 0, 30, 50);
+    let legal = NoLimit.legal(&situation);
+
+    assert_eq!(legal.call, Some(30));
+    assert_eq!(legal.raise, None);
+    assert_eq!(
+        NoLimit.validate(&situation, Action::Raise(30)),
+        Err(Error::RaiseNotAllowed)
+    );
+}
+
+#[test]
+fn short_stack_raises_all_in_for_less() {
+    let situation = facing(50, 0, 70, 50);
+    let legal = NoLimit.legal(&situation);
+
+    assert_eq!(legal.raise, Some(Limits { min: 70, max: 70 }));
+    assert_eq!(NoLimit.validate(&situation, Action::Raise(70)), Ok(()));
+    assert_eq!(
+        NoLimit.validate(&situation, Action::Raise(60)),
+        Err(Error::RaiseTooSmall {
+            min: 70,
+            actual: 60
+        })
+    );
+}
+
+#[test]
+fn short_all_in_does_not_reopen() {
+    let situation = Situation {
+        reopened: false,
+        ..facing(70, 50, 1_000, 170)
+    };
+    let legal = NoLimit.legal(&situation);
+
+    assert_eq!(legal.call, Some(20));
+    assert_eq!(legal.raise, None);
+}
+
+#[test]
+fn no_limit_min_raise_follows_last_raise() {
+    // Bet 20, raised to 100: the next raise must be to at least 180.
+    let situation = Situation {
+        last_raise: 80,
+        raises: 2,
+        ..facing(100, 20, 1_000, 140)
+    };
+
+    assert_eq!(
+        NoLimit.legal(&situation).raise,
+        Some(Limits {
+            min: 180,
+            max: 1_020
+        })
+    );
+}
+
+#[test]
+fn pot_limit_max() {
+    // Pot of 100, facing a bet of 50: call 50, then raise 200.
+    let situation = facing(50, 0, 1_000, 150);
+    let legal = PotLimit.legal(&situation);
+
+    assert_eq!(legal.raise, Some(Limits { min: 100, max: 250 }));
+    assert_eq!(legal.pot, 250);
+    assert_eq!(
+        PotLimit.validate(&situation, Action::Raise(251)),
+        Err(Error::RaiseTooLarge {
+            max: 250,
+            actual: 251
+        })
+    );
+
+    // Pot limit is capped by the stack.
+    let situation = facing(50, 0, 200, 150);
+    assert_eq!(
+        PotLimit.legal(&situation).raise,
+        Some(Limits { min: 100, max: 200 })
+    );
+}
+
+#[test]
+fn fixed_limit_sizes_and_cap() {
+    let structure = FixedLimit::new(10);
+
+    let flop = facing(0, 0, 1_000, 40);
+    assert_eq!(
+        structure.legal(&flop).raise,
+        Some(Limits { min: 10, max: 10 })
+    );
+
+    let turn = Situation {
+        street: Street::Turn,
+        ..facing(20, 0, 1_000, 60)
+    };
+    assert_eq!(
+        structure.legal(&turn).raise,
+        Some(Limits { min: 40, max: 40 })
+    );
+    assert_eq!(
+        structure.validate(&turn, Action::Raise(60)),
+        Err(Error::RaiseTooLarge {
+            max: 40,
+            actual: 60
+        })
+    );
+
+    let capped = Situation {
+        raises: 4,
+        ..facing(40, 30, 1_000, 200)
+    };
+    assert_eq!(structure.legal(&capped).raise, None);
+    assert_eq!(structure.legal(&capped).call, Some(10));
+    assert_eq!(
+        structure.validate(&capped, Action::Raise(50)),
+        Err(Error::RaiseNotAllowed)
+    );
+}
+
+#[test]
+fn spread_limit_range() {
+    let structure = SpreadLimit { min: 2, max: 6 };
+
+    let unopened = facing(0, 0, 100, 10);
+    assert_eq!(
+        structure.legal(&unopened).raise,
+        Some(Limits { min: 2, max: 6 })
+    );
+
+    // A raise must be at least the last bet of 4.
+    let situation = Situation {
+        last_raise: 4,
+        ..facing(4, 0, 100, 14)
+    };
+    assert_eq!(
+        structure.legal(&situation).raise,
+        Some(Limits { min: 8, max: 10 })
+    );
+}
+
+#[test]
+fn betting_delegates() {
+    let situation = facing(50, 0, 1_000, 150);
+
+    assert_eq!(
+        Betting::NoLimit.legal(&situation),
+        NoLimit.legal(&situation)
+    );
+    assert_eq!(
+        Betting::PotLimit.legal(&situation),
+        PotLimit.legal(&situation)
+    );
+    assert_eq!(
+        Betting::FixedLimit(FixedLimit::new(10)).legal(&situation),
+        FixedLimit::new(10).legal(&situation)
+    );
+}
+
+#[test]
+fn legal_allows_matches_validate() {
+    let structures = [
+        Betting::NoLimit,
+        Betting::PotLimit,
+        Betting::FixedLimit(FixedLimit::new(10)),
+        Betting::SpreadLimit(SpreadLimit { min: 10, max: 30 }),
+    ];
+    let situations = [
+        facing(0, 0, 100, 30),
+        facing(20, 0, 100, 50),
+        facing(20, 10, 15, 50),
+        facing(60, 20, 45, 100),
+    ];
+
+    for structure in &structures {
+        for situation in &situations {
+            let legal = structure.legal(situation);
+            for amount in 0..200 {
+                for &action in &[
+                    Action::Fold,
+                    Action::Check,
+                    Action::Call(amount),
+                    Action::Bet(amount),
+                    Action::Raise(amount),
+                ] {
+                    assert_eq!(
+                        structure.validate(situation, action).is_ok(),
+                        legal.allows(action)
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,536 @@
+//! Multi-table tournaments, with players moved to keep the tables even.
+use super::{Clock, Error, Schedule};
+use crate::player::{Action, Agent, View};
+use crate::table::{structure::Betting, Summary, Table};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use std::mem;
+use std::time::Duration;
+
+/// Runs a tournament over as many tables as it takes to seat everyone.
+///
+/// Players are numbered by the order of their agents, and drawn to random
+/// seats. After each hand:
+/// - Players out of chips are eliminated. Those busted in the same hand
+///   finish in the order of the chips they started it with.
+/// - Once the players left fit at one table less, the table with the fewest
+///   players is broken, and its players are moved one by one to the table
+///   with the fewest players. When they fit at one table, every table is
+///   broken and the final table is drawn afresh.
+/// - While a table has two players more than another, the player who would
+///   be big blind next at the largest table is moved to the smallest one.
+///
+/// Moved players get an empty seat drawn at random. Everything is drawn
+/// from `seed`, so a tournament is played the same way every time.
+///
+/// # Example
+/// ```
+/// # use aces_high::{bot::*, player::Agent, tournament::{director::*, *}};
+/// let levels = (0..12)
+///     .map(|i| Level { small_blind: 10 << i, big_blind: 20 << i, ..Level::default() })
+///     .collect();
+/// let schedule = Schedule { levels, clock: Clock::Hands(10) };
+/// let agents: Vec<Box<dyn Agent>> = (0..20)
+///     .map(|i| match i % 3 {
+///         0 => Box::new(Maniac) as Box<dyn Agent>,
+///         1 => Box::new(Station),
+///         _ => Box::new(Tag::default()),
+///     })
+///     .collect();
+///
+/// let mut director = Director::new(schedule, agents, 9, 1_500, 7);
+/// director.hand_for_hand = Some(4);
+/// assert_eq!(director.tables().len(), 3);
+///
+/// while !director.is_over() {
+///     director.play()?;
+///     let sizes: Vec<usize> = director.tables().iter().map(|&id| director.seated(id)).collect();
+///     assert!(sizes.iter().max().unwrap() - sizes.iter().min().unwrap() <= 1);
+/// }
+/// assert_eq!(director.tables().len(), 1);
+/// assert_eq!(director.standings().len(), 20);
+/// # Ok::<(), Error>(())
+/// ```
+pub struct Director {
+    pub schedule: Schedule,
+    pub betting: Betting,
+    /// Once this many players are left, such as one more than the places
+    /// paid, every table plays each hand before any other starts.
+    pub hand_for_hand: Option<usize>,
+
+    seats: usize,
+    /// Broken tables are left as `None`, so that the others keep their ids.
+    tables: Vec<Option<Seating>>,
+    rng: StdRng,
+    rounds: u64,
+    elapsed: Duration,
+    /// Players eliminated, the first one out first.
+    out: Vec<usize>,
+    moves: Vec<Move>,
+}
+
+/// A player moved to another table, with their chips.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Move {
+    pub player: usize,
+    /// The table and seat they left.
+    pub from: (usize, usize),
+    /// The table and seat they took.
+    pub to: (usize, usize),
+}
+
+struct Seating {
+    table: Table,
+    /// The player in each seat.
+    players: Vec<Option<usize>>,
+    agents: Vec<Box<dyn Agent>>,
+}
+
+/// Sits in empty seats, which are never dealt in.
+struct Vacant;
+
+impl Agent for Vacant {
+    fn decide(&mut self, _view: &View) -> Action {
+        Action::Fold
+    }
+}
+
+impl Director {
+    /// Constructs a tournament of one player per agent, starting with
+    /// `stack` chips each, at tables of `seats` seats.
+    ///
+    /// # Panics
+    /// Panics if there are fewer than two agents or seats a table.
+    pub fn new(
+        schedule: Schedule,
+        agents: Vec<Box<dyn Agent>>,
+        seats: usize,
+        stack: u32,
+        seed: u64,
+    ) -> Self {
+        assert!(agents.len() >= 2, "a tournament needs two players");
+        assert!(seats >= 2, "a table needs two seats");
+
+        let mut director = Director {
+            schedule,
+            betting: Betting::NoLimit,
+            hand_for_hand: None,
+            seats,
+            tables: Vec::new(),
+            rng: StdRng::seed_from_u64(seed),
+            rounds: 0,
+            elapsed: Duration::default(),
+            out: Vec::new(),
+            moves: Vec::new(),
+        };
+
+        let players = agents.len();
+        let tables = (players + seats - 1) / seats;
+        let mut entrants: Vec<(usize, Box<dyn Agent>)> = agents.into_iter().enumerate().collect();
+        entrants.shuffle(&mut director.rng);
+
+        let mut seatings: Vec<Seating> = (0..tables).map(|_| director.seating()).collect();
+        for (i, (player, agent)) in entrants.into_iter().enumerate() {
+            let seating = &mut seatings[i % tables];
+            let seat = i / tables;
+            seating.players[seat] = Some(player);
+            seating.agents[seat] = agent;
+            seating.table.players_mut()[seat].pot = stack;
+        }
+        director.tables = seatings.into_iter().map(Some).collect();
+        director
+    }
+
+    /// Returns the ids of the tables still playing.
+    pub fn tables(&self) -> Vec<usize> {
+        (0..self.tables.len())
+            .filter(|&id| self.tables[id].is_some())
+            .collect()
+    }
+
+    /// Returns the table `id`, or `None` if it has been broken.
+    pub fn table(&self, id: usize) -> Option<&Table> {
+        self.tables.get(id)?.as_ref().map(|seating| &seating.table)
+    }
+
+    /// Returns the number of players at table `id`.
+    pub fn seated(&self, id: usize) -> usize {
+        self.tables
+            .get(id)
+            .and_then(Option::as_ref)
+            .map_or(0, |seating| seating.players.iter().flatten().count())
+    }
+
+    /// Returns the table and seat of `player`, or `None` once eliminated.
+    pub fn seat_of(&self, player: usize) -> Option<(usize, usize)> {
+        self.tables.iter().enumerate().find_map(|(id, seating)| {
+            let seating = seating.as_ref()?;
+            let seat = seating.players.iter().position(|&p| p == Some(player))?;
+            Some((id, seat))
+        })
+    }
+
+    /// Returns every move made so far, in order.
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Returns the number of players left.
+    pub fn players_left(&self) -> usize {
+        self.tables().into_iter().map(|id| self.seated(id)).sum()
+    }
+
+    /// Returns the index of the level being played.
+    pub fn level(&self) -> usize {
+        self.schedule.level(self.rounds, self.elapsed)
+    }
+
+    /// Moves the virtual clock on, such as for a break.
+    pub fn advance(&mut self, time: Duration) {
+        self.elapsed += time;
+    }
+
+    /// Returns `true` if the tables are playing hand for hand.
+    pub fn is_hand_for_hand(&self) -> bool {
+        self.hand_for_hand.map_or(false, |left| {
+            self.players_left() <= left && self.tables().len() > 1
+        })
+    }
+
+    /// Returns `true` once a single player has every chip.
+    pub fn is_over(&self) -> bool {
+        self.players_left() < 2
+    }
+
+    /// Plays a round of one hand at every table, and returns the id of each
+    /// table played with the summary of its hand.
+    ///
+    /// The tables play in an order drawn at random, and the players are
+    /// eliminated and moved after each hand. Hand for hand, that is done
+    /// once every table has played, so the players busted in the round
+    /// finish in the order of the chips they started their hands with.
+    ///
+    /// Returns an error if the tournament is over, or if a hand couldn't be
+    /// played. Then the table it was played at is left as it was, but the
+    /// hands played before it in the round stand.
+    pub fn play(&mut self) -> Result<Vec<(usize, Summary)>, Error> {
+        if self.is_over() {
+            return Err(Error::Over);
+        }
+
+        let hand_for_hand = self.is_hand_for_hand();
+        let config = self.schedule.levels[self.level()].config(self.betting);
+        let mut order = self.tables();
+        order.shuffle(&mut self.rng);
+
+        let mut played = Vec::new();
+        let mut busted = Vec::new();
+        for id in order {
+            let seating = match &mut self.tables[id] {
+                // Broken after an earlier hand of the round.
+                None => continue,
+                Some(seating) if seating.players.iter().flatten().count() < 2 => continue,
+                Some(seating) => seating,
+            };
+            seating.table.set_config(config);
+            let summary = match seating.table.play(&mut seating.agents) {
+                Ok(summary) => summary,
+                Err(error) => {
+                    // The hands played before this one stand.
+                    self.eliminate(&mut busted);
+                    self.balance();
+                    return Err(error.into());
+                }
+            };
+
+            let players = seating.table.players();
+            busted.extend(
+                (0..players.len())
+                    .filter(|&seat| summary.stacks[seat] > 0 && players[seat].pot == 0)
+                    .map(|seat| (summary.stacks[seat], id, seat)),
+            );
+            played.push((id, summary));
+
+            if !hand_for_hand {
+                self.eliminate(&mut busted);
+                self.balance();
+            }
+        }
+        self.eliminate(&mut busted);
+        self.balance();
+
+        self.rounds += 1;
+        if let Clock::Time { hand, .. } = self.schedule.clock {
+            self.elapsed += hand;
+        }
+        Ok(played)
+    }
+
+    /// Returns the players from first place to last. Players left are
+    /// ordered by their chips, ahead of those eliminated.
+    pub fn standings(&self) -> Vec<usize> {
+        let mut left = Vec::new();
+        for seating in self.tables.iter().flatten() {
+            let players = seating.table.players();
+            for (seat, &player) in seating.players.iter().enumerate() {
+                if let Some(player) = player {
+                    left.push((players[seat].pot, player));
+                }
+            }
+        }
+        left.sort_by_key(|&(chips, player)| (std::cmp::Reverse(chips), player));
+
+        let mut standings: Vec<usize> = left.into_iter().map(|(_, player)| player).collect();
+        standings.extend(self.out.iter().rev());
+        standings
+    }
+
+    /// Eliminates the players of `busted`, as (chips at the start of the
+    /// hand, table, seat), the one who started with less first.
+    fn eliminate(&mut self, busted: &mut Vec<(u32, usize, usize)>) {
+        busted.sort_by_key(|&(chips, ..)| chips);
+        for (_, id, seat) in busted.drain(..) {
+            let seating = self.tables[id].as_mut().unwrap();
+            let player = seating.players[seat].take().unwrap();
+            seating.agents[seat] = Box::new(Vacant);
+            self.out.push(player);
+        }
+    }
+
+    /// Breaks tables and moves players until the tables are even.
+    fn balance(&mut self) {
+        let left = self.players_left();
+        let mut tables = self.tables();
+
+        if tables.len() > 1 && left <= self.seats {
+            self.final_table(tables);
+            return;
+        }
+
+        while tables.len() > 1 && left <= (tables.len() - 1) * self.seats {
+            let broken = *tables
+                .iter()
+                .rev()
+                .min_by_key(|&&id| self.seated(id))
+                .unwrap();
+            tables.retain(|&id| id != broken);
+
+            for seat in 0..self.seats {
+                if self.tables[broken].as_ref().unwrap().players[seat].is_some() {
+                    let to = self.smallest(&tables);
+                    self.transfer((broken, seat), to);
+                }
+            }
+            self.tables[broken] = None;
+        }
+
+        loop {
+            let from = *tables
+                .iter()
+                .max_by_key(|&&id| (self.seated(id), std::cmp::Reverse(id)))
+                .unwrap();
+            let to = self.smallest(&tables);
+            if self.seated(from) <= self.seated(to) + 1 {
+                break;
+            }
+            let seat = self.tables[from]
+                .as_ref()
+                .unwrap()
+                .table
+                .next_big_blind()
+                .unwrap();
+            self.transfer((from, seat), to);
+        }
+    }
+
+    /// Breaks every table in `tables`, and draws their players to seats at
+    /// a new one.
+    fn final_table(&mut self, tables: Vec<usize>) {
+        let mut seating = self.seating();
+        let mut seats: Vec<usize> = (0..self.seats).collect();
+        seats.shuffle(&mut self.rng);
+        let mut seats = seats.into_iter();
+        let id = self.tables.len();
+
+        for from in tables {
+            let mut broken = self.tables[from].take().unwrap();
+            for seat in 0..self.seats {
+                if let Some(player) = broken.players[seat].take() {
+                    let to = seats.next().unwrap();
+                    seating.players[to] = Some(player);
+                    seating.agents[to] = mem::replace(&mut broken.agents[seat], Box::new(Vacant));
+                    seating.table.players_mut()[to].pot = broken.table.players()[seat].pot;
+                    self.moves.push(Move {
+                        player,
+                        from: (from, seat),
+                        to: (id, to),
+                    });
+                }
+            }
+        }
+        self.tables.push(Some(seating));
+    }
+
+    /// Returns the table of `tables` with the fewest players.
+    fn smallest(&self, tables: &[usize]) -> usize {
+        *tables.iter().min_by_key(|&&id| self.seated(id)).unwrap()
+    }
+
+    /// Moves the player in seat `from` to an empty seat drawn at table `to`.
+    fn transfer(&mut self, from: (usize, usize), to: usize) {
+        let (id, seat) = from;
+        let seating = self.tables[id].as_mut().unwrap();
+        let player = seating.players[seat].take().unwrap();
+        let agent = mem::replace(&mut seating.agents[seat], Box::new(Vacant));
+        let chips = mem::take(&mut seating.table.players_mut()[seat].pot);
+
+        let seating = self.tables[to].as_mut().unwrap();
+        let empty: Vec<usize> = (0..seating.players.len())
+            .filter(|&seat| seating.players[seat].is_none())
+            .collect();
+        let new = *empty.choose(&mut self.rng).unwrap();
+        seating.players[new] = Some(player);
+        seating.agents[new] = agent;
+        seating.table.players_mut()[new].pot = chips;
+
+        self.moves.push(Move {
+            player,
+            from,
+            to: (to, new),
+        });
+    }
+
+    /// Returns an empty table, seeded from the director.
+    fn seating(&mut self) -> Seating {
+        let config = self.schedule.levels[self.level()].config(self.betting);
+        Seating {
+            table: Table::new_seeded(config, &vec![0; self.seats], self.rng.gen()),
+            players: vec![None; self.seats],
+            agents: (0..self.seats)
+                .map(|_| -> Box<dyn Agent> { Box::new(Vacant) })
+                .collect(),
+        }
+    }
+}
+
+#[test]
+fn moves_big_blind() {
+    use super::Level;
+    use crate::bot::Station;
+
+    let schedule = Schedule {
+        levels: vec![Level {
+            small_blind: 1,
+            big_blind: 2,
+            ..Level::default()
+        }],
+        clock: Clock::Hands(1),
+    };
+    let agents = (0..13)
+        .map(|_| -> Box<dyn Agent> { Box::new(Station) })
+        .collect();
+    let mut director = Director::new(schedule, agents, 9, 100, 1);
+    assert_eq!((director.seated(0), director.seated(1)), (7, 6));
+    director.play().unwrap();
+    assert!(director.moves().is_empty());
+
+    // Two players bust at the smaller table.
+    let mut busted: Vec<_> = (0..9)
+        .filter(|&seat| director.tables[1].as_ref().unwrap().players[seat].is_some())
+        .take(2)
+        .map(|seat| (100, 1, seat))
+        .collect();
+    let big_blind = director.table(0).unwrap().next_big_blind().unwrap();
+    director.eliminate(&mut busted);
+    director.balance();
+    assert_eq!((director.seated(0), director.seated(1)), (6, 5));
+    assert_eq!(director.moves().len(), 1);
+    assert_eq!(director.moves()[0].from, (0, big_blind));
+    assert_eq!(director.moves()[0].to.0, 1);
+
+    // Chips move with the player.
+    let player = director.moves()[0].player;
+    let (id, seat) = director.seat_of(player).unwrap();
+    assert_eq!((id, seat), director.moves()[0].to);
+    assert!(director.table(1).unwrap().players()[seat].pot > 0);
+}
+
+#[test]
+fn same_seed_same_tournament() {
+    use super::Level;
+    use crate::bot::{Maniac, Tag};
+
+    let run = || {
+        let levels = (0..10)
+            .map(|i| Level {
+                small_blind: 5 << i,
+                big_blind: 10 << i,
+                ..Level::default()
+            })
+            .collect();
+        let schedule = Schedule {
+            levels,
+            clock: Clock::Hands(5),
+        };
+        let agents = (0..30)
+            .map(|i| -> Box<dyn Agent> {
+                match i % 2 {
+                    0 => Box::new(Maniac),
+                    _ => Box::new(Tag::default()),
+                }
+            })
+            .collect();
+        let mut director = Director::new(schedule, agents, 6, 500, 11);
+        director.hand_for_hand = Some(10);
+        while !director.is_over() {
+            director.play().unwrap();
+            let tables = director.tables();
+            assert!(director.players_left() > (tables.len() - 1) * 6);
+        }
+        assert_eq!(director.play().unwrap_err(), Error::Over);
+        (director.standings(), director.moves().to_vec())
+    };
+
+    let (standings, moves) = run();
+    assert_eq!((standings.clone(), moves.clone()), run());
+    let mut sorted = standings;
+    sorted.sort();
+    assert_eq!(sorted, (0..30).collect::<Vec<_>>());
+    assert!(!moves.is_empty());
+}
+
+#[test]
+fn error_keeps_eliminations() {
+    use super::{Illegal, Level};
+    use crate::bot::Station;
+
+    let schedule = Schedule {
+        levels: vec![Level {
+            small_blind: 1,
+            big_blind: 2,
+            ..Level::default()
+        }],
+        clock: Clock::Hands(1),
+    };
+    let agents = (0..4)
+        .map(|_| -> Box<dyn Agent> { Box::new(Station) })
+        .collect();
+    let mut director = Director::new(schedule, agents, 2, 100, 0);
+    director.hand_for_hand = Some(4);
+
+    // Someone at the first table is all in on the blinds and, with this
+    // seed, loses before the second table plays and fails.
+    let short = director.tables[0].as_ref().unwrap().players[0].unwrap();
+    director.tables[0].as_mut().unwrap().table.players_mut()[0].pot = 1;
+    director.tables[1].as_mut().unwrap().agents[0] = Box::new(Illegal);
+
+    assert!(director.play().is_err());
+    assert_eq!(director.players_left(), 3);
+    assert_eq!(director.seat_of(short), None);
+    assert_eq!(director.standings().last(), Some(&short));
+    for seating in director.tables.iter().flatten() {
+        let players = seating.table.players();
+        for (seat, player) in seating.players.iter().enumerate() {
+            assert!(player.is_none() || players[seat].pot > 0);
+        }
+    }
+}
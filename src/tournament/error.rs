@@ -0,0 +1,37 @@
+use crate::table;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A hand couldn't be played.
+    Table(table::Error),
+    /// The tournament has a winner.
+    Over,
+    /// The seat has been eliminated.
+    Eliminated(usize),
+    /// The seat may not rebuy, because the period is over, it has too many
+    /// chips or has rebought too many times.
+    NoRebuy(usize),
+    /// The seat may not take an add-on now, or already has.
+    NoAddOn(usize),
+}
+
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Table(error) => write!(f, "{}", error),
+            Error::Over => write!(f, "the tournament is over"),
+            Error::Eliminated(seat) => write!(f, "seat {} has been eliminated", seat),
+            Error::NoRebuy(seat) => write!(f, "seat {} may not rebuy", seat),
+            Error::NoAddOn(seat) => write!(f, "seat {} may not take an add-on", seat),
+        }
+    }
+}
+
+impl From<table::Error> for Error {
+    fn from(error: table::Error) -> Self {
+        Error::Table(error)
+    }
+}
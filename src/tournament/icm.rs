@@ -0,0 +1,264 @@
+//! The Independent Chip Model, which turns tournament chips into prize
+//! money.
+//!
+//! A player wins with the probability of their share of the chips. Each
+//! place after that goes to one of the players left with the probability of
+//! their share of the chips still in play. A player's equity is the prize of
+//! every place weighted by the probability of finishing in it. Players
+//! without chips finish below everyone else, in no particular order.
+use rand::Rng;
+use std::collections::BTreeMap;
+
+/// What a call of an all-in is worth by ICM, for each way it can go.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Call {
+    /// Equity after folding.
+    pub fold: f64,
+    /// Equity after calling and winning the pot.
+    pub win: f64,
+    /// Equity after calling and losing.
+    pub lose: f64,
+}
+
+/// Returns the equity of each player, with `prizes` paid for the places from
+/// first on, by going through every way the places paid can be filled.
+///
+/// That takes time with the number of ways, so for large fields use
+/// `sampled` instead.
+///
+/// # Example
+/// ```
+/// # use aces_high::tournament::icm;
+/// let equities = icm::equities(&[5_000, 3_000, 2_000], &[50, 30, 20]);
+/// assert!((equities[0] - 38.393).abs() < 0.001);
+/// assert!((equities.iter().sum::<f64>() - 100.0).abs() < 1e-9);
+/// ```
+///
+/// # Panics
+/// Panics if there are more than 64 players.
+pub fn equities(stacks: &[u32], prizes: &[u64]) -> Vec<f64> {
+    assert!(stacks.len() <= 64, "too many players to go through");
+    let mut equities = vec![0.0; stacks.len()];
+
+    // The probability of each set of players taking the places so far.
+    let mut placed = BTreeMap::new();
+    placed.insert(0u64, 1.0);
+
+    for &prize in prizes.iter().take(stacks.len()) {
+        let mut next = BTreeMap::new();
+        for (&mask, &p) in &placed {
+            let left: Vec<usize> = (0..stacks.len()).filter(|&i| mask & 1 << i == 0).collect();
+            let chips: u64 = left.iter().map(|&i| u64::from(stacks[i])).sum();
+
+            for &i in &left {
+                let q = if chips > 0 {
+                    f64::from(stacks[i]) / chips as f64
+                } else {
+                    1.0 / left.len() as f64
+                };
+                if q > 0.0 {
+                    equities[i] += p * q * prize as f64;
+                    *next.entry(mask | 1 << i).or_insert(0.0) += p * q;
+                }
+            }
+        }
+        placed = next;
+    }
+
+    equities
+}
+
+/// Estimates the equity of each player by drawing `samples` finishing
+/// orders, which takes time only with the number of players.
+///
+/// # Example
+/// ```
+/// # use aces_high::tournament::icm;
+/// # use rand::{rngs::StdRng, SeedableRng};
+/// let stacks: Vec<u32> = (1..=200).map(|i| i * 100).collect();
+/// let prizes: Vec<u64> = (1..=20).rev().map(|i| i * 10).collect();
+/// let mut rng = StdRng::seed_from_u64(1);
+/// let equities = icm::sampled(&stacks, &prizes, 2_000, &mut rng);
+/// assert!(equities[199] > equities[0]);
+/// ```
+pub fn sampled<R: Rng + ?Sized>(
+    stacks: &[u32],
+    prizes: &[u64],
+    samples: u32,
+    rng: &mut R,
+) -> Vec<f64> {
+    let mut equities = vec![0.0; stacks.len()];
+    let mut order: Vec<(f64, u32, usize)> = Vec::with_capacity(stacks.len());
+
+    for _ in 0..samples {
+        // Sorting by these keys draws each place in proportion to the chips
+        // of the players left, as the model does.
+        order.clear();
+        order.extend(stacks.iter().enumerate().map(|(i, &stack)| {
+            let u: f64 = 1.0 - rng.gen::<f64>();
+            // Players without chips take the last places, even when `u` is
+            // 1 and the key would be 0 over 0.
+            let key = if stack == 0 {
+                f64::INFINITY
+            } else {
+                -u.ln() / f64::from(stack)
+            };
+            (key, rng.gen(), i)
+        }));
+        order.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap().then(a.1.cmp(&b.1)));
+
+        for (&(.., i), &prize) in order.iter().zip(prizes) {
+            equities[i] += prize as f64;
+        }
+    }
+
+    let samples = f64::from(samples.max(1));
+    equities.iter().map(|equity| equity / samples).collect()
+}
+
+/// Returns what `hero` calling an all-in by `villain` is worth by ICM.
+///
+/// `stacks` are the chips each player has behind before the all-in, and
+/// `pot` the chips already in the middle, blinds and antes included.
+///
+/// # Example
+/// ```
+/// # use aces_high::tournament::icm;
+/// // Close to the bubble, a coin flip isn't worth calling.
+/// let call = icm::call(&[3_000, 3_000, 3_000, 1_000], &[50, 30, 20], 0, 1, 300);
+/// assert!(call.needed() > 0.55);
+/// assert!(call.ev(0.5) < call.fold);
+/// ```
+pub fn call(stacks: &[u32], prizes: &[u64], hero: usize, villain: usize, pot: u32) -> Call {
+    let risk = stacks[hero].min(stacks[villain]);
+    let equity = |winner: usize, loser: usize| {
+        let mut stacks = stacks.to_vec();
+        stacks[winner] += risk + pot;
+        stacks[loser] -= risk;
+        equities(&stacks, prizes)[hero]
+    };
+
+    let mut fold = stacks.to_vec();
+    fold[villain] += pot;
+
+    Call {
+        fold: equities(&fold, prizes)[hero],
+        win: equity(hero, villain),
+        lose: equity(villain, hero),
+    }
+}
+
+impl Call {
+    /// Returns the share of the pot the hand called with must win on
+    /// average for calling to be worth as much as folding.
+    pub fn needed(&self) -> f64 {
+        (self.fold - self.lose) / (self.win - self.lose)
+    }
+
+    /// Returns the equity after calling with a hand which wins the pot with
+    /// probability `win`.
+    pub fn ev(&self, win: f64) -> f64 {
+        win * self.win + (1.0 - win) * self.lose
+    }
+}
+
+/// Proposes a deal splitting the prizes left between the players left by
+/// their equities, with `keep` of the first prize left to play for.
+///
+/// Each share is rounded down, and whatever is left over from rounding
+/// goes to the chip leader.
+///
+/// # Example
+/// ```
+/// # use aces_high::tournament::icm;
+/// let deal = icm::deal(&[6_000, 3_000, 1_000], &[500, 300, 200], 100);
+/// assert_eq!(deal.iter().sum::<u64>(), 900);
+/// assert!(deal[0] > deal[1] && deal[1] > deal[2]);
+/// ```
+///
+/// # Panics
+/// Panics if `keep` is more than the first prize.
+pub fn deal(stacks: &[u32], prizes: &[u64], keep: u64) -> Vec<u64> {
+    let mut prizes = prizes.to_vec();
+    prizes[0] = prizes[0]
+        .checked_sub(keep)
+        .expect("more kept than the first prize");
+    let equities = equities(stacks, &prizes);
+    split(stacks, &prizes, |i| equities[i])
+}
+
+/// Proposes a chip chop: everyone gets the smallest prize left, and the
+/// rest is split in proportion to the chips.
+///
+/// # Example
+/// ```
+/// # use aces_high::tournament::icm;
+/// assert_eq!(icm::chip_chop(&[6_000, 3_000, 1_000], &[500, 300, 200]), [440, 320, 240]);
+/// ```
+pub fn chip_chop(stacks: &[u32], prizes: &[u64]) -> Vec<u64> {
+    let prizes = &prizes[..prizes.len().min(stacks.len())];
+    let last = if prizes.len() < stacks.len() {
+        0
+    } else {
+        prizes.iter().copied().min().unwrap_or(0)
+    };
+    let rest = (prizes.iter().sum::<u64>() - last * stacks.len() as u64) as f64;
+    let total: u64 = stacks.iter().map(|&stack| u64::from(stack)).sum();
+    split(stacks, prizes, |i| {
+        last as f64 + rest * f64::from(stacks[i]) / total as f64
+    })
+}
+
+/// Rounds the shares down, and gives what's left of the prizes to the chip
+/// leader.
+fn split<F: Fn(usize) -> f64>(stacks: &[u32], prizes: &[u64], share: F) -> Vec<u64> {
+    let pool: u64 = prizes.iter().take(stacks.len()).sum();
+    let mut shares: Vec<u64> = (0..stacks.len()).map(|i| share(i) as u64).collect();
+    let leader = (0..stacks.len()).max_by_key(|&i| (stacks[i], std::cmp::Reverse(i)));
+    if let Some(leader) = leader {
+        shares[leader] += pool - shares.iter().sum::<u64>();
+    }
+    shares
+}
+
+#[test]
+fn exact() {
+    let exact = equities(&[1_000, 2_000, 3_000, 4_000], &[500, 300, 200]);
+    let expected = [
+        133.214_285_714,
+        235.873_015_873,
+        294.880_952_381,
+        336.031_746_032,
+    ];
+    for (equity, expected) in exact.iter().zip(&expected) {
+        assert!((equity - expected).abs() < 1e-6, "{}", equity);
+    }
+
+    // Heads up, equity goes by the chips.
+    assert_eq!(equities(&[3, 1], &[70, 30]), [60.0, 40.0]);
+    // Without chips, a player takes the last place.
+    assert_eq!(equities(&[0, 5, 0], &[30, 20, 10]), [15.0, 30.0, 15.0]);
+    assert_eq!(equities(&[7], &[30, 20]), [30.0]);
+}
+
+#[test]
+fn sampled_matches_exact() {
+    use rand::{rngs::mock::StepRng, rngs::StdRng, SeedableRng};
+
+    // Drawing only zeros makes every `u` 1.
+    let zeros = &mut StepRng::new(0, 0);
+    assert_eq!(sampled(&[0, 5], &[30, 20], 1, zeros), [20.0, 30.0]);
+
+    let stacks = [1_000, 2_000, 3_000, 4_000, 500, 0];
+    let prizes = [500, 300, 200, 100];
+    let exact = equities(&stacks, &prizes);
+    let sampled = sampled(&stacks, &prizes, 20_000, &mut StdRng::seed_from_u64(1));
+    for (exact, sampled) in exact.iter().zip(&sampled) {
+        assert!((exact - sampled).abs() < 5.0, "{} {}", exact, sampled);
+    }
+    assert_eq!(sampled[5], 0.0);
+
+    let call = call(&[2_000, 2_000], &[70, 30], 0, 1, 0);
+    assert!((call.needed() - 0.5).abs() < 1e-9);
+    assert_eq!((call.fold, call.win, call.lose), (50.0, 70.0, 30.0));
+}
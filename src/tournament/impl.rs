@@ -0,0 +1,276 @@
+use super::{icm, Clock, Entry, Error, Level, Payouts, Schedule, Tournament};
+use crate::player::Agent;
+use crate::table::{structure::Betting, Config, Summary, Table};
+use std::time::Duration;
+
+impl Level {
+    /// Returns the config of a table playing this level.
+    pub fn config(&self, betting: Betting) -> Config {
+        Config {
+            betting,
+            small_blind: self.small_blind,
+            big_blind: self.big_blind,
+            ante: self.ante,
+            big_blind_ante: self.big_blind_ante,
+        }
+    }
+}
+
+impl Schedule {
+    /// Returns the index of the level played after `hands` hands and
+    /// `elapsed` time on the clock.
+    ///
+    /// # Panics
+    /// Panics if there are no levels, or if a level lasts no hands or no
+    /// time.
+    pub fn level(&self, hands: u64, elapsed: Duration) -> usize {
+        assert!(!self.levels.is_empty(), "a schedule needs a level");
+        let level = match self.clock {
+            Clock::Hands(length) => hands / length,
+            Clock::Time { level, .. } => (elapsed.as_nanos() / level.as_nanos()) as u64,
+        };
+        (level as usize).min(self.levels.len() - 1)
+    }
+}
+
+impl Payouts {
+    /// Constructs `Payouts` with the shares of each place, first place
+    /// first.
+    ///
+    /// # Panics
+    /// Panics if the shares add up to 0.
+    pub fn new(shares: &[u32]) -> Self {
+        assert!(shares.iter().any(|&share| share > 0), "nothing is paid");
+        Payouts {
+            shares: shares.to_vec(),
+        }
+    }
+
+    pub fn winner_takes_all() -> Self {
+        Payouts::new(&[1])
+    }
+
+    /// Returns the number of places paid.
+    pub fn places(&self) -> usize {
+        self.shares.len()
+    }
+
+    /// Splits `pool` between the places paid, first place first.
+    pub fn prizes(&self, pool: u64) -> Vec<u64> {
+        let total: u64 = self.shares.iter().map(|&share| u64::from(share)).sum();
+        let mut prizes: Vec<u64> = self
+            .shares
+            .iter()
+            .map(|&share| (u128::from(pool) * u128::from(share) / u128::from(total)) as u64)
+            .collect();
+        prizes[0] += pool - prizes.iter().sum::<u64>();
+        prizes
+    }
+}
+
+impl Default for Payouts {
+    fn default() -> Self {
+        Payouts::winner_takes_all()
+    }
+}
+
+impl Tournament {
+    /// Constructs a freezeout of `seats` seats starting with `stack` chips
+    /// each, which pays the winner only. The cards dealt are seeded by
+    /// `seed`.
+    pub fn new(schedule: Schedule, seats: usize, stack: u32, seed: u64) -> Self {
+        let betting = Betting::NoLimit;
+        let config = schedule.levels[schedule.level(0, Duration::default())].config(betting);
+
+        Tournament {
+            schedule,
+            betting,
+            buy_in: 0,
+            rebuy: None,
+            add_on: None,
+            payouts: Payouts::default(),
+            table: Table::new_seeded(config, &vec![stack; seats], seed),
+            elapsed: Duration::default(),
+            entries: vec![Entry::default(); seats],
+            busted: Vec::new(),
+            out: Vec::new(),
+        }
+    }
+
+    pub fn table(&self) -> &Table {
+        &self.table
+    }
+
+    /// Returns the index of the level being played.
+    pub fn level(&self) -> usize {
+        self.schedule.level(self.table.hands(), self.elapsed)
+    }
+
+    /// Returns the time on the virtual clock.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Moves the virtual clock on, such as for a break.
+    pub fn advance(&mut self, time: Duration) {
+        self.elapsed += time;
+    }
+
+    /// Returns what `seat` has bought.
+    pub fn entry(&self, seat: usize) -> Entry {
+        self.entries[seat]
+    }
+
+    /// Plays one hand at the stakes of the current level.
+    ///
+    /// Seats who busted in the hand before and didn't rebuy are eliminated
+    /// first. Returns an error, and leaves the tournament as it was, if the
+    /// tournament is over or the hand couldn't be played.
+    pub fn play(&mut self, agents: &mut [Box<dyn Agent>]) -> Result<Summary, Error> {
+        if self.is_over() {
+            return Err(Error::Over);
+        }
+
+        let config = self.schedule.levels[self.level()].config(self.betting);
+        let previous = *self.table.config();
+        self.table.set_config(config);
+        let summary = match self.table.play(agents) {
+            Ok(summary) => summary,
+            Err(error) => {
+                self.table.set_config(previous);
+                return Err(error.into());
+            }
+        };
+
+        self.out.append(&mut self.busted);
+        if let Clock::Time { hand, .. } = self.schedule.clock {
+            self.elapsed += hand;
+        }
+
+        // Whoever started the hand with less finishes below.
+        let players = self.table.players();
+        let mut busted: Vec<usize> = (0..players.len())
+            .filter(|&seat| summary.stacks[seat] > 0 && players[seat].pot == 0)
+            .collect();
+        busted.sort_by_key(|&seat| summary.stacks[seat]);
+        self.busted = busted;
+
+        // Nobody is left to play a rebuy against.
+        if self.alive() < 2 {
+            self.out.append(&mut self.busted);
+        }
+        Ok(summary)
+    }
+
+    /// Buys `seat` the rebuy's chips.
+    pub fn rebuy(&mut self, seat: usize) -> Result<(), Error> {
+        self.check_in(seat)?;
+        let rebuy = self.rebuy.ok_or(Error::NoRebuy(seat))?;
+        let level = self.level();
+        let entry = &mut self.entries[seat];
+        let player = &mut self.table.players_mut()[seat];
+
+        let allowed = level <= rebuy.last_level
+            && player.pot <= rebuy.chips
+            && rebuy.limit.map_or(true, |limit| entry.rebuys < limit);
+        if !allowed {
+            return Err(Error::NoRebuy(seat));
+        }
+
+        player.pot += rebuy.chips;
+        entry.rebuys += 1;
+        self.busted.retain(|&other| other != seat);
+        Ok(())
+    }
+
+    /// Buys `seat` the add-on's chips.
+    pub fn add_on(&mut self, seat: usize) -> Result<(), Error> {
+        self.check_in(seat)?;
+        let add_on = self.add_on.ok_or(Error::NoAddOn(seat))?;
+        let level = self.level();
+        let entry = &mut self.entries[seat];
+        let player = &mut self.table.players_mut()[seat];
+
+        if level != add_on.level || entry.add_on || player.pot == 0 {
+            return Err(Error::NoAddOn(seat));
+        }
+
+        player.pot += add_on.chips;
+        entry.add_on = true;
+        Ok(())
+    }
+
+    /// Returns `true` once a single seat has every chip.
+    pub fn is_over(&self) -> bool {
+        self.alive() < 2
+    }
+
+    /// Returns the seats from first place to last. Seats still playing are
+    /// ordered by their chips, ahead of those out of chips.
+    pub fn standings(&self) -> Vec<usize> {
+        let players = self.table.players();
+        let mut standings: Vec<usize> = (0..players.len())
+            .filter(|&seat| players[seat].pot > 0)
+            .collect();
+        standings.sort_by_key(|&seat| std::cmp::Reverse(players[seat].pot));
+        standings.extend(self.busted.iter().rev());
+        standings.extend(self.out.iter().rev());
+        standings
+    }
+
+    /// Returns every buy-in, rebuy and add-on paid.
+    pub fn prize_pool(&self) -> u64 {
+        let rebuy = self.rebuy.map_or(0, |rebuy| rebuy.cost);
+        let add_on = self.add_on.map_or(0, |add_on| add_on.cost);
+        self.entries
+            .iter()
+            .map(|entry| {
+                self.buy_in
+                    + u64::from(entry.rebuys) * rebuy
+                    + u64::from(entry.add_on as u8) * add_on
+            })
+            .sum()
+    }
+
+    /// Returns the prize of each seat, were the tournament to end with the
+    /// current standings.
+    pub fn prizes(&self) -> Vec<u64> {
+        let mut prizes = vec![0; self.entries.len()];
+        let paid = self.payouts.prizes(self.prize_pool());
+        for (&seat, prize) in self.standings().iter().zip(paid) {
+            prizes[seat] = prize;
+        }
+        prizes
+    }
+
+    /// Returns the prize each seat can expect by ICM, from the chips they
+    /// have now. Seats out of chips get the prize of their place.
+    pub fn icm(&self) -> Vec<f64> {
+        let paid = self.payouts.prizes(self.prize_pool());
+        let stacks: Vec<u32> = self
+            .table
+            .players()
+            .iter()
+            .map(|player| player.pot)
+            .collect();
+        let left = self.alive();
+
+        let mut equities = icm::equities(&stacks, &paid[..paid.len().min(left)]);
+        for (place, &seat) in self.standings().iter().enumerate().skip(left) {
+            equities[seat] = paid.get(place).map_or(0.0, |&prize| prize as f64);
+        }
+        equities
+    }
+
+    fn alive(&self) -> usize {
+        let players = self.table.players();
+        players.iter().filter(|player| player.pot > 0).count()
+    }
+
+    fn check_in(&self, seat: usize) -> Result<(), Error> {
+        if self.out.contains(&seat) {
+            return Err(Error::Eliminated(seat));
+        }
+        Ok(())
+    }
+}
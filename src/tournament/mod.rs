@@ -0,0 +1,255 @@
+//! Tournaments at a single table, from the first level to the payouts.
+pub mod director;
+pub mod error;
+pub mod icm;
+mod r#impl;
+
+pub use error::Error;
+
+use crate::table::{structure::Betting, Table};
+use std::time::Duration;
+
+/// The stakes of one blind level.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Level {
+    pub small_blind: u32,
+    pub big_blind: u32,
+    pub ante: u32,
+    /// If set, the ante is posted once, by the big blind, for everyone.
+    pub big_blind_ante: bool,
+}
+
+/// What moves the blinds up to the next level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Clock {
+    /// A level lasts this many hands.
+    Hands(u64),
+    /// A level lasts `level` on a virtual clock, which moves on by `hand`
+    /// for every hand played, and by `Tournament::advance` for breaks.
+    Time { level: Duration, hand: Duration },
+}
+
+/// The blind levels, played in order. The last one lasts until the end.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Schedule {
+    pub levels: Vec<Level>,
+    pub clock: Clock,
+}
+
+/// Chips bought by a seat that has run low.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rebuy {
+    pub chips: u32,
+    /// Added to the prize pool.
+    pub cost: u64,
+    /// Rebuys are allowed by seats with at most `chips` left, up to and
+    /// including this level.
+    pub last_level: usize,
+    /// Rebuys allowed per seat, or `None` for as many as they like.
+    pub limit: Option<u32>,
+}
+
+/// Chips every seat may buy once, during one level.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AddOn {
+    pub chips: u32,
+    /// Added to the prize pool.
+    pub cost: u64,
+    pub level: usize,
+}
+
+/// How the prize pool is split between the places paid.
+///
+/// Each place gets its share of the pool, in proportion to the sum of the
+/// shares. Chips left over from rounding go to the winner.
+///
+/// # Example
+/// ```
+/// # use aces_high::tournament::Payouts;
+/// let payouts = Payouts::new(&[50, 30, 20]);
+/// assert_eq!(payouts.prizes(1_001), [501, 300, 200]);
+/// assert_eq!(Payouts::winner_takes_all().prizes(10), [10]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Payouts {
+    shares: Vec<u32>,
+}
+
+/// A freezeout or rebuy tournament at one table.
+///
+/// Seats are eliminated when they run out of chips, unless they may still
+/// rebuy: then they have until the next hand to do so. Seats busted in the
+/// same hand finish in the order of the chips they started it with.
+///
+/// # Example
+/// ```
+/// # use aces_high::{bot::*, player::Agent, tournament::*};
+/// # use std::time::Duration;
+/// let levels = vec![
+///     Level { small_blind: 10, big_blind: 20, ..Level::default() },
+///     Level { small_blind: 20, big_blind: 40, ante: 40, big_blind_ante: true },
+/// ];
+/// let schedule = Schedule { levels, clock: Clock::Hands(10) };
+/// let mut tournament = Tournament::new(schedule, 4, 1_000, 7);
+/// tournament.buy_in = 10;
+/// tournament.payouts = Payouts::new(&[65, 35]);
+///
+/// let mut agents: Vec<Box<dyn Agent>> = vec![
+///     Box::new(Maniac), Box::new(Station), Box::new(Tag::default()), Box::new(Maniac),
+/// ];
+/// while !tournament.is_over() {
+///     tournament.play(&mut agents)?;
+/// }
+///
+/// let standings = tournament.standings();
+/// assert_eq!(standings.len(), 4);
+/// assert_eq!(tournament.prizes()[standings[0]], 26);
+/// assert_eq!(tournament.prizes().iter().sum::<u64>(), 40);
+/// assert_eq!(tournament.icm()[standings[0]], 26.0);
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Tournament {
+    pub schedule: Schedule,
+    pub betting: Betting,
+    /// Paid by every seat to enter, into the prize pool.
+    pub buy_in: u64,
+    pub rebuy: Option<Rebuy>,
+    pub add_on: Option<AddOn>,
+    pub payouts: Payouts,
+
+    table: Table,
+    elapsed: Duration,
+    entries: Vec<Entry>,
+    /// Seats out of chips who may still rebuy, in the order they busted.
+    busted: Vec<usize>,
+    /// Seats eliminated, the first one out first.
+    out: Vec<usize>,
+}
+
+/// What a seat has bought.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Entry {
+    pub rebuys: u32,
+    pub add_on: bool,
+}
+
+/// Bets nothing, which no table allows.
+#[cfg(test)]
+struct Illegal;
+
+#[cfg(test)]
+impl crate::player::Agent for Illegal {
+    fn decide(&mut self, _view: &crate::player::View) -> crate::player::Action {
+        crate::player::Action::Bet(0)
+    }
+}
+
+#[test]
+fn rebuys_and_levels() {
+    use crate::bot::Maniac;
+    use crate::history::Blind;
+
+    let level = |big_blind, ante| Level {
+        small_blind: big_blind / 2,
+        big_blind,
+        ante,
+        big_blind_ante: true,
+    };
+    let schedule = Schedule {
+        levels: vec![level(10, 0), level(20, 20), level(40, 40)],
+        clock: Clock::Time {
+            level: Duration::from_secs(600),
+            hand: Duration::from_secs(60),
+        },
+    };
+    assert_eq!(schedule.level(0, Duration::from_secs(599)), 0);
+    assert_eq!(schedule.level(0, Duration::from_secs(1_200)), 2);
+    assert_eq!(schedule.level(0, Duration::from_secs(99_999)), 2);
+
+    let mut tournament = Tournament::new(schedule, 3, 200, 3);
+    tournament.buy_in = 10;
+    tournament.rebuy = Some(Rebuy {
+        chips: 200,
+        cost: 10,
+        last_level: 1,
+        limit: Some(2),
+    });
+    tournament.add_on = Some(AddOn {
+        chips: 300,
+        cost: 10,
+        level: 1,
+    });
+    tournament.payouts = Payouts::new(&[7, 3]);
+    assert_eq!(tournament.add_on(0), Err(Error::NoAddOn(0)));
+
+    let mut agents: Vec<Box<dyn crate::player::Agent>> =
+        vec![Box::new(Maniac), Box::new(Maniac), Box::new(Maniac)];
+    while !tournament.is_over() {
+        let level = tournament.level();
+        let summary = tournament.play(&mut agents).unwrap();
+        if level > 0 {
+            let posts = &tournament.table().last_hand().unwrap().posts;
+            let antes: Vec<_> = posts
+                .iter()
+                .filter(|post| post.blind == Blind::Ante)
+                .collect();
+            assert_eq!(antes.len(), 1);
+            assert!(posts
+                .iter()
+                .any(|post| post.blind == Blind::Big && post.seat == antes[0].seat));
+        }
+        for seat in 0..3 {
+            if summary.stacks[seat] > 0 && tournament.table().players()[seat].pot == 0 {
+                let _ = tournament.rebuy(seat);
+            }
+            if tournament.level() == 1 {
+                let _ = tournament.add_on(seat);
+            }
+        }
+    }
+    assert_eq!(tournament.play(&mut agents).unwrap_err(), Error::Over);
+
+    let mut standings = tournament.standings();
+    let prizes = tournament.prizes();
+    assert_eq!(prizes.iter().sum::<u64>(), tournament.prize_pool());
+    assert!(prizes[standings[0]] > prizes[standings[1]]);
+    assert_eq!(prizes[standings[2]], 0);
+    assert_eq!(
+        tournament.rebuy(standings[2]),
+        Err(Error::Eliminated(standings[2]))
+    );
+
+    let bought = (0..3).map(|seat| tournament.entry(seat)).map(|entry| {
+        assert!(entry.rebuys <= 2);
+        10 * u64::from(entry.rebuys) + 10 * u64::from(entry.add_on as u8)
+    });
+    assert_eq!(tournament.prize_pool(), 30 + bought.sum::<u64>());
+    standings.sort();
+    assert_eq!(standings, [0, 1, 2]);
+}
+
+#[test]
+fn error_keeps_stakes() {
+    use crate::bot::Station;
+    use crate::player::Agent;
+
+    let level = |big_blind| Level {
+        small_blind: big_blind / 2,
+        big_blind,
+        ..Level::default()
+    };
+    let schedule = Schedule {
+        levels: vec![level(10), level(20)],
+        clock: Clock::Hands(1),
+    };
+    let mut tournament = Tournament::new(schedule, 2, 200, 1);
+    let mut agents: Vec<Box<dyn Agent>> = vec![Box::new(Station), Box::new(Station)];
+    tournament.play(&mut agents).unwrap();
+
+    let before = *tournament.table().config();
+    assert_eq!(tournament.level(), 1);
+    agents = vec![Box::new(Illegal), Box::new(Illegal)];
+    assert!(tournament.play(&mut agents).is_err());
+    assert_eq!(*tournament.table().config(), before);
+}